@@ -0,0 +1,155 @@
+//! Checks that `gui` builds without the standard library, see the readme.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    panic::PanicInfo,
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use gui::{
+    event::{Event, EventContext, PointerEvent, PointerId, PointerKind},
+    geometry::{Point, Rect},
+    scene::{Node, Scene},
+    surface::SurfaceId,
+    view::View,
+    Context, ViewNode,
+};
+
+/// Builds a context and dispatches a click, so the tree, views, scene and events are linked into the library.
+///
+/// Returns the number of clicks handled.
+#[no_mangle]
+pub extern "C" fn gui_no_std_check() -> u32 {
+    let mut context = Context::<u32, Widget>::new(0);
+    context.build(|_, scene| {
+        Column(Vec::from([
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Rect::new(0.0, 10.0, 10.0, 10.0),
+        ]))
+        .build(scene);
+    });
+
+    for kind in [PointerKind::Down, PointerKind::Up] {
+        context.pointer(
+            SurfaceId::MAIN,
+            PointerEvent {
+                pointer: PointerId(0),
+                kind,
+                position: Point::new(5.0, 15.0),
+                time: Duration::ZERO,
+            },
+        );
+    }
+
+    *context.data()
+}
+
+struct Column(Vec<Rect>);
+
+impl View<u32> for Column {
+    type State = Rect;
+
+    fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+    where
+        N: From<Self::State>,
+    {
+        let bounds = self
+            .0
+            .iter()
+            .fold(Rect::ZERO, |bounds, rect| bounds.union(rect));
+        let mut node = scene.build_node::<Self, u32>(bounds);
+
+        for &rect in &self.0 {
+            let _ = node.children().build_node::<Self, u32>(rect);
+        }
+
+        node
+    }
+}
+
+struct Widget(Rect);
+
+impl From<Rect> for Widget {
+    fn from(bounds: Rect) -> Self {
+        Widget(bounds)
+    }
+}
+
+impl ViewNode<u32> for Widget {
+    fn rebuild(&mut self) {}
+
+    fn bounds(&self) -> Rect {
+        self.0
+    }
+
+    fn event(&mut self, cx: &mut EventContext<'_, u32>, event: &Event<'_>) -> bool {
+        match event {
+            Event::Pointer(PointerEvent {
+                kind: PointerKind::Up,
+                ..
+            }) => {
+                *cx.data_mut() += 1;
+                true
+            }
+
+            _ => false,
+        }
+    }
+}
+
+/// The size of the heap.
+const HEAP_SIZE: usize = 64 * 1024;
+
+/// An allocator which never frees memory.
+struct BumpAllocator {
+    heap: UnsafeCell<[u8; HEAP_SIZE]>,
+    next: AtomicUsize,
+}
+
+// SAFETY: Allocations are reserved atomically, so each byte of the heap is handed out once.
+unsafe impl Sync for BumpAllocator {}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let heap = self.heap.get() as usize;
+        let mut next = self.next.load(Ordering::Relaxed);
+
+        loop {
+            let start = (heap + next + layout.align() - 1) & !(layout.align() - 1);
+            let end = start - heap + layout.size();
+
+            if end > HEAP_SIZE {
+                return ptr::null_mut();
+            }
+
+            match self
+                .next
+                .compare_exchange_weak(next, end, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return start as *mut u8,
+                Err(current) => next = current,
+            }
+        }
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    heap: UnsafeCell::new([0; HEAP_SIZE]),
+    next: AtomicUsize::new(0),
+};
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
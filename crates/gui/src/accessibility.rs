@@ -0,0 +1,27 @@
+//! Semantic information about nodes.
+//!
+//! Nodes describe what they are with a [`Role`] and a label through [`ViewNode::role`](crate::ViewNode::role) and
+//! [`ViewNode::label`](crate::ViewNode::label). This information is used by assistive technologies and to find
+//! nodes in tests.
+
+/// What a node represents to the user.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Role {
+    /// The node has no semantic meaning, such as a node which only lays out it's children.
+    #[default]
+    None,
+    Window,
+    Group,
+    Label,
+    Button,
+    CheckBox,
+    TextInput,
+    Image,
+    List,
+    ListItem,
+    ScrollView,
+    Menu,
+    MenuItem,
+    Tooltip,
+}
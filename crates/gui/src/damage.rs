@@ -0,0 +1,274 @@
+//! Tracking of the areas of a surface which need to be repainted.
+//!
+//! The [`DamageTracker`] remembers the bounds of every node at the time the tree was last painted. Comparing
+//! this with the current bounds of each node gives the area which changed since the last paint:
+//!
+//! - Nodes which were moved or resized damage the old and new bounds.
+//! - Nodes which were removed damage the old bounds.
+//! - Nodes which were added damage the new bounds.
+//! - Nodes which were [marked](DamageTracker::mark) damage the old and new bounds.
+//!
+//! The resulting [`Damage`] may be used to scissor painting and to present only part of a surface, such as with
+//! `EGL_KHR_swap_buffers_with_damage`.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use crate::{
+    geometry::Rect,
+    tree::{NodeId, RetainedTree},
+};
+
+/// The maximum number of rectangles in a [`Damage`] before all the rectangles are merged into one.
+///
+/// Presenting many small rectangles is usually slower than presenting one larger rectangle.
+const MAX_RECTS: usize = 8;
+
+/// Tracks which nodes changed visually since the last paint.
+#[derive(Debug, Default)]
+pub struct DamageTracker {
+    /// The bounds of each node when the tree was last painted.
+    painted: BTreeMap<NodeId, Rect>,
+    /// Nodes whose contents changed since the last paint.
+    marked: BTreeSet<NodeId>,
+    /// Extra areas which were damaged.
+    rects: Vec<Rect>,
+    /// Whether the entire surface is damaged.
+    full: bool,
+}
+
+impl DamageTracker {
+    /// Creates a damage tracker.
+    ///
+    /// The entire surface is considered damaged until the first paint.
+    pub fn new() -> Self {
+        Self {
+            full: true,
+            ..Default::default()
+        }
+    }
+
+    /// Marks the contents of a node as changed.
+    pub fn mark(&mut self, id: NodeId) {
+        self.marked.insert(id);
+    }
+
+    /// Marks an area of the surface as damaged.
+    pub fn add_rect(&mut self, rect: Rect) {
+        if !rect.is_empty() {
+            self.rects.push(rect);
+        }
+    }
+
+    /// Marks the entire surface as damaged.
+    ///
+    /// This should be used when the contents of the surface were lost or the surface was resized.
+    pub fn mark_all(&mut self) {
+        self.full = true;
+    }
+
+    /// Computes the damage of a surface with the given bounds.
+    ///
+    /// The surface displays `root` and it's descendants.
+    pub fn damage<N, F>(
+        &self,
+        tree: &RetainedTree<N>,
+        root: Option<NodeId>,
+        surface: Rect,
+        bounds: F,
+    ) -> Damage
+    where
+        F: Fn(&N) -> Rect,
+    {
+        if self.full {
+            return Damage {
+                rects: Vec::from([surface]),
+            };
+        }
+
+        let mut rects = self.rects.clone();
+        let mut visited = 0;
+
+        for (id, node) in root.into_iter().flat_map(|root| tree.descendants(root)) {
+            let current = bounds(node);
+
+            match self.painted.get(&id) {
+                Some(painted) => {
+                    visited += 1;
+
+                    if self.marked.contains(&id) || painted != &current {
+                        rects.push(*painted);
+                        rects.push(current);
+                    }
+                }
+
+                // The node was added.
+                None => rects.push(current),
+            }
+        }
+
+        // Only look for removed nodes if some of the painted nodes were not visited.
+        if visited != self.painted.len() {
+            rects.extend(
+                self.painted
+                    .iter()
+                    .filter(|(&id, _)| tree.get(id).is_none())
+                    .map(|(_, &rect)| rect),
+            );
+        }
+
+        Damage::from_rects(rects, surface)
+    }
+
+    /// Records the current bounds of `root` and it's descendants and clears the damage.
+    ///
+    /// This should be called after the surface was painted.
+    pub fn painted<N, F>(&mut self, tree: &RetainedTree<N>, root: Option<NodeId>, bounds: F)
+    where
+        F: Fn(&N) -> Rect,
+    {
+        self.painted.clear();
+        self.painted.extend(
+            root.into_iter()
+                .flat_map(|root| tree.descendants(root))
+                .map(|(id, node)| (id, bounds(node))),
+        );
+        self.marked.clear();
+        self.rects.clear();
+        self.full = false;
+    }
+}
+
+/// The areas of a surface which need to be repainted.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Damage {
+    rects: Vec<Rect>,
+}
+
+impl Damage {
+    /// Returns the damaged rectangles.
+    ///
+    /// The rectangles do not overlap.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Returns whether nothing needs to be repainted.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Returns the smallest rectangle containing all the damage.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.rects.iter().copied().reduce(|a, b| a.union(&b))
+    }
+
+    /// Converts the damage from logical units to physical pixels.
+    ///
+    /// Each rectangle is expanded to whole pixels, so every pixel partially covered by the damage is included.
+    pub fn to_physical(&self, scale_factor: f32) -> Damage {
+        let rects = self
+            .rects
+            .iter()
+            .map(|rect| rect.scale(scale_factor).round_out())
+            .collect::<Vec<_>>();
+
+        // Expanding may cause rectangles to overlap, so merge them again.
+        match rects.iter().copied().reduce(|a, b| a.union(&b)) {
+            Some(bounds) => Damage::from_rects(rects, bounds),
+            None => Damage::default(),
+        }
+    }
+
+    fn from_rects(rects: Vec<Rect>, surface: Rect) -> Self {
+        let mut merged = Vec::<Rect>::with_capacity(rects.len());
+
+        for rect in rects.iter().filter_map(|rect| rect.intersect(&surface)) {
+            let mut rect = rect;
+
+            // Merge with any overlapping rectangles. Merging may cause the rectangle to overlap rectangles that
+            // were already checked, so repeat until no more rectangles overlap.
+            while let Some(index) = merged
+                .iter()
+                .position(|other| other.intersect(&rect).is_some())
+            {
+                rect = rect.union(&merged.swap_remove(index));
+            }
+
+            merged.push(rect);
+        }
+
+        if merged.len() > MAX_RECTS {
+            let bounds = merged.iter().copied().reduce(|a, b| a.union(&b)).unwrap();
+            merged.clear();
+            merged.push(bounds);
+        }
+
+        Self { rects: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::Rect, tree::RetainedTree};
+
+    use super::DamageTracker;
+
+    const SURFACE: Rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+    #[test]
+    fn moved_and_removed() {
+        let mut tree = RetainedTree::new();
+        let mut root = tree.set_root(SURFACE);
+        let root_id = root.id();
+        let a = root.push_child(Rect::new(0.0, 0.0, 10.0, 10.0)).id();
+        let b = root.push_child(Rect::new(50.0, 50.0, 10.0, 10.0)).id();
+
+        let mut tracker = DamageTracker::new();
+        assert_eq!(
+            tracker
+                .damage(&tree, Some(root_id), SURFACE, |r| *r)
+                .rects(),
+            [SURFACE]
+        );
+
+        tracker.painted(&tree, Some(root_id), |r| *r);
+        assert!(tracker
+            .damage(&tree, Some(root_id), SURFACE, |r| *r)
+            .is_empty());
+
+        // Moving a node damages both the old and new area, which overlap.
+        *tree.get_mut(a).unwrap() = Rect::new(5.0, 0.0, 10.0, 10.0);
+        assert_eq!(
+            tracker
+                .damage(&tree, Some(root_id), SURFACE, |r| *r)
+                .rects(),
+            [Rect::new(0.0, 0.0, 15.0, 10.0)]
+        );
+
+        tracker.painted(&tree, Some(root_id), |r| *r);
+        tracker.mark(b);
+        assert_eq!(
+            tracker
+                .damage(&tree, Some(root_id), SURFACE, |r| *r)
+                .rects(),
+            [Rect::new(50.0, 50.0, 10.0, 10.0)]
+        );
+
+        // Replacing the tree damages the bounds of both the removed and the added nodes.
+        tracker.painted(&tree, Some(root_id), |r| *r);
+        let root_id = tree.set_root(Rect::new(90.0, 90.0, 20.0, 20.0)).id();
+
+        let damage = tracker.damage(&tree, Some(root_id), SURFACE, |r| *r);
+        assert_eq!(damage.rects(), [SURFACE]);
+
+        // Nodes of other surfaces do not cause damage.
+        tracker.painted(&tree, Some(root_id), |r| *r);
+        tree.push_root(SURFACE);
+        assert!(tracker
+            .damage(&tree, Some(root_id), SURFACE, |r| *r)
+            .is_empty());
+    }
+}
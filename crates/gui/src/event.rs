@@ -0,0 +1,169 @@
+//! Events delivered to nodes.
+
+use core::time::Duration;
+
+use crate::{
+    damage::DamageTracker,
+    geometry::Point,
+    gesture::{Gesture, Phase},
+    history::{Command, History},
+    ime::ImeEvent,
+    transfer::{Clipboard, DragEvent},
+    tree::NodeId,
+};
+
+/// An event delivered to a node.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Event<'a> {
+    /// A raw pointer event.
+    ///
+    /// Most nodes should prefer handling [`Event::Gesture`]. This is useful for nodes which need precise
+    /// input, such as a drawing surface.
+    Pointer(PointerEvent),
+
+    /// A gesture recognized by the node's [`GestureArena`](crate::gesture::GestureArena).
+    Gesture(Gesture),
+
+    /// A scroll wheel or touchpad was scrolled.
+    ///
+    /// If the node under the pointer does not handle the event, the event is delivered to it's parent.
+    Scroll(ScrollEvent),
+
+    /// A drag and drop event.
+    Drag(DragEvent<'a>),
+
+    /// The node gained or lost focus.
+    Focus { focused: bool },
+
+    /// An input method event.
+    ///
+    /// This is only delivered to the focused node.
+    Ime(ImeEvent<'a>),
+}
+
+/// Identifies a pointer, such as a mouse or a finger on a touch screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointerId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerKind {
+    /// The pointer was pressed.
+    Down,
+
+    Move,
+
+    /// The pointer was released.
+    Up,
+
+    /// The pointer was lost, such as the host taking over a touch sequence.
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    pub pointer: PointerId,
+    pub kind: PointerKind,
+    pub position: Point,
+    /// When the event occurred.
+    ///
+    /// The time is relative to an arbitrary point chosen by the host, but must increase monotonically.
+    pub time: Duration,
+}
+
+/// The device which produced a [`ScrollEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollSource {
+    /// A scroll wheel which scrolls in steps.
+    Wheel,
+
+    /// A touchpad, which scrolls continuously and may continue scrolling after the fingers are lifted.
+    Touchpad,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollEvent {
+    /// The position of the pointer.
+    pub position: Point,
+    /// The distance to scroll.
+    ///
+    /// Positive values scroll towards the end of the content, revealing content below or to the right. The host
+    /// converts steps of a scroll wheel to a distance, such as the height of three lines of text.
+    pub delta: Point,
+    pub source: ScrollSource,
+    /// The phase of a touchpad scroll.
+    ///
+    /// Touchpad scrolls begin when the fingers touch the touchpad and end when the fingers are lifted. Scroll
+    /// wheel events are always [`Phase::Changed`].
+    pub phase: Phase,
+    /// When the event occurred.
+    pub time: Duration,
+}
+
+/// The context an event is handled in.
+///
+/// This provides access to the data of the [`Context`](crate::Context) and services such as the clipboard.
+pub struct EventContext<'a, T> {
+    pub(crate) id: NodeId,
+    pub(crate) data: &'a mut T,
+    pub(crate) clipboard: &'a mut dyn Clipboard,
+    pub(crate) damage: &'a mut DamageTracker,
+    pub(crate) focus: &'a mut Option<NodeId>,
+    pub(crate) history: &'a mut History<T>,
+}
+
+impl<T> EventContext<'_, T> {
+    /// Returns the id of the node handling the event.
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    pub fn data(&self) -> &T {
+        self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut T {
+        self.data
+    }
+
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        self.clipboard
+    }
+
+    /// Applies an undoable command to the data.
+    ///
+    /// The node handling the event is marked as damaged when the command is undone or redone.
+    pub fn execute<C>(&mut self, command: C)
+    where
+        C: Command<T> + 'static,
+    {
+        self.history.execute(self.data, command, Some(self.id));
+    }
+
+    /// Begins grouping the commands executed afterwards into one undoable transaction.
+    ///
+    /// See [`History::begin_transaction`].
+    pub fn begin_transaction(&mut self) {
+        self.history.begin_transaction();
+    }
+
+    /// Ends a transaction begun with [`EventContext::begin_transaction`].
+    pub fn commit_transaction(&mut self) {
+        self.history.commit_transaction();
+    }
+
+    /// Marks the node handling the event as visually changed.
+    pub fn mark_damaged(&mut self) {
+        self.damage.mark(self.id);
+    }
+
+    /// Gives focus to the node handling the event.
+    pub fn request_focus(&mut self) {
+        *self.focus = Some(self.id);
+    }
+
+    /// Returns whether the node handling the event has focus.
+    pub fn has_focus(&self) -> bool {
+        *self.focus == Some(self.id)
+    }
+}
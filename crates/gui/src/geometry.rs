@@ -0,0 +1,166 @@
+//! Geometry types used to describe the position and size of nodes.
+//!
+//! Nodes are laid out in logical units. A surface with a [scale factor](crate::Context::scale_factor) of 2 has two
+//! physical pixels per logical unit on each axis, and the scale factor may be fractional, such as 1.25. Positions
+//! are converted to physical pixels with [`Rect::scale`] and snapped to whole pixels with [`Rect::snap`] when
+//! painting.
+
+/// A point in 2D space.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Multiplies the point by a scale factor, such as to convert between logical units and physical pixels.
+    pub fn scale(&self, factor: f32) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// A 2D size.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    pub const fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+}
+
+/// An axis aligned rectangle.
+///
+/// The origin of the rectangle is the top left corner.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub const fn from_origin_size(origin: Point, size: Size) -> Self {
+        Self::new(origin.x, origin.y, size.width, size.height)
+    }
+
+    pub fn origin(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    pub fn center(&self) -> Point {
+        Point::new(self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Returns whether the rectangle has no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.x && point.x < self.right() && point.y >= self.y && point.y < self.bottom()
+    }
+
+    /// Returns the area shared by both rectangles.
+    ///
+    /// Returns [`None`] if the rectangles do not overlap.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        let rect = Rect::new(x, y, right - x, bottom - y);
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+
+        Rect::new(x, y, right - x, bottom - y)
+    }
+
+    /// Returns the rectangle moved by an offset.
+    pub fn translate(&self, x: f32, y: f32) -> Rect {
+        Rect::new(self.x + x, self.y + y, self.width, self.height)
+    }
+
+    /// Multiplies the rectangle by a scale factor, such as to convert between logical units and physical pixels.
+    pub fn scale(&self, factor: f32) -> Rect {
+        Rect::new(
+            self.x * factor,
+            self.y * factor,
+            self.width * factor,
+            self.height * factor,
+        )
+    }
+
+    /// Rounds each edge of the rectangle to the nearest whole number.
+    ///
+    /// Edges are rounded rather than the size, so rectangles which share an edge still share the edge after
+    /// snapping.
+    pub fn snap(&self) -> Rect {
+        let x = libm::roundf(self.x);
+        let y = libm::roundf(self.y);
+        Rect::new(
+            x,
+            y,
+            libm::roundf(self.right()) - x,
+            libm::roundf(self.bottom()) - y,
+        )
+    }
+
+    /// Returns the smallest rectangle with whole number edges containing the rectangle.
+    pub fn round_out(&self) -> Rect {
+        let x = libm::floorf(self.x);
+        let y = libm::floorf(self.y);
+        Rect::new(
+            x,
+            y,
+            libm::ceilf(self.right()) - x,
+            libm::ceilf(self.bottom()) - y,
+        )
+    }
+}
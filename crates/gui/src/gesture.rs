@@ -0,0 +1,1044 @@
+//! Gesture recognition.
+//!
+//! A [`Recognizer`] consumes a stream of [`PointerEvent`]s and recognizes a semantic [`Gesture`], such as a tap or
+//! a pan. Multiple recognizers may be interested in the same pointer events; a [`GestureArena`] decides which
+//! recognizer wins:
+//!
+//! - The first recognizer to recognize a gesture wins and every other recognizer is cancelled.
+//! - A recognizer may be required to wait for another recognizer to fail before it may win. For example a tap
+//!   must wait for a double tap to fail, otherwise a double tap would never be recognized.
+//!
+//! Some gestures depend on time passing without any events, such as a long press. The host must call
+//! [`GestureArena::tick`] when the [deadline](GestureArena::deadline) is reached.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
+
+use crate::{
+    event::{PointerEvent, PointerId, PointerKind},
+    geometry::Point,
+};
+
+/// The distance a pointer may move before a tap or long press fails and a pan begins.
+pub const DEFAULT_SLOP: f32 = 8.0;
+
+/// A recognized gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap {
+        position: Point,
+    },
+
+    DoubleTap {
+        position: Point,
+    },
+
+    LongPress {
+        position: Point,
+    },
+
+    /// A single pointer was dragged.
+    Pan {
+        phase: Phase,
+        position: Point,
+        /// The distance moved since the pan began.
+        translation: Point,
+        /// The velocity in units per second.
+        velocity: Point,
+    },
+
+    /// Two pointers were moved closer together or further apart.
+    Pinch {
+        phase: Phase,
+        /// The point between both pointers.
+        center: Point,
+        /// The ratio of the current distance between the pointers to the initial distance.
+        scale: f32,
+    },
+
+    /// A single pointer was quickly moved in one direction and released.
+    Swipe {
+        direction: SwipeDirection,
+        /// The velocity in units per second.
+        velocity: Point,
+    },
+}
+
+/// The phase of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Began,
+    Changed,
+    Ended,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// The result of a recognizer processing an event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    /// The gesture may still be recognized.
+    Possible,
+
+    /// The gesture cannot be recognized from the events.
+    Failed,
+
+    /// A continuous gesture began or changed and more events are expected.
+    Active(Gesture),
+
+    /// The gesture was recognized or a continuous gesture ended.
+    ///
+    /// The recognizer expects no more events.
+    Recognized(Gesture),
+}
+
+/// Recognizes a gesture from pointer events.
+pub trait Recognizer {
+    fn pointer(&mut self, event: &PointerEvent) -> Status;
+
+    /// Notifies the recognizer that time has passed.
+    fn tick(&mut self, time: Duration) -> Status {
+        let _ = time;
+        Status::Possible
+    }
+
+    /// The time at which the recognizer expects [`Recognizer::tick`] to be called.
+    fn deadline(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Resets the recognizer to handle a new sequence of events.
+    fn reset(&mut self);
+}
+
+/// Identifies a recognizer in a [`GestureArena`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecognizerId(usize);
+
+/// Resolves conflicts between recognizers competing for the same pointer events.
+#[derive(Default)]
+pub struct GestureArena {
+    members: Vec<Member>,
+    /// The number of pointers currently down.
+    pointers: usize,
+}
+
+struct Member {
+    recognizer: Box<dyn Recognizer>,
+    state: MemberState,
+    /// Recognizers which must fail before this recognizer may win.
+    requires_failure_of: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemberState {
+    Possible,
+    /// The recognizer recognized a gesture but must wait for other recognizers to fail.
+    Waiting {
+        gesture: Gesture,
+        finished: bool,
+    },
+    /// The recognizer won and is handling a continuous gesture.
+    Active,
+    /// The recognizer failed, was cancelled or finished recognizing a gesture.
+    Done,
+}
+
+impl GestureArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a recognizer to the arena.
+    pub fn add<R>(&mut self, recognizer: R) -> RecognizerId
+    where
+        R: Recognizer + 'static,
+    {
+        self.members.push(Member {
+            recognizer: Box::new(recognizer),
+            state: MemberState::Possible,
+            requires_failure_of: Vec::new(),
+        });
+
+        RecognizerId(self.members.len() - 1)
+    }
+
+    /// Requires the `other` recognizer to fail before `recognizer` may win.
+    pub fn require_failure(&mut self, recognizer: RecognizerId, other: RecognizerId) {
+        self.members[recognizer.0].requires_failure_of.push(other.0);
+    }
+
+    /// Returns whether no recognizer is interested in further events.
+    pub fn is_finished(&self) -> bool {
+        self.members
+            .iter()
+            .all(|member| member.state == MemberState::Done)
+    }
+
+    /// Returns the earliest time any recognizer expects [`GestureArena::tick`] to be called.
+    pub fn deadline(&self) -> Option<Duration> {
+        self.members
+            .iter()
+            .filter(|member| member.state == MemberState::Possible)
+            .filter_map(|member| member.recognizer.deadline())
+            .min()
+    }
+
+    /// Processes a pointer event, returning any gestures which were recognized.
+    pub fn pointer(&mut self, event: &PointerEvent) -> Vec<Gesture> {
+        match event.kind {
+            PointerKind::Down => {
+                // Start a new sequence of events.
+                if self.pointers == 0 && self.is_finished() {
+                    self.reset();
+                }
+
+                self.pointers += 1;
+            }
+
+            PointerKind::Up | PointerKind::Cancel => {
+                self.pointers = self.pointers.saturating_sub(1);
+            }
+
+            PointerKind::Move => (),
+        }
+
+        self.update(|recognizer| recognizer.pointer(event))
+    }
+
+    /// Notifies the recognizers that time has passed, returning any gestures which were recognized.
+    pub fn tick(&mut self, time: Duration) -> Vec<Gesture> {
+        self.update(|recognizer| recognizer.tick(time))
+    }
+
+    /// Resets every recognizer.
+    pub fn reset(&mut self) {
+        for member in &mut self.members {
+            member.recognizer.reset();
+            member.state = MemberState::Possible;
+        }
+    }
+
+    fn update<F>(&mut self, f: F) -> Vec<Gesture>
+    where
+        F: Fn(&mut dyn Recognizer) -> Status,
+    {
+        let mut gestures = Vec::new();
+
+        for index in 0..self.members.len() {
+            let member = &mut self.members[index];
+
+            // Waiting recognizers have already recognized their gesture.
+            if !matches!(member.state, MemberState::Possible | MemberState::Active) {
+                continue;
+            }
+
+            let status = f(&mut *member.recognizer);
+            self.resolve(index, status, &mut gestures);
+        }
+
+        gestures
+    }
+
+    fn resolve(&mut self, index: usize, status: Status, gestures: &mut Vec<Gesture>) {
+        let (gesture, finished) = match status {
+            Status::Possible => return,
+
+            Status::Failed => {
+                self.members[index].state = MemberState::Done;
+                self.release_waiting(gestures);
+                return;
+            }
+
+            Status::Active(gesture) => (gesture, false),
+            Status::Recognized(gesture) => (gesture, true),
+        };
+
+        let member = &mut self.members[index];
+
+        // The recognizer already won.
+        if member.state == MemberState::Active {
+            gestures.push(gesture);
+
+            if finished {
+                member.state = MemberState::Done;
+            }
+
+            return;
+        }
+
+        if self.is_blocked(index) {
+            self.members[index].state = MemberState::Waiting { gesture, finished };
+            return;
+        }
+
+        self.win(index, gesture, finished, gestures);
+    }
+
+    /// Returns whether any recognizer that must fail before the recognizer may win has not failed yet.
+    fn is_blocked(&self, index: usize) -> bool {
+        self.members[index]
+            .requires_failure_of
+            .iter()
+            .any(|&other| {
+                matches!(
+                    self.members[other].state,
+                    MemberState::Possible | MemberState::Waiting { .. }
+                )
+            })
+    }
+
+    fn win(&mut self, index: usize, gesture: Gesture, finished: bool, gestures: &mut Vec<Gesture>) {
+        // Cancel every other competing recognizer.
+        for (other, member) in self.members.iter_mut().enumerate() {
+            if other != index && member.state != MemberState::Done {
+                member.recognizer.reset();
+                member.state = MemberState::Done;
+            }
+        }
+
+        self.members[index].state = if finished {
+            MemberState::Done
+        } else {
+            MemberState::Active
+        };
+
+        gestures.push(gesture);
+    }
+
+    /// Lets waiting recognizers win if the recognizers they wait on have failed.
+    fn release_waiting(&mut self, gestures: &mut Vec<Gesture>) {
+        for index in 0..self.members.len() {
+            if let MemberState::Waiting { gesture, finished } = self.members[index].state {
+                if !self.is_blocked(index) {
+                    self.win(index, gesture, finished, gestures);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Recognizes a single tap.
+#[derive(Debug)]
+pub struct Tap {
+    /// How far the pointer may move before the tap fails.
+    pub slop: f32,
+    /// How long the pointer may be down before the tap fails.
+    pub max_duration: Duration,
+    down: Option<(PointerId, Point, Duration)>,
+}
+
+impl Default for Tap {
+    fn default() -> Self {
+        Self {
+            slop: DEFAULT_SLOP,
+            max_duration: Duration::from_millis(500),
+            down: None,
+        }
+    }
+}
+
+impl Recognizer for Tap {
+    fn pointer(&mut self, event: &PointerEvent) -> Status {
+        match (event.kind, self.down) {
+            (PointerKind::Down, None) => {
+                self.down = Some((event.pointer, event.position, event.time));
+                Status::Possible
+            }
+
+            (PointerKind::Move, Some((pointer, start, _))) if pointer == event.pointer => {
+                if exceeds(start, event.position, self.slop) {
+                    Status::Failed
+                } else {
+                    Status::Possible
+                }
+            }
+
+            (PointerKind::Up, Some((pointer, _, time))) if pointer == event.pointer => {
+                if event.time.saturating_sub(time) <= self.max_duration {
+                    Status::Recognized(Gesture::Tap {
+                        position: event.position,
+                    })
+                } else {
+                    Status::Failed
+                }
+            }
+
+            // A second pointer or a cancellation.
+            _ => Status::Failed,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.down = None;
+    }
+}
+
+/// Recognizes two taps in quick succession.
+#[derive(Debug)]
+pub struct DoubleTap {
+    /// How far the pointer may move before the double tap fails.
+    pub slop: f32,
+    /// The maximum time between releasing the first tap and pressing the second tap.
+    pub interval: Duration,
+    first: Option<Point>,
+    down: Option<PointerId>,
+    /// When the first tap was released.
+    released: Option<Duration>,
+}
+
+impl Default for DoubleTap {
+    fn default() -> Self {
+        Self {
+            slop: DEFAULT_SLOP,
+            interval: Duration::from_millis(300),
+            first: None,
+            down: None,
+            released: None,
+        }
+    }
+}
+
+impl Recognizer for DoubleTap {
+    fn pointer(&mut self, event: &PointerEvent) -> Status {
+        match event.kind {
+            PointerKind::Down if self.down.is_none() => {
+                match (self.first, self.released) {
+                    (None, _) => self.first = Some(event.position),
+
+                    (Some(first), Some(released)) => {
+                        if event.time.saturating_sub(released) > self.interval
+                            || exceeds(first, event.position, self.slop)
+                        {
+                            return Status::Failed;
+                        }
+                    }
+
+                    (Some(_), None) => return Status::Failed,
+                }
+
+                self.down = Some(event.pointer);
+                Status::Possible
+            }
+
+            PointerKind::Move if self.down == Some(event.pointer) => {
+                if exceeds(self.first.unwrap(), event.position, self.slop) {
+                    Status::Failed
+                } else {
+                    Status::Possible
+                }
+            }
+
+            PointerKind::Up if self.down == Some(event.pointer) => {
+                self.down = None;
+
+                if self.released.is_some() {
+                    return Status::Recognized(Gesture::DoubleTap {
+                        position: self.first.unwrap(),
+                    });
+                }
+
+                self.released = Some(event.time);
+                Status::Possible
+            }
+
+            _ => Status::Failed,
+        }
+    }
+
+    fn tick(&mut self, time: Duration) -> Status {
+        match self.released {
+            // The second tap did not start in time.
+            Some(released)
+                if self.down.is_none() && time.saturating_sub(released) > self.interval =>
+            {
+                Status::Failed
+            }
+
+            _ => Status::Possible,
+        }
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        self.released
+            .filter(|_| self.down.is_none())
+            .map(|released| released + self.interval)
+    }
+
+    fn reset(&mut self) {
+        self.first = None;
+        self.down = None;
+        self.released = None;
+    }
+}
+
+/// Recognizes a pointer held down without moving.
+#[derive(Debug)]
+pub struct LongPress {
+    /// How far the pointer may move before the long press fails.
+    pub slop: f32,
+    /// How long the pointer must be held down.
+    pub duration: Duration,
+    down: Option<(PointerId, Point, Duration)>,
+}
+
+impl Default for LongPress {
+    fn default() -> Self {
+        Self {
+            slop: DEFAULT_SLOP,
+            duration: Duration::from_millis(500),
+            down: None,
+        }
+    }
+}
+
+impl Recognizer for LongPress {
+    fn pointer(&mut self, event: &PointerEvent) -> Status {
+        match (event.kind, self.down) {
+            (PointerKind::Down, None) => {
+                self.down = Some((event.pointer, event.position, event.time));
+                Status::Possible
+            }
+
+            (PointerKind::Move, Some((pointer, start, _))) if pointer == event.pointer => {
+                if exceeds(start, event.position, self.slop) {
+                    Status::Failed
+                } else {
+                    Status::Possible
+                }
+            }
+
+            _ => Status::Failed,
+        }
+    }
+
+    fn tick(&mut self, time: Duration) -> Status {
+        match self.down {
+            Some((_, position, down)) if time.saturating_sub(down) >= self.duration => {
+                Status::Recognized(Gesture::LongPress { position })
+            }
+
+            _ => Status::Possible,
+        }
+    }
+
+    fn deadline(&self) -> Option<Duration> {
+        self.down.map(|(_, _, down)| down + self.duration)
+    }
+
+    fn reset(&mut self) {
+        self.down = None;
+    }
+}
+
+/// Recognizes a single pointer being dragged.
+#[derive(Debug)]
+pub struct Pan {
+    /// How far the pointer must move before the pan begins.
+    pub slop: f32,
+    pointer: Option<PointerId>,
+    start: Point,
+    last: (Point, Duration),
+    velocity: Point,
+    active: bool,
+}
+
+impl Default for Pan {
+    fn default() -> Self {
+        Self {
+            slop: DEFAULT_SLOP,
+            pointer: None,
+            start: Point::ZERO,
+            last: (Point::ZERO, Duration::ZERO),
+            velocity: Point::ZERO,
+            active: false,
+        }
+    }
+}
+
+impl Pan {
+    fn gesture(&self, phase: Phase) -> Gesture {
+        let position = self.last.0;
+
+        Gesture::Pan {
+            phase,
+            position,
+            translation: Point::new(position.x - self.start.x, position.y - self.start.y),
+            velocity: self.velocity,
+        }
+    }
+}
+
+impl Recognizer for Pan {
+    fn pointer(&mut self, event: &PointerEvent) -> Status {
+        match event.kind {
+            PointerKind::Down if self.pointer.is_none() => {
+                self.pointer = Some(event.pointer);
+                self.start = event.position;
+                self.last = (event.position, event.time);
+                Status::Possible
+            }
+
+            PointerKind::Move if self.pointer == Some(event.pointer) => {
+                self.velocity = velocity(self.last, (event.position, event.time), self.velocity);
+                self.last = (event.position, event.time);
+
+                if self.active {
+                    Status::Active(self.gesture(Phase::Changed))
+                } else if exceeds(self.start, event.position, self.slop) {
+                    self.active = true;
+                    Status::Active(self.gesture(Phase::Began))
+                } else {
+                    Status::Possible
+                }
+            }
+
+            PointerKind::Up if self.pointer == Some(event.pointer) && self.active => {
+                self.last = (event.position, event.time);
+                Status::Recognized(self.gesture(Phase::Ended))
+            }
+
+            PointerKind::Cancel if self.active => {
+                Status::Recognized(self.gesture(Phase::Cancelled))
+            }
+
+            // A second pointer was added before the pan began or the pan never began.
+            _ if self.active => Status::Possible,
+            _ => Status::Failed,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self {
+            slop: self.slop,
+            ..Self::default()
+        };
+    }
+}
+
+/// Recognizes two pointers moving closer together or further apart.
+#[derive(Debug)]
+pub struct Pinch {
+    /// How much the scale must change before the pinch begins.
+    pub threshold: f32,
+    pointers: Vec<(PointerId, Point)>,
+    /// The initial distance between the pointers.
+    initial: f32,
+    active: bool,
+}
+
+impl Default for Pinch {
+    fn default() -> Self {
+        Self {
+            threshold: 0.05,
+            pointers: Vec::new(),
+            initial: 0.0,
+            active: false,
+        }
+    }
+}
+
+impl Pinch {
+    fn gesture(&self, phase: Phase) -> Gesture {
+        let (a, b) = (self.pointers[0].1, self.pointers[1].1);
+
+        Gesture::Pinch {
+            phase,
+            center: Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0),
+            scale: distance(a, b) / self.initial,
+        }
+    }
+}
+
+impl Recognizer for Pinch {
+    fn pointer(&mut self, event: &PointerEvent) -> Status {
+        let index = self
+            .pointers
+            .iter()
+            .position(|&(pointer, _)| pointer == event.pointer);
+
+        match (event.kind, index) {
+            (PointerKind::Down, None) if self.pointers.len() < 2 => {
+                self.pointers.push((event.pointer, event.position));
+
+                if self.pointers.len() == 2 {
+                    self.initial = distance(self.pointers[0].1, self.pointers[1].1);
+                }
+
+                Status::Possible
+            }
+
+            (PointerKind::Move, Some(index)) => {
+                self.pointers[index].1 = event.position;
+
+                if self.pointers.len() < 2 || self.initial == 0.0 {
+                    return Status::Possible;
+                }
+
+                if self.active {
+                    return Status::Active(self.gesture(Phase::Changed));
+                }
+
+                let gesture = self.gesture(Phase::Began);
+
+                match gesture {
+                    Gesture::Pinch { scale, .. } if (scale - 1.0).abs() >= self.threshold => {
+                        self.active = true;
+                        Status::Active(gesture)
+                    }
+
+                    _ => Status::Possible,
+                }
+            }
+
+            (PointerKind::Up, Some(_)) if self.active => {
+                Status::Recognized(self.gesture(Phase::Ended))
+            }
+
+            (PointerKind::Cancel, _) if self.active => {
+                Status::Recognized(self.gesture(Phase::Cancelled))
+            }
+
+            _ => Status::Failed,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pointers.clear();
+        self.initial = 0.0;
+        self.active = false;
+    }
+}
+
+/// Recognizes a pointer quickly moved in one direction and released.
+#[derive(Debug)]
+pub struct Swipe {
+    /// The minimum distance the pointer must move.
+    pub min_distance: f32,
+    /// The minimum velocity of the pointer when released, in units per second.
+    pub min_velocity: f32,
+    pointer: Option<PointerId>,
+    start: Point,
+    last: (Point, Duration),
+    velocity: Point,
+}
+
+impl Default for Swipe {
+    fn default() -> Self {
+        Self {
+            min_distance: 32.0,
+            min_velocity: 300.0,
+            pointer: None,
+            start: Point::ZERO,
+            last: (Point::ZERO, Duration::ZERO),
+            velocity: Point::ZERO,
+        }
+    }
+}
+
+impl Recognizer for Swipe {
+    fn pointer(&mut self, event: &PointerEvent) -> Status {
+        match event.kind {
+            PointerKind::Down if self.pointer.is_none() => {
+                self.pointer = Some(event.pointer);
+                self.start = event.position;
+                self.last = (event.position, event.time);
+                Status::Possible
+            }
+
+            PointerKind::Move if self.pointer == Some(event.pointer) => {
+                self.velocity = velocity(self.last, (event.position, event.time), self.velocity);
+                self.last = (event.position, event.time);
+                Status::Possible
+            }
+
+            PointerKind::Up if self.pointer == Some(event.pointer) => {
+                let dx = event.position.x - self.start.x;
+                let dy = event.position.y - self.start.y;
+                let velocity = self.velocity;
+
+                let (distance, speed, direction) = if dx.abs() >= dy.abs() {
+                    let direction = if dx < 0.0 {
+                        SwipeDirection::Left
+                    } else {
+                        SwipeDirection::Right
+                    };
+
+                    (dx.abs(), velocity.x.abs(), direction)
+                } else {
+                    let direction = if dy < 0.0 {
+                        SwipeDirection::Up
+                    } else {
+                        SwipeDirection::Down
+                    };
+
+                    (dy.abs(), velocity.y.abs(), direction)
+                };
+
+                if distance >= self.min_distance && speed >= self.min_velocity {
+                    Status::Recognized(Gesture::Swipe {
+                        direction,
+                        velocity,
+                    })
+                } else {
+                    Status::Failed
+                }
+            }
+
+            _ => Status::Failed,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pointer = None;
+        self.velocity = Point::ZERO;
+    }
+}
+
+/// Returns whether the distance between two points exceeds the slop.
+fn exceeds(a: Point, b: Point, slop: f32) -> bool {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    dx * dx + dy * dy > slop * slop
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    libm::sqrtf(dx * dx + dy * dy)
+}
+
+/// Estimates the velocity of a pointer between two events.
+///
+/// If no time passed between both events, the previous velocity is returned.
+fn velocity(from: (Point, Duration), to: (Point, Duration), previous: Point) -> Point {
+    let elapsed = to.1.saturating_sub(from.1).as_secs_f32();
+
+    if elapsed == 0.0 {
+        return previous;
+    }
+
+    Point::new((to.0.x - from.0.x) / elapsed, (to.0.y - from.0.y) / elapsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::time::Duration;
+
+    use crate::{
+        event::{PointerEvent, PointerId, PointerKind},
+        geometry::Point,
+    };
+
+    use super::{
+        DoubleTap, Gesture, GestureArena, LongPress, Pan, Phase, Pinch, Swipe, SwipeDirection, Tap,
+    };
+
+    fn event(pointer: u64, kind: PointerKind, x: f32, y: f32, ms: u64) -> PointerEvent {
+        PointerEvent {
+            pointer: PointerId(pointer),
+            kind,
+            position: Point::new(x, y),
+            time: Duration::from_millis(ms),
+        }
+    }
+
+    fn run(arena: &mut GestureArena, events: &[PointerEvent]) -> Vec<Gesture> {
+        events
+            .iter()
+            .flat_map(|event| arena.pointer(event))
+            .collect()
+    }
+
+    fn taps() -> GestureArena {
+        let mut arena = GestureArena::new();
+        let double_tap = arena.add(DoubleTap::default());
+        let tap = arena.add(Tap::default());
+        arena.require_failure(tap, double_tap);
+        arena
+    }
+
+    #[test]
+    fn tap_waits_for_double_tap() {
+        let mut arena = taps();
+
+        let gestures = run(
+            &mut arena,
+            &[
+                event(0, PointerKind::Down, 10.0, 10.0, 0),
+                event(0, PointerKind::Up, 10.0, 10.0, 50),
+            ],
+        );
+        assert!(gestures.is_empty());
+        assert_eq!(arena.deadline(), Some(Duration::from_millis(350)));
+
+        // The double tap fails once the interval passes, letting the tap win.
+        assert!(arena.tick(Duration::from_millis(200)).is_empty());
+        assert_eq!(
+            arena.tick(Duration::from_millis(400)),
+            vec![Gesture::Tap {
+                position: Point::new(10.0, 10.0)
+            }]
+        );
+        assert!(arena.is_finished());
+    }
+
+    #[test]
+    fn out_of_order_events() {
+        let mut arena = taps();
+
+        // An event stamped before the previous event is treated as if no time passed.
+        run(
+            &mut arena,
+            &[
+                event(0, PointerKind::Down, 10.0, 10.0, 100),
+                event(0, PointerKind::Up, 10.0, 10.0, 50),
+                event(0, PointerKind::Down, 10.0, 10.0, 20),
+            ],
+        );
+        assert!(arena.tick(Duration::from_millis(10)).is_empty());
+    }
+
+    #[test]
+    fn double_tap() {
+        let mut arena = taps();
+
+        let gestures = run(
+            &mut arena,
+            &[
+                event(0, PointerKind::Down, 10.0, 10.0, 0),
+                event(0, PointerKind::Up, 10.0, 10.0, 50),
+                event(0, PointerKind::Down, 12.0, 10.0, 150),
+                event(0, PointerKind::Up, 12.0, 10.0, 200),
+            ],
+        );
+
+        assert_eq!(
+            gestures,
+            vec![Gesture::DoubleTap {
+                position: Point::new(10.0, 10.0)
+            }]
+        );
+        assert!(arena.is_finished());
+    }
+
+    #[test]
+    fn pan_cancels_long_press() {
+        let mut arena = GestureArena::new();
+        arena.add(LongPress::default());
+        arena.add(Pan::default());
+
+        let gestures = run(
+            &mut arena,
+            &[
+                event(0, PointerKind::Down, 0.0, 0.0, 0),
+                event(0, PointerKind::Move, 4.0, 0.0, 10),
+                event(0, PointerKind::Move, 20.0, 0.0, 20),
+            ],
+        );
+        assert!(matches!(
+            gestures[..],
+            [Gesture::Pan {
+                phase: Phase::Began,
+                ..
+            }]
+        ));
+
+        // The long press was cancelled.
+        assert_eq!(arena.deadline(), None);
+        assert!(arena.tick(Duration::from_millis(1000)).is_empty());
+
+        let gestures = run(&mut arena, &[event(0, PointerKind::Up, 30.0, 0.0, 1000)]);
+        assert!(matches!(
+            gestures[..],
+            [Gesture::Pan {
+                phase: Phase::Ended,
+                translation: Point { x, y },
+                ..
+            }] if x == 30.0 && y == 0.0
+        ));
+    }
+
+    #[test]
+    fn long_press() {
+        let mut arena = GestureArena::new();
+        arena.add(LongPress::default());
+        arena.add(Pan::default());
+
+        assert!(run(&mut arena, &[event(0, PointerKind::Down, 5.0, 5.0, 0)]).is_empty());
+        assert_eq!(
+            arena.tick(Duration::from_millis(500)),
+            vec![Gesture::LongPress {
+                position: Point::new(5.0, 5.0)
+            }]
+        );
+
+        // The pan was cancelled.
+        assert!(run(&mut arena, &[event(0, PointerKind::Move, 50.0, 5.0, 600)]).is_empty());
+    }
+
+    #[test]
+    fn pinch() {
+        let mut arena = GestureArena::new();
+        arena.add(Pinch::default());
+
+        let gestures = run(
+            &mut arena,
+            &[
+                event(0, PointerKind::Down, 0.0, 0.0, 0),
+                event(1, PointerKind::Down, 100.0, 0.0, 0),
+                event(1, PointerKind::Move, 200.0, 0.0, 10),
+                event(1, PointerKind::Up, 200.0, 0.0, 20),
+            ],
+        );
+
+        assert_eq!(
+            gestures,
+            vec![
+                Gesture::Pinch {
+                    phase: Phase::Began,
+                    center: Point::new(100.0, 0.0),
+                    scale: 2.0
+                },
+                Gesture::Pinch {
+                    phase: Phase::Ended,
+                    center: Point::new(100.0, 0.0),
+                    scale: 2.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn swipe() {
+        let mut arena = GestureArena::new();
+        arena.add(Swipe::default());
+
+        let gestures = run(
+            &mut arena,
+            &[
+                event(0, PointerKind::Down, 100.0, 0.0, 0),
+                event(0, PointerKind::Move, 80.0, 0.0, 20),
+                event(0, PointerKind::Move, 40.0, 2.0, 40),
+                event(0, PointerKind::Up, 40.0, 2.0, 40),
+            ],
+        );
+
+        assert!(matches!(
+            gestures[..],
+            [Gesture::Swipe {
+                direction: SwipeDirection::Left,
+                ..
+            }]
+        ));
+    }
+}
@@ -0,0 +1,367 @@
+//! Undo and redo.
+//!
+//! Changes to the data of a [`Context`](crate::Context) become undoable by expressing them as [`Command`]s, which
+//! know how to apply and revert themselves. Event handlers execute commands with [`EventContext::execute`], which
+//! records the command in the [`History`] of the context.
+//!
+//! Each command executed on it's own becomes a [`Transaction`], the unit which is undone and redone. Commands
+//! executed between [`EventContext::begin_transaction`] and [`EventContext::commit_transaction`] are grouped into
+//! one transaction. Consecutive commands which [merge](Command::merge), such as typing characters, are combined
+//! into a single transaction until the history is [sealed](History::seal). The context seals the history when the
+//! focus changes, so typing into different text inputs is undone separately.
+//!
+//! A transaction remembers the nodes which executed it's commands. When a transaction is undone or redone with
+//! [`Context::undo`](crate::Context::undo) and [`Context::redo`](crate::Context::redo), those nodes are marked as
+//! damaged.
+//!
+//! [`EventContext::execute`]: crate::event::EventContext::execute
+//! [`EventContext::begin_transaction`]: crate::event::EventContext::begin_transaction
+//! [`EventContext::commit_transaction`]: crate::event::EventContext::commit_transaction
+
+use alloc::{boxed::Box, vec::Vec};
+use core::any::Any;
+
+use crate::tree::NodeId;
+
+/// The number of transactions which can be undone by default.
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// A reversible change to data of type `T`.
+pub trait Command<T> {
+    /// Applies the change.
+    fn apply(&mut self, data: &mut T);
+
+    /// Reverts the change, restoring the data to the state before the change was applied.
+    fn revert(&mut self, data: &mut T);
+
+    /// Tries to combine a command executed directly after this command into this command, such as another
+    /// character typed after this one.
+    ///
+    /// `next` was already applied. Returns whether the commands were combined, in which case reverting this
+    /// command must revert both changes. Commands of other types may be rejected by failing to downcast `next`.
+    fn merge(&mut self, next: &dyn Any) -> bool {
+        let _ = next;
+        false
+    }
+}
+
+/// A group of commands which is undone and redone together.
+pub struct Transaction<T> {
+    commands: Vec<Box<dyn Command<T>>>,
+    /// The nodes which executed the commands.
+    nodes: Vec<NodeId>,
+}
+
+impl<T> Transaction<T> {
+    fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Returns the nodes which executed the commands of the transaction.
+    ///
+    /// The nodes may have been removed from the tree since.
+    pub fn nodes(&self) -> &[NodeId] {
+        &self.nodes
+    }
+
+    /// Returns the number of commands in the transaction.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    fn add_node(&mut self, node: Option<NodeId>) {
+        if let Some(node) = node {
+            if !self.nodes.contains(&node) {
+                self.nodes.push(node);
+            }
+        }
+    }
+}
+
+/// The undo and redo stacks of a context.
+pub struct History<T> {
+    undo: Vec<Transaction<T>>,
+    redo: Vec<Transaction<T>>,
+    /// The transaction begun explicitly which is being recorded.
+    open: Option<Transaction<T>>,
+    /// How many times the open transaction was begun without being committed.
+    depth: usize,
+    /// Whether the next command may merge into the last transaction.
+    mergeable: bool,
+    limit: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> History<T> {
+    pub fn new() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            open: None,
+            depth: 0,
+            mergeable: false,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    /// Sets how many transactions can be undone. The oldest transactions are forgotten first.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.enforce_limit();
+    }
+
+    /// Applies a command to the data and records it.
+    ///
+    /// `node` is the node executing the command, which is marked as damaged when the command is undone or redone.
+    /// Executing a command clears the redo stack.
+    pub fn execute<C>(&mut self, data: &mut T, mut command: C, node: Option<NodeId>)
+    where
+        C: Command<T> + 'static,
+    {
+        command.apply(data);
+        self.redo.clear();
+
+        if let Some(open) = &mut self.open {
+            open.add_node(node);
+
+            // Merge into the previous command of the transaction, so a transaction of many edits stays small.
+            let merged = match open.commands.last_mut() {
+                Some(last) => last.merge(&command),
+                None => false,
+            };
+
+            if !merged {
+                open.commands.push(Box::new(command));
+            }
+
+            return;
+        }
+
+        let merged = match self.undo.last_mut() {
+            Some(last) if self.mergeable && last.commands.len() == 1 => {
+                let merged = last.commands[0].merge(&command);
+
+                if merged {
+                    last.add_node(node);
+                }
+
+                merged
+            }
+
+            _ => false,
+        };
+
+        if !merged {
+            let mut transaction = Transaction::new();
+            transaction.add_node(node);
+            transaction.commands.push(Box::new(command));
+            self.push(transaction);
+        }
+
+        self.mergeable = true;
+    }
+
+    /// Begins grouping the commands executed afterwards into one transaction.
+    ///
+    /// Transactions may be nested, in which case the commands are grouped until the outermost transaction is
+    /// committed.
+    pub fn begin_transaction(&mut self) {
+        self.depth += 1;
+        self.open.get_or_insert_with(Transaction::new);
+    }
+
+    /// Ends a transaction begun with [`History::begin_transaction`].
+    ///
+    /// # Panics
+    ///
+    /// If no transaction was begun.
+    pub fn commit_transaction(&mut self) {
+        assert!(self.depth > 0, "no transaction was begun");
+        self.depth -= 1;
+
+        if self.depth == 0 {
+            let transaction = self.open.take().unwrap();
+
+            if !transaction.is_empty() {
+                self.push(transaction);
+                // Explicit transactions are never merged into.
+                self.mergeable = false;
+            }
+        }
+    }
+
+    /// Prevents the next command from merging into the last transaction.
+    pub fn seal(&mut self) {
+        self.mergeable = false;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last transaction, returning the transaction.
+    ///
+    /// A transaction which was begun but not committed yet is committed first.
+    pub fn undo(&mut self, data: &mut T) -> Option<&Transaction<T>> {
+        self.commit_open();
+        let mut transaction = self.undo.pop()?;
+
+        for command in transaction.commands.iter_mut().rev() {
+            command.revert(data);
+        }
+
+        self.mergeable = false;
+        self.redo.push(transaction);
+        self.redo.last()
+    }
+
+    /// Applies the last undone transaction again, returning the transaction.
+    pub fn redo(&mut self, data: &mut T) -> Option<&Transaction<T>> {
+        self.commit_open();
+        let mut transaction = self.redo.pop()?;
+
+        for command in &mut transaction.commands {
+            command.apply(data);
+        }
+
+        self.mergeable = false;
+        self.undo.push(transaction);
+        self.undo.last()
+    }
+
+    /// Forgets every transaction.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.mergeable = false;
+    }
+
+    fn push(&mut self, transaction: Transaction<T>) {
+        self.undo.push(transaction);
+        self.enforce_limit();
+    }
+
+    fn enforce_limit(&mut self) {
+        if self.undo.len() > self.limit {
+            let excess = self.undo.len() - self.limit;
+            self.undo.drain(..excess);
+        }
+    }
+
+    fn commit_open(&mut self) {
+        if self.depth > 0 {
+            self.depth = 1;
+            self.commit_transaction();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use core::any::Any;
+
+    use super::{Command, History};
+
+    /// Inserts text at an offset.
+    struct Insert {
+        offset: usize,
+        text: String,
+    }
+
+    impl Insert {
+        fn new(offset: usize, text: &str) -> Self {
+            Self {
+                offset,
+                text: text.into(),
+            }
+        }
+    }
+
+    impl Command<String> for Insert {
+        fn apply(&mut self, data: &mut String) {
+            data.insert_str(self.offset, &self.text);
+        }
+
+        fn revert(&mut self, data: &mut String) {
+            data.replace_range(self.offset..self.offset + self.text.len(), "");
+        }
+
+        fn merge(&mut self, next: &dyn Any) -> bool {
+            match next.downcast_ref::<Insert>() {
+                Some(next) if next.offset == self.offset + self.text.len() => {
+                    self.text.push_str(&next.text);
+                    true
+                }
+
+                _ => false,
+            }
+        }
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut history = History::new();
+        let mut text = String::new();
+
+        // Typing merges into one transaction until sealed.
+        history.execute(&mut text, Insert::new(0, "a"), None);
+        history.execute(&mut text, Insert::new(1, "b"), None);
+        history.seal();
+        history.execute(&mut text, Insert::new(2, "c"), None);
+        // Not adjacent, so not merged.
+        history.execute(&mut text, Insert::new(0, "_"), None);
+        assert_eq!(text, "_abc");
+
+        history.undo(&mut text);
+        history.undo(&mut text);
+        assert_eq!(text, "ab");
+        history.undo(&mut text);
+        assert_eq!(text, "");
+        assert!(!history.can_undo());
+
+        history.redo(&mut text);
+        assert_eq!(text, "ab");
+
+        // Executing a command forgets undone transactions.
+        history.execute(&mut text, Insert::new(2, "d"), None);
+        assert!(!history.can_redo());
+
+        // Transactions group commands, and are never merged into.
+        history.begin_transaction();
+        history.execute(&mut text, Insert::new(0, "1"), None);
+        history.begin_transaction();
+        history.execute(&mut text, Insert::new(0, "2"), None);
+        history.commit_transaction();
+        history.commit_transaction();
+        history.execute(&mut text, Insert::new(5, "e"), None);
+        assert_eq!(text, "21abde");
+
+        history.undo(&mut text);
+        let transaction = history.undo(&mut text).unwrap();
+        assert_eq!(transaction.len(), 2);
+        assert_eq!(text, "abd");
+
+        // The oldest transactions are forgotten past the limit.
+        history.set_limit(1);
+        history.undo(&mut text);
+        assert!(!history.can_undo());
+        assert_eq!(text, "ab");
+    }
+}
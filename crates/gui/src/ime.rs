@@ -0,0 +1,240 @@
+//! Text input using an input method.
+//!
+//! An input method (IME) composes text before it is committed, which is needed to type languages such as
+//! Chinese, Japanese and Korean. While composing, the text being composed (the preedit) is displayed at the
+//! cursor and the input method may show a candidate window next to the cursor.
+//!
+//! A node becomes a text input client by returning a [`TextInputState`] from
+//! [`ViewNode::text_input`](crate::ViewNode::text_input) while it has focus. The host polls
+//! [`Context::take_text_input_update`](crate::Context::take_text_input_update) to enable or disable the input
+//! method and to tell the input method where the cursor is. Input method events are delivered to the focused
+//! node as [`Event::Ime`](crate::event::Event::Ime).
+//!
+//! All offsets are in bytes of UTF-8 text.
+
+use alloc::string::{String, ToString};
+use core::ops::Range;
+
+use crate::geometry::Rect;
+
+/// An event from an input method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent<'a> {
+    /// Replaces the text being composed.
+    ///
+    /// An empty text removes the preedit.
+    Preedit {
+        text: &'a str,
+        /// The cursor inside the preedit text.
+        ///
+        /// If the range is not empty, the range should be highlighted.
+        cursor: Option<Range<usize>>,
+    },
+
+    /// Inserts text at the cursor, replacing the selection and removing the preedit.
+    Commit { text: &'a str },
+
+    /// Deletes text around the cursor.
+    DeleteSurrounding {
+        /// The number of bytes to delete before the cursor.
+        before: usize,
+        /// The number of bytes to delete after the cursor.
+        after: usize,
+    },
+}
+
+/// The purpose of a text input, which the input method may use to show an appropriate keyboard.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ContentPurpose {
+    #[default]
+    Normal,
+    Password,
+    Number,
+    Phone,
+    Email,
+    Url,
+}
+
+/// The state of a text input client which is reported to the input method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextInputState {
+    /// The text around the cursor.
+    pub surrounding_text: String,
+    /// The position of the cursor in the surrounding text.
+    pub cursor: usize,
+    /// The other end of the selection in the surrounding text.
+    ///
+    /// This is equal to the cursor if nothing is selected.
+    pub anchor: usize,
+    pub purpose: ContentPurpose,
+    /// The area of the cursor on the surface.
+    ///
+    /// The input method uses this to position the candidate window.
+    pub cursor_area: Rect,
+}
+
+/// A change to the text input which should be forwarded to the input method.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextInputUpdate {
+    /// A text input client gained focus.
+    Enable(TextInputState),
+
+    /// The state of the focused text input client changed.
+    Update(TextInputState),
+
+    /// No text input client has focus.
+    Disable,
+}
+
+/// Editable text which applies input method events.
+///
+/// This is a building block for nodes which act as text input clients.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextBuffer {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+    preedit: Option<(String, Option<Range<usize>>)>,
+}
+
+impl TextBuffer {
+    /// Creates a buffer with the cursor at the end of the text.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            cursor: text.len(),
+            anchor: text.len(),
+            preedit: None,
+        }
+    }
+
+    /// Returns the committed text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the selected range of the committed text.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Moves the cursor, selecting the text between the anchor and cursor.
+    ///
+    /// # Panics
+    ///
+    /// If either offset is not on a character boundary.
+    pub fn select(&mut self, anchor: usize, cursor: usize) {
+        assert!(self.text.is_char_boundary(anchor) && self.text.is_char_boundary(cursor));
+        self.anchor = anchor;
+        self.cursor = cursor;
+    }
+
+    /// Returns the text being composed and the cursor inside of it.
+    pub fn preedit(&self) -> Option<(&str, Option<Range<usize>>)> {
+        self.preedit
+            .as_ref()
+            .map(|(text, cursor)| (text.as_str(), cursor.clone()))
+    }
+
+    /// Returns the committed text with the preedit inserted at the cursor.
+    pub fn display_text(&self) -> String {
+        let mut text = self.text.clone();
+
+        if let Some((preedit, _)) = &self.preedit {
+            text.insert_str(self.cursor, preedit);
+        }
+
+        text
+    }
+
+    /// Applies an input method event.
+    pub fn apply(&mut self, event: &ImeEvent<'_>) {
+        match event {
+            ImeEvent::Preedit { text, cursor } => {
+                self.preedit = (!text.is_empty()).then(|| (text.to_string(), cursor.clone()));
+            }
+
+            ImeEvent::Commit { text } => {
+                self.preedit = None;
+
+                let selection = self.selection();
+                self.text.replace_range(selection.clone(), text);
+                self.cursor = selection.start + text.len();
+                self.anchor = self.cursor;
+            }
+
+            &ImeEvent::DeleteSurrounding { before, after } => {
+                let selection = self.selection();
+                let mut start = selection.start.saturating_sub(before);
+                let mut end = (selection.end + after).min(self.text.len());
+
+                // Never split a character.
+                while !self.text.is_char_boundary(start) {
+                    start -= 1;
+                }
+
+                while !self.text.is_char_boundary(end) {
+                    end += 1;
+                }
+
+                self.text.replace_range(start..end, "");
+                self.cursor = start;
+                self.anchor = start;
+            }
+        }
+    }
+
+    /// Returns the state to report to the input method.
+    pub fn state(&self, purpose: ContentPurpose, cursor_area: Rect) -> TextInputState {
+        TextInputState {
+            surrounding_text: self.text.clone(),
+            cursor: self.cursor,
+            anchor: self.anchor,
+            purpose,
+            cursor_area,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImeEvent, TextBuffer};
+
+    #[test]
+    fn compose() {
+        let mut buffer = TextBuffer::new("日本");
+
+        buffer.apply(&ImeEvent::Preedit {
+            text: "ご",
+            cursor: Some(3..3),
+        });
+        assert_eq!(buffer.display_text(), "日本ご");
+        assert_eq!(buffer.text(), "日本");
+
+        buffer.apply(&ImeEvent::Preedit {
+            text: "",
+            cursor: None,
+        });
+        assert_eq!(buffer.preedit(), None);
+
+        buffer.apply(&ImeEvent::Commit { text: "語" });
+        assert_eq!(buffer.text(), "日本語");
+        assert_eq!(buffer.cursor(), 9);
+
+        // Deleting part of a character deletes the whole character.
+        buffer.apply(&ImeEvent::DeleteSurrounding {
+            before: 4,
+            after: 0,
+        });
+        assert_eq!(buffer.text(), "日");
+        assert_eq!(buffer.cursor(), 3);
+
+        buffer.select(0, 3);
+        buffer.apply(&ImeEvent::Commit { text: "月" });
+        assert_eq!(buffer.text(), "月");
+    }
+}
@@ -0,0 +1,171 @@
+//! Layout direction and alignment.
+//!
+//! The layout direction is part of the environment of a [`Scene`](crate::scene::Scene). Views which lay out their
+//! children horizontally, such as [rows](crate::view::row::Row) and [grids](crate::view::list::List::columns),
+//! place the first child on the right in a right-to-left layout. Alignments are specified relative to the start
+//! and end of the layout direction, so they mirror too.
+//!
+//! The direction of text within a node is resolved separately by the bidirectional algorithm of the `text` crate,
+//! using the layout direction as the base direction of paragraphs.
+
+use alloc::vec::Vec;
+
+use crate::geometry::Rect;
+
+/// The direction in which horizontal layouts flow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    pub fn is_rtl(self) -> bool {
+        self == Direction::RightToLeft
+    }
+
+    /// Mirrors a rectangle horizontally within the bounds of it's container in a right-to-left layout.
+    ///
+    /// Layouts may compute positions from left to right and mirror the result.
+    pub fn mirror(self, rect: Rect, container: Rect) -> Rect {
+        match self {
+            Direction::LeftToRight => rect,
+            Direction::RightToLeft => Rect::new(
+                container.x + container.right() - rect.right(),
+                rect.y,
+                rect.width,
+                rect.height,
+            ),
+        }
+    }
+}
+
+/// The horizontal alignment of content, relative to the layout direction.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// The left edge in a left-to-right layout, or the right edge in a right-to-left layout.
+    #[default]
+    Start,
+    Center,
+    /// The right edge in a left-to-right layout, or the left edge in a right-to-left layout.
+    End,
+}
+
+impl Alignment {
+    /// Returns the distance from the left edge of the available space to content of a width.
+    pub fn offset(self, direction: Direction, available: f32, width: f32) -> f32 {
+        let free = available - width;
+
+        match (self, direction) {
+            (Alignment::Center, _) => free / 2.0,
+            (Alignment::Start, Direction::LeftToRight)
+            | (Alignment::End, Direction::RightToLeft) => 0.0,
+            (Alignment::End, Direction::LeftToRight)
+            | (Alignment::Start, Direction::RightToLeft) => free,
+        }
+    }
+}
+
+/// Lays out children of the given widths in a horizontal stack.
+///
+/// The first child is placed at the start of the layout direction, and the stack is aligned within the bounds.
+/// Returns the bounds of each child in the same order as `widths`.
+pub fn stack(
+    direction: Direction,
+    alignment: Alignment,
+    bounds: Rect,
+    spacing: f32,
+    widths: &[f32],
+) -> Vec<Rect> {
+    let gaps = widths.len().saturating_sub(1) as f32 * spacing;
+    let width = widths.iter().sum::<f32>() + gaps;
+    let x = bounds.x + alignment.offset(direction, bounds.width, width);
+    // Children are mirrored within the stack, which is already aligned.
+    let stack = Rect::new(x, bounds.y, width, bounds.height);
+
+    let mut x = stack.x;
+    widths
+        .iter()
+        .map(|&width| {
+            let rect = Rect::new(x, bounds.y, width, bounds.height);
+            x += width + spacing;
+            direction.mirror(rect, stack)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Rect;
+
+    use super::{stack, Alignment, Direction};
+
+    #[test]
+    fn mirror() {
+        let container = Rect::new(10.0, 0.0, 100.0, 20.0);
+        let rect = Rect::new(20.0, 5.0, 30.0, 10.0);
+
+        assert_eq!(Direction::LeftToRight.mirror(rect, container), rect);
+        assert_eq!(
+            Direction::RightToLeft.mirror(rect, container),
+            Rect::new(70.0, 5.0, 30.0, 10.0)
+        );
+
+        assert_eq!(
+            Alignment::Start.offset(Direction::RightToLeft, 100.0, 40.0),
+            60.0
+        );
+        assert_eq!(
+            Alignment::End.offset(Direction::RightToLeft, 100.0, 40.0),
+            0.0
+        );
+        assert_eq!(
+            Alignment::Center.offset(Direction::RightToLeft, 100.0, 40.0),
+            30.0
+        );
+    }
+
+    #[test]
+    fn horizontal_stack() {
+        let bounds = Rect::new(0.0, 0.0, 100.0, 10.0);
+        let widths = [10.0, 20.0];
+
+        assert_eq!(
+            stack(
+                Direction::LeftToRight,
+                Alignment::Start,
+                bounds,
+                5.0,
+                &widths
+            ),
+            [
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                Rect::new(15.0, 0.0, 20.0, 10.0)
+            ]
+        );
+
+        // The first child is on the right.
+        assert_eq!(
+            stack(
+                Direction::RightToLeft,
+                Alignment::Start,
+                bounds,
+                5.0,
+                &widths
+            ),
+            [
+                Rect::new(90.0, 0.0, 10.0, 10.0),
+                Rect::new(65.0, 0.0, 20.0, 10.0)
+            ]
+        );
+
+        assert_eq!(
+            stack(Direction::RightToLeft, Alignment::End, bounds, 5.0, &widths),
+            [
+                Rect::new(25.0, 0.0, 10.0, 10.0),
+                Rect::new(0.0, 0.0, 20.0, 10.0)
+            ]
+        );
+    }
+}
@@ -1,15 +1,60 @@
+//! # Features
+//!
+//! The crate only requires `core` and `alloc`, so it may be used on targets without the standard library such as
+//! embedded displays, by disabling the default features. An allocator must be provided on such targets.
+//!
+//! - `std` (default): Implements conveniences which require the standard library, such as sharing a
+//!   [`Clipboard`](transfer::Clipboard) between threads.
+//! - `renderer`: Paints the tree using a `renderer`. This enables `std`.
+//! - `serde`: Enables the `snapshot` module, which captures and restores the state of a [`Context`].
+//! - `testing`: Enables the [`testing`] module, which drives a [`Context`] without a window.
+//!
+//! `no_std_check` contains a crate which checks that the crate builds without the standard library, see the
+//! readme of the crate.
+//!
+//! The tests of the modules enabled by features, such as the snapshot tests, only run with the features enabled,
+//! so run the tests with `cargo test --all-features`.
+
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-mod tree;
-
+pub mod accessibility;
+pub mod damage;
+pub mod event;
+pub mod geometry;
+pub mod gesture;
+pub mod history;
+pub mod ime;
+pub mod layout;
+#[cfg(feature = "renderer")]
+pub mod render;
 pub mod scene;
+#[cfg(feature = "serde")]
+pub mod snapshot;
+pub mod surface;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod transfer;
+pub mod tree;
 pub mod view;
 
-use alloc::vec::Vec;
-use tree::RetainedTree;
-use view::View;
+use accessibility::Role;
+use alloc::{boxed::Box, vec::Vec};
+use core::time::Duration;
+use damage::{Damage, DamageTracker};
+use event::{Event, EventContext, PointerEvent, PointerKind, ScrollEvent};
+use geometry::{Point, Rect};
+use gesture::GestureArena;
+use history::{Command, History};
+use ime::{ImeEvent, TextInputState, TextInputUpdate};
+use layout::Direction;
+use scene::Scene;
+use surface::{Positioner, SurfaceEvent, SurfaceId, SurfaceKind};
+use transfer::{Clipboard, Content, Drag, DragEvent, DragSource, DropTarget, MemoryClipboard};
+use tree::{NodeId, RetainedTree};
 
 // Discussion: When do we stop UI?
 //
@@ -79,6 +124,9 @@ use view::View;
 ///
 /// A context creates an internal `Node` tree. The tree is incrementally updated as events are processed.
 ///
+/// The tree has a root for each [surface](surface) of the context. The context starts with only the main
+/// window, [`SurfaceId::MAIN`].
+///
 /// This type should not be used in public apis, instead the implementation should wrap this type with their
 /// own `Context`.
 pub struct Context<Data, Node> {
@@ -86,6 +134,31 @@ pub struct Context<Data, Node> {
     view_tree: RetainedTree<Node>,
     /// The data of the context.
     data: Data,
+    /// The surfaces of the context, starting with the main window.
+    surfaces: Vec<Surface>,
+    /// The id of the next surface which is opened.
+    next_surface: u64,
+    /// Changes to the surfaces which were not taken by the host yet.
+    surface_events: Vec<SurfaceEvent>,
+    clipboard: Box<dyn Clipboard>,
+    /// The drag and drop operation in progress.
+    drag: Option<Drag>,
+    /// The node receiving the pointer events in progress, and the node recognizing gestures with it's
+    /// recognizers.
+    pointer_target: Option<(NodeId, Option<(NodeId, GestureArena)>)>,
+    /// The number of pointers currently down.
+    pointers: usize,
+    /// The node with keyboard focus.
+    focus: Option<NodeId>,
+    /// The text input state last reported to the host.
+    text_input: Option<TextInputState>,
+    /// The layout direction the surfaces are built in.
+    direction: Direction,
+    /// The commands which can be undone and redone.
+    history: History<Data>,
+    /// The snapshot being restored into surfaces which were not built since.
+    #[cfg(feature = "serde")]
+    restoring: Option<snapshot::Snapshot<()>>,
 }
 
 impl<Data, Node> Context<Data, Node>
@@ -96,6 +169,951 @@ where
         Self {
             view_tree: RetainedTree::new(),
             data,
+            surfaces: Vec::from([Surface {
+                id: SurfaceId::MAIN,
+                kind: SurfaceKind::Window,
+                parent: None,
+                anchor: None,
+                root: None,
+                damage: DamageTracker::new(),
+                scale_factor: 1.0,
+            }]),
+            next_surface: 1,
+            surface_events: Vec::new(),
+            clipboard: Box::new(MemoryClipboard::new()),
+            drag: None,
+            pointer_target: None,
+            pointers: 0,
+            focus: None,
+            text_input: None,
+            direction: Direction::default(),
+            history: History::new(),
+            #[cfg(feature = "serde")]
+            restoring: None,
+        }
+    }
+
+    /// Returns the [layout direction](layout) the surfaces are built in.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Sets the layout direction the surfaces are built in, usually the direction of the language of the user
+    /// interface.
+    ///
+    /// The surfaces should be rebuilt after the direction changed.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Builds the main window.
+    ///
+    /// `f` builds the view of the main window in the scene using [`View::build`](view::View::build). Building the
+    /// view in the closure allows the view to borrow the data of the context.
+    ///
+    /// The existing nodes of the main window are reused, see [`Context::build_surface`].
+    pub fn build<F>(&mut self, f: F)
+    where
+        F: FnOnce(&Data, &mut Scene<'_, Node>),
+    {
+        self.build_surface(SurfaceId::MAIN, f);
+    }
+
+    /// Builds a surface.
+    ///
+    /// `f` builds the view of the surface in the scene, see [`Context::build`].
+    ///
+    /// The existing nodes of the surface are reused by the nodes built at the same position in the tree, keeping
+    /// their ids. Only the reused nodes which [paint differently](ViewNode::paints_same) than before, and the added,
+    /// moved and removed nodes, are damaged.
+    ///
+    /// # Panics
+    ///
+    /// If the surface is not open.
+    pub fn build_surface<F>(&mut self, id: SurfaceId, f: F)
+    where
+        F: FnOnce(&Data, &mut Scene<'_, Node>),
+    {
+        let index = self.surface_index(id).expect("surface is not open");
+        let surface = &mut self.surfaces[index];
+        let mut replaced = Vec::new();
+        let mut scene = Scene::rebuild(&mut self.view_tree, surface.root, &mut replaced);
+        scene.set_direction(self.direction);
+        f(&self.data, &mut scene);
+        surface.root = scene.built_root();
+
+        for (node, previous) in replaced {
+            match self.view_tree.get(node) {
+                Some(current) if current.paints_same(&previous) => {}
+                _ => surface.damage.mark(node),
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        self.restore_surface(id);
+    }
+
+    /// Opens a secondary window built by `f`.
+    pub fn open_window<F>(&mut self, f: F) -> SurfaceId
+    where
+        F: FnOnce(&Data, &mut Scene<'_, Node>),
+    {
+        let id = self.open_surface(SurfaceKind::Window, None, None);
+        self.build_surface(id, f);
+        id
+    }
+
+    /// Opens a popup, such as a menu, positioned relative to the bounds of the `anchor` node.
+    ///
+    /// The [`anchor_rect`](Positioner::anchor_rect) of the positioner is replaced with the bounds of the anchor.
+    /// The popup is closed when the anchor is removed from the tree, such as when the surface displaying the
+    /// anchor is rebuilt without it.
+    ///
+    /// Returns [`None`] if the anchor is not in the tree.
+    pub fn open_popup<F>(
+        &mut self,
+        anchor: NodeId,
+        positioner: Positioner,
+        f: F,
+    ) -> Option<SurfaceId>
+    where
+        F: FnOnce(&Data, &mut Scene<'_, Node>),
+    {
+        self.open_anchored(anchor, SurfaceKind::Popup(positioner), f)
+    }
+
+    /// Opens a tooltip positioned relative to the bounds of the `anchor` node.
+    ///
+    /// Tooltips behave like popups, except tooltips do not receive pointer events.
+    pub fn open_tooltip<F>(
+        &mut self,
+        anchor: NodeId,
+        positioner: Positioner,
+        f: F,
+    ) -> Option<SurfaceId>
+    where
+        F: FnOnce(&Data, &mut Scene<'_, Node>),
+    {
+        self.open_anchored(anchor, SurfaceKind::Tooltip(positioner), f)
+    }
+
+    /// Closes a surface and any popups and tooltips positioned relative to it.
+    ///
+    /// The main window can not be closed.
+    pub fn close_surface(&mut self, id: SurfaceId) {
+        if id == SurfaceId::MAIN {
+            return;
+        }
+
+        let index = match self.surface_index(id) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let surface = self.surfaces.remove(index);
+
+        if let Some(root) = surface.root {
+            self.view_tree.remove(root);
+        }
+
+        self.surface_events.push(SurfaceEvent::Destroyed { id });
+
+        let children = self
+            .surfaces
+            .iter()
+            .filter(|surface| surface.parent == Some(id))
+            .map(|surface| surface.id)
+            .collect::<Vec<_>>();
+
+        for child in children {
+            self.close_surface(child);
+        }
+
+        if self
+            .focus
+            .is_some_and(|focus| self.view_tree.get(focus).is_none())
+        {
+            self.set_focus(None);
+        }
+    }
+
+    /// Returns the ids of every open surface.
+    pub fn surfaces(&self) -> impl Iterator<Item = SurfaceId> + '_ {
+        self.surfaces.iter().map(|surface| surface.id)
+    }
+
+    /// Returns the kind of a surface.
+    pub fn surface_kind(&self, id: SurfaceId) -> Option<&SurfaceKind> {
+        self.surface(id).map(|surface| &surface.kind)
+    }
+
+    /// Returns the root node of a surface.
+    ///
+    /// Returns [`None`] if the surface was not built yet.
+    pub fn surface_root(&self, id: SurfaceId) -> Option<NodeId> {
+        self.surface(id)?.root
+    }
+
+    /// Returns the surface which displays a node.
+    pub fn surface_of(&self, node: NodeId) -> Option<SurfaceId> {
+        let root = self.view_tree.root_of(node)?;
+
+        self.surfaces
+            .iter()
+            .find(|surface| surface.root == Some(root))
+            .map(|surface| surface.id)
+    }
+
+    /// Returns the changes to the surfaces since this was last called.
+    ///
+    /// Popups and tooltips whose anchor moved are repositioned, and popups and tooltips whose anchor was
+    /// removed are closed. The host should create, reposition and destroy platform surfaces accordingly.
+    pub fn take_surface_events(&mut self) -> Vec<SurfaceEvent> {
+        let mut removed = Vec::new();
+
+        for surface in &mut self.surfaces {
+            let (anchor, positioner) = match (surface.anchor, surface.kind.positioner_mut()) {
+                (Some(anchor), Some(positioner)) => (anchor, positioner),
+                _ => continue,
+            };
+
+            match self.view_tree.get(anchor).map(ViewNode::bounds) {
+                Some(bounds) if bounds != positioner.anchor_rect => {
+                    positioner.anchor_rect = bounds;
+                    self.surface_events.push(SurfaceEvent::Repositioned {
+                        id: surface.id,
+                        positioner: *positioner,
+                    });
+                }
+
+                Some(_) => (),
+                None => removed.push(surface.id),
+            }
+        }
+
+        for id in removed {
+            self.close_surface(id);
+        }
+
+        core::mem::take(&mut self.surface_events)
+    }
+
+    /// Returns the retained tree of nodes built by the context.
+    pub fn tree(&self) -> &RetainedTree<Node> {
+        &self.view_tree
+    }
+
+    /// Marks the contents of a node as visually changed.
+    pub fn mark_damaged(&mut self, id: NodeId) {
+        self.damage_tracker(id).mark(id);
+    }
+
+    /// Returns the areas of a surface which changed since the surface was last painted.
+    ///
+    /// `bounds` is the area of the surface. The damage is in logical units, see [`Damage::to_physical`].
+    pub fn damage(&self, id: SurfaceId, bounds: Rect) -> Damage {
+        match self.surface(id) {
+            Some(surface) => {
+                surface
+                    .damage
+                    .damage(&self.view_tree, surface.root, bounds, ViewNode::bounds)
+            }
+
+            None => Damage::default(),
+        }
+    }
+
+    /// Marks a surface as painted, clearing any damage.
+    pub fn mark_painted(&mut self, id: SurfaceId) {
+        if let Some(index) = self.surface_index(id) {
+            let surface = &mut self.surfaces[index];
+            surface
+                .damage
+                .painted(&self.view_tree, surface.root, ViewNode::bounds);
+        }
+    }
+
+    /// Returns the number of physical pixels per logical unit of a surface.
+    ///
+    /// Returns 1 if the surface is not open.
+    pub fn scale_factor(&self, id: SurfaceId) -> f32 {
+        self.surface(id).map_or(1.0, |surface| surface.scale_factor)
+    }
+
+    /// Sets the number of physical pixels per logical unit of a surface, such as when a window moves to another
+    /// output.
+    ///
+    /// The scale factor may be fractional, such as 1.25. Layout remains in logical units, so the surface does not
+    /// need to be rebuilt. Instead the entire surface is damaged and the nodes of the surface are notified with
+    /// [`ViewNode::scale_factor_changed`], so content rasterized at the previous scale factor such as text can be
+    /// rasterized again.
+    pub fn set_scale_factor(&mut self, id: SurfaceId, scale_factor: f32) {
+        let index = match self.surface_index(id) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let surface = &mut self.surfaces[index];
+
+        if surface.scale_factor == scale_factor {
+            return;
+        }
+
+        surface.scale_factor = scale_factor;
+        surface.damage.mark_all();
+
+        if let Some(root) = surface.root {
+            let nodes = self
+                .view_tree
+                .descendants(root)
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>();
+
+            for id in nodes {
+                if let Some(node) = self.view_tree.get_mut(id) {
+                    node.scale_factor_changed(scale_factor);
+                }
+            }
+        }
+    }
+
+    /// Paints a surface using a renderer.
+    ///
+    /// The surface is painted in physical pixels using the [scale factor](Context::scale_factor) of the surface.
+    #[cfg(feature = "renderer")]
+    pub fn paint<R>(&mut self, id: SurfaceId, renderer: &mut R)
+    where
+        R: renderer::Renderer + renderer::RenderQuad + renderer::RenderClip,
+        Node: render::Paint<R>,
+    {
+        if let Some(root) = self.surface_root(id) {
+            render::paint(&self.view_tree, root, renderer, self.scale_factor(id));
+        }
+
+        self.mark_painted(id);
+    }
+
+    /// Paints the areas of a surface which changed since the surface was last painted.
+    ///
+    /// `bounds` is the area of the surface in logical units. The returned damage is in physical pixels and should
+    /// be used when presenting the surface.
+    #[cfg(feature = "renderer")]
+    pub fn paint_damage<R>(&mut self, id: SurfaceId, renderer: &mut R, bounds: Rect) -> Damage
+    where
+        R: renderer::Renderer + renderer::RenderQuad + renderer::RenderClip,
+        Node: render::Paint<R>,
+    {
+        let damage = self.damage(id, bounds);
+        let scale_factor = self.scale_factor(id);
+
+        if let Some(root) = self.surface_root(id) {
+            for &rect in damage.rects() {
+                render::paint_clipped(&self.view_tree, root, renderer, rect, scale_factor);
+            }
+        }
+
+        self.mark_painted(id);
+        damage.to_physical(scale_factor)
+    }
+
+    /// Sets the clipboard used by event handlers.
+    ///
+    /// By default the context uses a [`MemoryClipboard`].
+    pub fn set_clipboard(&mut self, clipboard: Box<dyn Clipboard>) {
+        self.clipboard = clipboard;
+    }
+
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        &mut *self.clipboard
+    }
+
+    /// Returns the topmost node of a surface containing the position which matches the predicate.
+    pub fn hit_test<F>(&self, surface: SurfaceId, position: Point, predicate: F) -> Option<NodeId>
+    where
+        F: Fn(&Node) -> bool,
+    {
+        let root = self.surface_root(surface)?;
+
+        // Nodes later in depth first order are above earlier nodes.
+        self.view_tree
+            .descendants(root)
+            .filter(|(_, node)| node.bounds().contains(position) && predicate(node))
+            .last()
+            .map(|(id, _)| id)
+    }
+
+    /// Handles a pointer event on a surface.
+    ///
+    /// The topmost node under the pointer when the first pointer is pressed receives every pointer event until
+    /// all pointers are released. Gestures are recognized by the recognizers of the node, or the nearest parent of
+    /// the node which has recognizers, and delivered to that node.
+    ///
+    /// Pressing a pointer outside of the open popups closes the popups.
+    pub fn pointer(&mut self, surface: SurfaceId, event: PointerEvent) {
+        if matches!(self.surface_kind(surface), Some(SurfaceKind::Tooltip(_))) {
+            return;
+        }
+
+        if event.kind == PointerKind::Down {
+            if self.pointers == 0 {
+                self.dismiss_popups(surface);
+                let target = self.hit_test(surface, event.position, |_| true);
+
+                self.pointer_target = target.map(|target| {
+                    let gestures =
+                        core::iter::successors(Some(target), |&id| self.view_tree.parent(id))
+                            .find_map(|id| Some((id, self.view_tree.get(id)?.gestures()?)));
+                    (target, gestures)
+                });
+            }
+
+            self.pointers += 1;
+        }
+
+        if matches!(event.kind, PointerKind::Up | PointerKind::Cancel) {
+            self.pointers = self.pointers.saturating_sub(1);
+        }
+
+        let (target, gestures) = match self.pointer_target.as_mut() {
+            Some((target, gestures)) => {
+                let gestures = gestures
+                    .as_mut()
+                    .map(|(id, arena)| (*id, arena.pointer(&event)));
+                (*target, gestures)
+            }
+
+            None => return,
+        };
+
+        self.dispatch(target, &Event::Pointer(event));
+
+        if let Some((id, gestures)) = gestures {
+            for gesture in gestures {
+                self.dispatch(id, &Event::Gesture(gesture));
+            }
+        }
+
+        self.release_pointer_target();
+    }
+
+    /// Notifies the gesture recognizers that time has passed.
+    ///
+    /// This should be called when the [`Context::deadline`] is reached.
+    pub fn tick(&mut self, time: Duration) {
+        let (id, gestures) = match self.pointer_target.as_mut() {
+            Some((_, Some((id, arena)))) => (*id, arena.tick(time)),
+            _ => return,
+        };
+
+        for gesture in gestures {
+            self.dispatch(id, &Event::Gesture(gesture));
+        }
+
+        self.release_pointer_target();
+    }
+
+    /// Returns when [`Context::tick`] should be called next.
+    pub fn deadline(&self) -> Option<Duration> {
+        self.pointer_target
+            .as_ref()
+            .and_then(|(_, gestures)| gestures.as_ref()?.1.deadline())
+    }
+
+    /// Stops delivering pointer events to the target once all pointers are released and no gestures remain.
+    fn release_pointer_target(&mut self) {
+        let finished = match &self.pointer_target {
+            Some((_, Some((_, arena)))) => arena.is_finished(),
+            _ => true,
+        };
+
+        if self.pointers == 0 && finished {
+            self.pointer_target = None;
+        }
+    }
+
+    /// Closes every popup which is not the surface or a parent of the surface.
+    fn dismiss_popups(&mut self, surface: SurfaceId) {
+        let mut keep = Vec::new();
+        let mut next = Some(surface);
+
+        while let Some(id) = next {
+            keep.push(id);
+            next = self.surface(id).and_then(|surface| surface.parent);
+        }
+
+        let popups = self
+            .surfaces
+            .iter()
+            .filter(|surface| {
+                matches!(surface.kind, SurfaceKind::Popup(_)) && !keep.contains(&surface.id)
+            })
+            .map(|surface| surface.id)
+            .collect::<Vec<_>>();
+
+        for popup in popups {
+            self.close_surface(popup);
+        }
+    }
+
+    /// Handles a scroll event on a surface.
+    ///
+    /// The event is delivered to the topmost node under the pointer, then to each parent of the node until a node
+    /// handles the event. Returns whether a node handled the event.
+    pub fn scroll(&mut self, surface: SurfaceId, event: ScrollEvent) -> bool {
+        let mut next = self.hit_test(surface, event.position, |_| true);
+
+        while let Some(id) = next {
+            if self.dispatch(id, &Event::Scroll(event)) {
+                return true;
+            }
+
+            next = self.view_tree.parent(id);
+        }
+
+        false
+    }
+
+    /// Returns the node with keyboard focus.
+    pub fn focus(&self) -> Option<NodeId> {
+        self.focus
+    }
+
+    /// Moves the keyboard focus to another node.
+    pub fn set_focus(&mut self, focus: Option<NodeId>) {
+        let previous = core::mem::replace(&mut self.focus, focus);
+        self.focus_changed(previous);
+    }
+
+    /// Delivers an input method event to the focused node.
+    ///
+    /// Returns whether the focused node handled the event.
+    pub fn ime(&mut self, event: ImeEvent<'_>) -> bool {
+        match self.focus {
+            Some(focus) => self.dispatch(focus, &Event::Ime(event)),
+            None => false,
+        }
+    }
+
+    /// Returns how the state of the text input changed since this was last called.
+    ///
+    /// The host should forward the update to the input method.
+    pub fn take_text_input_update(&mut self) -> Option<TextInputUpdate> {
+        let current = self
+            .focus
+            .and_then(|focus| self.view_tree.get(focus))
+            .and_then(ViewNode::text_input);
+
+        let update = match (&self.text_input, &current) {
+            (None, None) => None,
+            (None, Some(state)) => Some(TextInputUpdate::Enable(state.clone())),
+            (Some(_), None) => Some(TextInputUpdate::Disable),
+            (Some(previous), Some(state)) => {
+                (previous != state).then(|| TextInputUpdate::Update(state.clone()))
+            }
+        };
+
+        self.text_input = current;
+        update
+    }
+
+    /// Starts dragging the content of a node's [`DragSource`].
+    ///
+    /// The position is in the coordinates of the surface displaying the node.
+    ///
+    /// Returns false if the node is not a drag source.
+    pub fn begin_drag(&mut self, source: NodeId, position: Point) -> bool {
+        let content = match self.view_tree.get(source).and_then(ViewNode::drag_source) {
+            Some(source) => source.content.clone(),
+            None => return false,
+        };
+
+        let surface = match self.surface_of(source) {
+            Some(surface) => surface,
+            None => return false,
+        };
+
+        self.cancel_drag();
+        self.drag = Some(Drag {
+            content,
+            source: Some(source),
+            target: None,
+        });
+        self.drag_motion(surface, position);
+        true
+    }
+
+    /// Starts a drag of content offered by the host, such as content dragged from another application.
+    pub fn drag_enter(&mut self, surface: SurfaceId, content: Content, position: Point) {
+        self.cancel_drag();
+        self.drag = Some(Drag {
+            content,
+            source: None,
+            target: None,
+        });
+        self.drag_motion(surface, position);
+    }
+
+    /// Returns the content being dragged.
+    pub fn drag_content(&self) -> Option<&Content> {
+        self.drag.as_ref().map(|drag| &drag.content)
+    }
+
+    /// Moves the drag in progress to a position on a surface.
+    pub fn drag_motion(&mut self, surface: SurfaceId, position: Point) {
+        let mut drag = match self.drag.take() {
+            Some(drag) => drag,
+            None => return,
+        };
+
+        let target = self.hit_test(surface, position, |node| {
+            node.drop_target()
+                .is_some_and(|target| target.accepts(&drag.content))
+        });
+
+        if target == drag.target {
+            if let Some(target) = target {
+                self.dispatch(target, &Event::Drag(DragEvent::Over { position }));
+            }
+        } else {
+            if let Some(previous) = drag.target {
+                self.dispatch(previous, &Event::Drag(DragEvent::Leave));
+            }
+
+            if let Some(target) = target {
+                let event = DragEvent::Enter {
+                    position,
+                    content: &drag.content,
+                };
+                self.dispatch(target, &Event::Drag(event));
+            }
+
+            drag.target = target;
+        }
+
+        self.drag = Some(drag);
+    }
+
+    /// Notifies the drop target that the drag left the surface.
+    ///
+    /// The drag remains in progress and may re-enter the surface.
+    pub fn drag_leave(&mut self) {
+        if let Some(target) = self.drag.as_mut().and_then(|drag| drag.target.take()) {
+            self.dispatch(target, &Event::Drag(DragEvent::Leave));
+        }
+    }
+
+    /// Drops the content being dragged.
+    ///
+    /// Returns whether a drop target accepted the drop.
+    pub fn drop(&mut self, surface: SurfaceId, position: Point) -> bool {
+        self.drag_motion(surface, position);
+
+        let drag = match self.drag.take() {
+            Some(drag) => drag,
+            None => return false,
+        };
+
+        let accepted = match drag.target {
+            Some(target) => {
+                let event = DragEvent::Drop {
+                    position,
+                    content: &drag.content,
+                };
+                self.dispatch(target, &Event::Drag(event))
+            }
+
+            None => false,
+        };
+
+        if let Some(source) = drag.source {
+            self.dispatch(source, &Event::Drag(DragEvent::End { accepted }));
+        }
+
+        accepted
+    }
+
+    /// Cancels the drag in progress.
+    pub fn cancel_drag(&mut self) {
+        self.drag_leave();
+
+        if let Some(source) = self.drag.take().and_then(|drag| drag.source) {
+            self.dispatch(source, &Event::Drag(DragEvent::End { accepted: false }));
+        }
+    }
+
+    /// Delivers an event to a node.
+    ///
+    /// Returns whether the node handled the event.
+    fn dispatch(&mut self, id: NodeId, event: &Event<'_>) -> bool {
+        let index = self.surface_index_of(id);
+        let node = match self.view_tree.get_mut(id) {
+            Some(node) => node,
+            None => return false,
+        };
+
+        let focus = self.focus;
+        let mut cx = EventContext {
+            id,
+            data: &mut self.data,
+            clipboard: &mut *self.clipboard,
+            damage: &mut self.surfaces[index].damage,
+            focus: &mut self.focus,
+            history: &mut self.history,
+        };
+
+        let handled = node.event(&mut cx, event);
+
+        if self.focus != focus {
+            self.focus_changed(focus);
+        }
+
+        handled
+    }
+
+    /// Notifies the previously focused node and the focused node that the focus changed.
+    fn focus_changed(&mut self, previous: Option<NodeId>) {
+        if previous == self.focus {
+            return;
+        }
+
+        // Edits in different nodes are undone separately.
+        self.history.seal();
+
+        if let Some(previous) = previous {
+            self.dispatch(previous, &Event::Focus { focused: false });
+        }
+
+        if let Some(focus) = self.focus {
+            self.dispatch(focus, &Event::Focus { focused: true });
+        }
+    }
+
+    /// Adds a surface which is not built yet.
+    fn open_surface(
+        &mut self,
+        kind: SurfaceKind,
+        parent: Option<SurfaceId>,
+        anchor: Option<NodeId>,
+    ) -> SurfaceId {
+        let id = SurfaceId(self.next_surface);
+        self.next_surface += 1;
+
+        // Popups and tooltips are usually shown on the same output as their parent.
+        let scale_factor = parent.map_or(1.0, |parent| self.scale_factor(parent));
+
+        self.surface_events.push(SurfaceEvent::Created {
+            id,
+            parent,
+            kind: kind.clone(),
+        });
+        self.surfaces.push(Surface {
+            id,
+            kind,
+            parent,
+            anchor,
+            root: None,
+            damage: DamageTracker::new(),
+            scale_factor,
+        });
+
+        id
+    }
+
+    /// Opens a popup or tooltip positioned relative to the anchor node.
+    fn open_anchored<F>(&mut self, anchor: NodeId, mut kind: SurfaceKind, f: F) -> Option<SurfaceId>
+    where
+        F: FnOnce(&Data, &mut Scene<'_, Node>),
+    {
+        let parent = self.surface_of(anchor)?;
+
+        if let Some(positioner) = kind.positioner_mut() {
+            positioner.anchor_rect = self.view_tree.get(anchor)?.bounds();
+        }
+
+        let id = self.open_surface(kind, Some(parent), Some(anchor));
+        self.build_surface(id, f);
+        Some(id)
+    }
+
+    fn surface(&self, id: SurfaceId) -> Option<&Surface> {
+        self.surfaces.iter().find(|surface| surface.id == id)
+    }
+
+    fn surface_index(&self, id: SurfaceId) -> Option<usize> {
+        self.surfaces.iter().position(|surface| surface.id == id)
+    }
+
+    /// Returns the index of the surface displaying a node, falling back to the main window.
+    fn surface_index_of(&self, node: NodeId) -> usize {
+        self.surface_of(node)
+            .and_then(|id| self.surface_index(id))
+            .unwrap_or(0)
+    }
+
+    /// Returns the damage tracker of the surface displaying a node.
+    fn damage_tracker(&mut self, node: NodeId) -> &mut DamageTracker {
+        let index = self.surface_index_of(node);
+        &mut self.surfaces[index].damage
+    }
+
+    /// Returns a reference to the data associated with the context.
+    pub fn data(&self) -> &Data {
+        &self.data
+    }
+
+    /// Returns a mutable reference to the data associated with the context.
+    ///
+    /// This may be used to update the data outside of event handlers, such as advancing animations. The surfaces
+    /// should be rebuilt after the data changed.
+    pub fn data_mut(&mut self) -> &mut Data {
+        &mut self.data
+    }
+
+    /// Returns the undo and redo history of the data.
+    pub fn history(&self) -> &History<Data> {
+        &self.history
+    }
+
+    /// Returns a mutable reference to the undo and redo history, such as to set the limit.
+    pub fn history_mut(&mut self) -> &mut History<Data> {
+        &mut self.history
+    }
+
+    /// Applies an undoable command to the data outside of event handlers.
+    ///
+    /// The surfaces should be rebuilt after the data changed.
+    pub fn execute<C>(&mut self, command: C)
+    where
+        C: Command<Data> + 'static,
+    {
+        self.history.execute(&mut self.data, command, None);
+    }
+
+    /// Reverts the last transaction of the history.
+    ///
+    /// The nodes which executed the commands of the transaction are marked as damaged. Returns whether a
+    /// transaction was undone, in which case the surfaces should be rebuilt.
+    pub fn undo(&mut self) -> bool {
+        let nodes = match self.history.undo(&mut self.data) {
+            Some(transaction) => transaction.nodes().to_vec(),
+            None => return false,
+        };
+
+        self.mark_nodes_damaged(&nodes);
+        true
+    }
+
+    /// Applies the last undone transaction of the history again.
+    ///
+    /// The nodes which executed the commands of the transaction are marked as damaged. Returns whether a
+    /// transaction was redone, in which case the surfaces should be rebuilt.
+    pub fn redo(&mut self) -> bool {
+        let nodes = match self.history.redo(&mut self.data) {
+            Some(transaction) => transaction.nodes().to_vec(),
+            None => return false,
+        };
+
+        self.mark_nodes_damaged(&nodes);
+        true
+    }
+
+    /// Marks the nodes which still exist as damaged.
+    fn mark_nodes_damaged(&mut self, nodes: &[NodeId]) {
+        for &node in nodes {
+            if self.view_tree.get(node).is_some() {
+                self.mark_damaged(node);
+            }
+        }
+    }
+
+    /// Captures the data of the context, the local state of the nodes and the focused node.
+    #[cfg(feature = "serde")]
+    pub fn snapshot(&self) -> snapshot::Snapshot<Data>
+    where
+        Data: Clone,
+    {
+        let mut nodes = Vec::new();
+        let mut focus = None;
+
+        for surface in &self.surfaces {
+            let Some(root) = surface.root else {
+                continue;
+            };
+
+            for (id, path) in snapshot::paths(&self.view_tree, surface.id, root, ViewNode::key) {
+                if self.focus == Some(id) {
+                    focus = Some(path.clone());
+                }
+
+                if let Some(state) = self.view_tree.get(id).and_then(ViewNode::save_state) {
+                    nodes.push((path, state));
+                }
+            }
+        }
+
+        snapshot::Snapshot {
+            data: self.data.clone(),
+            nodes,
+            focus,
+        }
+    }
+
+    /// Replaces the data of the context with the data of a snapshot.
+    ///
+    /// The surfaces should be rebuilt afterwards. The local state of the nodes and the focus are restored when the
+    /// surface displaying them is next built, into the nodes at the same [paths](snapshot::NodePath). Surfaces
+    /// which are not built by the next time the snapshot is restored are skipped. The history is cleared, since
+    /// it's commands apply to the replaced data.
+    #[cfg(feature = "serde")]
+    pub fn restore(&mut self, snapshot: snapshot::Snapshot<Data>) {
+        self.data = snapshot.data;
+        self.history.clear();
+        self.restoring = Some(snapshot::Snapshot {
+            data: (),
+            nodes: snapshot.nodes,
+            focus: snapshot.focus,
+        });
+    }
+
+    /// Restores the local state of the nodes of a surface which was just built.
+    #[cfg(feature = "serde")]
+    fn restore_surface(&mut self, id: SurfaceId) {
+        let Some(root) = self.surface_root(id) else {
+            return;
+        };
+
+        let Some(restoring) = &mut self.restoring else {
+            return;
+        };
+
+        for (path, state) in &restoring.nodes {
+            if path.surface != id {
+                continue;
+            }
+
+            if let Some(node) = snapshot::find(&self.view_tree, root, path, ViewNode::key) {
+                self.view_tree.get_mut(node).unwrap().restore_state(state);
+            }
+        }
+
+        restoring.nodes.retain(|(path, _)| path.surface != id);
+
+        let focus = match &restoring.focus {
+            Some(path) if path.surface == id => {
+                let focus = snapshot::find(&self.view_tree, root, path, ViewNode::key);
+                restoring.focus = None;
+                focus
+            }
+
+            _ => None,
+        };
+
+        if restoring.nodes.is_empty() && restoring.focus.is_none() {
+            self.restoring = None;
+        }
+
+        if focus.is_some() {
+            self.set_focus(focus);
         }
     }
 
@@ -107,10 +1125,120 @@ where
     // TODO: Visitor
 }
 
+/// A surface displaying one of the roots of the tree.
+struct Surface {
+    id: SurfaceId,
+    kind: SurfaceKind,
+    /// The surface a popup or tooltip is positioned relative to.
+    parent: Option<SurfaceId>,
+    /// The node a popup or tooltip is positioned relative to.
+    anchor: Option<NodeId>,
+    /// The root node of the surface, which is [`None`] until the surface is built.
+    root: Option<NodeId>,
+    /// Tracks which parts of the surface need to be repainted.
+    damage: DamageTracker,
+    /// The number of physical pixels per logical unit.
+    scale_factor: f32,
+}
+
 /// An object-safe view node which type erases a [`View`].
 ///
 /// This trait may be extended to allow nodes to provide extra functionality, such as layout or accessibility
 /// information.
 pub trait ViewNode<T> {
     fn rebuild(&mut self);
+
+    /// The area the node occupies on the surface.
+    ///
+    /// This is used to determine which areas of the surface must be repainted when the node changes.
+    fn bounds(&self) -> Rect {
+        Rect::ZERO
+    }
+
+    /// What the node represents to the user.
+    fn role(&self) -> Role {
+        Role::None
+    }
+
+    /// The text describing the node, such as the text of a button.
+    fn label(&self) -> Option<&str> {
+        None
+    }
+
+    /// Handles an event.
+    ///
+    /// Returns whether the event was handled.
+    fn event(&mut self, cx: &mut EventContext<'_, T>, event: &Event<'_>) -> bool {
+        let _ = (cx, event);
+        false
+    }
+
+    /// The state of the text input if this node is a text input client.
+    ///
+    /// The input method is enabled while a text input client has focus.
+    fn text_input(&self) -> Option<TextInputState> {
+        None
+    }
+
+    /// Creates the gesture recognizers for this node.
+    ///
+    /// This is called when a pointer is pressed on the node.
+    fn gestures(&self) -> Option<GestureArena> {
+        None
+    }
+
+    /// The data provided when this node is dragged.
+    fn drag_source(&self) -> Option<&DragSource> {
+        None
+    }
+
+    /// The data this node accepts when dropped on.
+    fn drop_target(&self) -> Option<&DropTarget> {
+        None
+    }
+
+    /// Returns whether this node paints the same as the `previous` node it replaced when the surface was rebuilt.
+    ///
+    /// Nodes which paint differently are damaged. By default every rebuilt node is assumed to have changed.
+    fn paints_same(&self, previous: &Self) -> bool
+    where
+        Self: Sized,
+    {
+        let _ = previous;
+        false
+    }
+
+    /// Called when the scale factor of the surface containing this node changes.
+    ///
+    /// Nodes which cache content rasterized in physical pixels, such as text, should discard the content so it is
+    /// rasterized again at the new scale factor.
+    fn scale_factor_changed(&mut self, scale_factor: f32) {
+        let _ = scale_factor;
+    }
+
+    /// Identifies this node among it's siblings in a [snapshot](snapshot), such as the id of the item displayed
+    /// by the node.
+    ///
+    /// Nodes with a key are found by the key when a snapshot is restored, so their state is restored even if
+    /// siblings were added or removed. Nodes without a key are found by their index among their siblings. The keys
+    /// of siblings must be unique.
+    #[cfg(feature = "serde")]
+    fn key(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the state of this node which should be included in a [snapshot](snapshot), such as whether a
+    /// section is expanded.
+    ///
+    /// State derived from the data of the context should not be included.
+    #[cfg(feature = "serde")]
+    fn save_state(&self) -> Option<snapshot::LocalState> {
+        None
+    }
+
+    /// Restores state returned by [`ViewNode::save_state`] after the node was built from a restored snapshot.
+    #[cfg(feature = "serde")]
+    fn restore_state(&mut self, state: &snapshot::LocalState) {
+        let _ = state;
+    }
 }
@@ -0,0 +1,547 @@
+//! Painting of the retained tree using a [`renderer`].
+//!
+//! The tree is painted in depth first order. A parent is painted before it's children, meaning children appear
+//! above their parent. Siblings are painted in order of their [`z_index`](Paint::z_index), falling back to the
+//! order the siblings were built in.
+//!
+//! Nodes paint in logical units. The painter converts everything painted to physical pixels using the scale factor
+//! of the surface, snapping edges to whole pixels.
+//!
+//! The [clip](Paint::clip) of a node is pushed to the renderer with [`RenderClip`] while it's children are painted.
+//! Solid rectangles and images are shrunk to the clip instead, which is cheaper for the renderer.
+//!
+//! Nodes paint quads, and images and runs of text if the renderer implements [`RenderImage`]. Text is painted as
+//! glyphs rasterized in physical pixels, such as by `text::raster::GlyphCache`, and uploaded as [`A8`] images with
+//! [`CreateImage`](renderer::CreateImage).
+//!
+//! [`A8`]: renderer::Format::A8
+
+use alloc::vec::Vec;
+
+use renderer::{
+    Border, CornerRadii, DrawImage, Filter, Image, Quad, RenderClip, RenderImage, RenderQuad,
+    Renderer,
+};
+
+use crate::{
+    geometry::{Point, Rect},
+    tree::{NodeId, RetainedTree},
+};
+
+/// A node which can be painted with the renderer `R`.
+pub trait Paint<R>
+where
+    R: Renderer + RenderQuad + RenderClip,
+{
+    /// Paints the node.
+    ///
+    /// Anything painted is clipped to the clip of the parent nodes.
+    fn paint(&self, painter: &mut Painter<'_, R>);
+
+    /// The area the children of this node are clipped to.
+    ///
+    /// Returns [`None`] if the children are not clipped.
+    fn clip(&self) -> Option<Rect> {
+        None
+    }
+
+    /// The order of this node relative to it's siblings.
+    ///
+    /// Siblings with a higher z index are painted above siblings with a lower z index.
+    fn z_index(&self) -> i32 {
+        0
+    }
+}
+
+/// Issues draw calls to a renderer on behalf of a node.
+pub struct Painter<'a, R> {
+    renderer: &'a mut R,
+    /// The clip in logical units.
+    clip: Option<Rect>,
+    scale_factor: f32,
+}
+
+impl<R> Painter<'_, R>
+where
+    R: Renderer + RenderQuad,
+{
+    /// Returns the area that painting is clipped to, in logical units.
+    pub fn clip(&self) -> Option<Rect> {
+        self.clip
+    }
+
+    /// Returns the number of physical pixels per logical unit of the surface being painted.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Draws a quad in logical units, clipped to the current clip.
+    ///
+    /// The quad is scaled to physical pixels and it's edges are snapped to whole pixels. The radii and border width
+    /// are scaled, while the softness remains in physical pixels so edges are equally sharp at any scale factor.
+    ///
+    /// Solid rectangles are shrunk to the clip and drawn with hard edges, so the clipped edges meet the content
+    /// next to the clip without a seam. Other quads are drawn whole and clipped by the renderer.
+    pub fn draw_quad(&mut self, quad: Quad) {
+        let rect = Rect::new(quad.x, quad.y, quad.width, quad.height)
+            .scale(self.scale_factor)
+            .snap();
+
+        let clipped = match self.physical_clip() {
+            Some(clip) => match rect.intersect(&clip) {
+                Some(clipped) => clipped,
+                // Entirely clipped.
+                None => return,
+            },
+
+            None if rect.is_empty() => return,
+            None => rect,
+        };
+
+        // Shrinking a quad with rounded corners, a border or a gradient would change it's shape.
+        let (rect, softness) = if quad.is_rectangle() && clipped != rect {
+            (clipped, 0.0)
+        } else {
+            (rect, quad.softness)
+        };
+
+        self.renderer.draw_quad(Quad {
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+            radii: quad.radii.scale(self.scale_factor),
+            border: quad.border.map(|border| Border {
+                width: border.width * self.scale_factor,
+                ..border
+            }),
+            softness,
+            ..quad
+        });
+    }
+
+    /// Returns the clip in physical pixels, snapped to whole pixels.
+    fn physical_clip(&self) -> Option<Rect> {
+        self.clip.map(|clip| clip.scale(self.scale_factor).snap())
+    }
+
+    /// Returns the underlying renderer.
+    ///
+    /// Anything drawn using the renderer directly is in physical pixels and is not clipped.
+    pub fn renderer(&mut self) -> &mut R {
+        self.renderer
+    }
+}
+
+impl<R> Painter<'_, R>
+where
+    R: Renderer + RenderQuad + RenderImage,
+{
+    /// Draws an image into a destination in logical units, clipped to the current clip.
+    ///
+    /// The destination is scaled to physical pixels and it's edges are snapped to whole pixels.
+    pub fn draw_image(&mut self, image: &R::Image, draw: DrawImage) {
+        let [x, y, width, height] = draw.destination;
+        let rect = Rect::new(x, y, width, height)
+            .scale(self.scale_factor)
+            .snap();
+
+        self.draw_physical_image(image, rect, draw);
+    }
+
+    /// Draws a run of glyphs, tinting the images of the glyphs with the color of the run.
+    ///
+    /// Each image is drawn at it's size in physical pixels, so glyphs remain sharp at any scale factor.
+    pub fn draw_text_run(&mut self, run: &TextRun<'_, R::Image>) {
+        for glyph in run.glyphs {
+            // The offset is relative to the whole pixel containing the position.
+            let position = glyph.position.scale(self.scale_factor);
+            let [offset_x, offset_y] = glyph.offset;
+            let rect = Rect::new(
+                position.x.floor() + offset_x as f32,
+                position.y.floor() + offset_y as f32,
+                glyph.image.width() as f32,
+                glyph.image.height() as f32,
+            );
+
+            let draw = DrawImage {
+                tint: run.color,
+                filter: Filter::Nearest,
+                ..DrawImage::new(0.0, 0.0, 0.0, 0.0)
+            };
+            self.draw_physical_image(glyph.image, rect, draw);
+        }
+    }
+
+    /// Draws an image into a destination in physical pixels, shrinking the destination and the source to the clip.
+    fn draw_physical_image(&mut self, image: &R::Image, rect: Rect, draw: DrawImage) {
+        let clipped = match self.physical_clip() {
+            Some(clip) => match rect.intersect(&clip) {
+                Some(clipped) => clipped,
+                None => return,
+            },
+
+            None if rect.is_empty() => return,
+            None => rect,
+        };
+
+        // Take the same fraction of the source as the clip takes of the destination.
+        let [u, v, source_width, source_height] = draw.source;
+        let scale_x = source_width / rect.width;
+        let scale_y = source_height / rect.height;
+        let source = [
+            u + (clipped.x - rect.x) * scale_x,
+            v + (clipped.y - rect.y) * scale_y,
+            clipped.width * scale_x,
+            clipped.height * scale_y,
+        ];
+
+        self.renderer.draw_image(
+            image,
+            DrawImage {
+                destination: [clipped.x, clipped.y, clipped.width, clipped.height],
+                source,
+                ..draw
+            },
+        );
+    }
+}
+
+/// A run of text painted with [`Painter::draw_text_run`], as glyphs rasterized into images.
+#[derive(Debug)]
+pub struct TextRun<'a, I> {
+    pub glyphs: &'a [Glyph<'a, I>],
+    /// The color the images of the glyphs are tinted with.
+    ///
+    /// Glyphs rasterized as [`A8`](renderer::Format::A8) masks are drawn in this color. Color glyphs such as emoji
+    /// should be drawn in a run with a white color.
+    pub color: [f32; 4],
+}
+
+/// A glyph rasterized into an image.
+#[derive(Debug)]
+pub struct Glyph<'a, I> {
+    pub image: &'a I,
+    /// The position of the glyph on the baseline, in logical units.
+    pub position: Point,
+    /// The offset of the top left corner of the image from the whole physical pixel containing the position, in
+    /// physical pixels.
+    ///
+    /// For glyphs rasterized by `text::raster::GlyphCache`, this is the left and the negated top of the placement of
+    /// the image.
+    pub offset: [i32; 2],
+}
+
+/// Paints a root node and all of it's descendants on a surface with a scale factor.
+pub fn paint<N, R>(tree: &RetainedTree<N>, root: NodeId, renderer: &mut R, scale_factor: f32)
+where
+    N: Paint<R>,
+    R: Renderer + RenderQuad + RenderClip,
+{
+    if tree.get(root).is_some() {
+        paint_node(tree, root, renderer, None, scale_factor);
+    }
+}
+
+/// Paints the descendants of a root node which are visible inside of `clip`, in logical units.
+///
+/// This may be used to only repaint the [damaged](crate::damage::Damage) areas of a surface.
+pub fn paint_clipped<N, R>(
+    tree: &RetainedTree<N>,
+    root: NodeId,
+    renderer: &mut R,
+    clip: Rect,
+    scale_factor: f32,
+) where
+    N: Paint<R>,
+    R: Renderer + RenderQuad + RenderClip,
+{
+    if tree.get(root).is_some() {
+        renderer.push_clip(physical(clip, scale_factor), CornerRadii::ZERO);
+        paint_node(tree, root, renderer, Some(clip), scale_factor);
+        renderer.pop_clip();
+    }
+}
+
+/// Returns a rectangle in logical units as the physical x, y, width and height, snapped to whole pixels.
+fn physical(rect: Rect, scale_factor: f32) -> [f32; 4] {
+    let rect = rect.scale(scale_factor).snap();
+    [rect.x, rect.y, rect.width, rect.height]
+}
+
+fn paint_node<N, R>(
+    tree: &RetainedTree<N>,
+    id: NodeId,
+    renderer: &mut R,
+    clip: Option<Rect>,
+    scale_factor: f32,
+) where
+    N: Paint<R>,
+    R: Renderer + RenderQuad + RenderClip,
+{
+    let node = tree.get(id).expect("Node must exist");
+
+    node.paint(&mut Painter {
+        renderer,
+        clip,
+        scale_factor,
+    });
+
+    let clip = match (clip, node.clip()) {
+        (Some(parent), Some(clip)) => match parent.intersect(&clip) {
+            Some(clip) => Some(clip),
+            // The children are entirely clipped.
+            None => return,
+        },
+
+        (parent, clip) => clip.or(parent),
+    };
+
+    let mut children = tree
+        .children(id)
+        .map(|(id, node)| (node.z_index(), id))
+        .collect::<Vec<_>>();
+
+    // The sort is stable so siblings with the same z index are painted in order.
+    children.sort_by_key(|&(z_index, _)| z_index);
+
+    if let Some(clip) = node.clip() {
+        renderer.push_clip(physical(clip, scale_factor), CornerRadii::ZERO);
+    }
+
+    for (_, child) in children {
+        paint_node(tree, child, renderer, clip, scale_factor);
+    }
+
+    if node.clip().is_some() {
+        renderer.pop_clip();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::{vec, vec::Vec};
+    use core::fmt;
+
+    use renderer::{CornerRadii, DrawImage, Quad, RenderClip, RenderImage, RenderQuad, Renderer};
+
+    use crate::{
+        geometry::{Point, Rect},
+        tree::RetainedTree,
+    };
+
+    use super::{paint, Glyph, Paint, Painter, TextRun};
+
+    #[derive(Debug)]
+    struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("error")
+        }
+    }
+
+    impl std::error::Error for Error {}
+
+    struct Image(u32, u32);
+
+    impl renderer::Image<Recorder> for Image {
+        fn width(&self) -> u32 {
+            self.0
+        }
+
+        fn height(&self) -> u32 {
+            self.1
+        }
+    }
+
+    /// Records the destinations of quads and the destinations and sources of images, the clips pushed and popped,
+    /// and the softness of quads.
+    #[derive(Default)]
+    struct Recorder(Vec<[f32; 4]>, Vec<Option<[f32; 4]>>, Vec<f32>);
+
+    impl Renderer for Recorder {
+        type Error = Error;
+        type Image = Image;
+    }
+
+    impl RenderQuad for Recorder {
+        fn draw_quad(&mut self, quad: Quad) {
+            self.0.push([quad.x, quad.y, quad.width, quad.height]);
+            self.2.push(quad.softness);
+        }
+    }
+
+    impl RenderClip for Recorder {
+        fn push_clip(&mut self, rect: [f32; 4], _: CornerRadii) {
+            self.1.push(Some(rect));
+        }
+
+        fn pop_clip(&mut self) {
+            self.1.push(None);
+        }
+    }
+
+    impl RenderImage for Recorder {
+        fn draw_image(&mut self, _: &Image, draw: DrawImage) {
+            self.0.push(draw.destination);
+            self.0.push(draw.source);
+        }
+    }
+
+    struct Node {
+        rect: [f32; 4],
+        radius: f32,
+        clip: Option<Rect>,
+        z_index: i32,
+    }
+
+    impl Node {
+        fn new(rect: [f32; 4]) -> Self {
+            Self {
+                rect,
+                radius: 0.0,
+                clip: None,
+                z_index: 0,
+            }
+        }
+    }
+
+    impl Paint<Recorder> for Node {
+        fn paint(&self, painter: &mut Painter<'_, Recorder>) {
+            let [x, y, width, height] = self.rect;
+            painter.draw_quad(Quad {
+                radii: CornerRadii::uniform(self.radius),
+                ..Quad::new(x, y, width, height, [1.0; 4])
+            });
+        }
+
+        fn clip(&self) -> Option<Rect> {
+            self.clip
+        }
+
+        fn z_index(&self) -> i32 {
+            self.z_index
+        }
+    }
+
+    #[test]
+    fn clip_and_z_order() {
+        let mut tree = RetainedTree::new();
+        let mut root = tree.set_root(Node {
+            clip: Some(Rect::new(0.0, 0.0, 100.0, 100.0)),
+            ..Node::new([0.0, 0.0, 100.0, 100.0])
+        });
+
+        let _ = root.push_child(Node {
+            z_index: 1,
+            ..Node::new([50.0, 50.0, 100.0, 100.0])
+        });
+        let _ = root.push_child(Node::new([10.0, 10.0, 10.0, 10.0]));
+        // Entirely outside of the clip.
+        let _ = root.push_child(Node::new([200.0, 200.0, 10.0, 10.0]));
+        // Rounded quads are drawn whole and clipped by the renderer.
+        let _ = root.push_child(Node {
+            radius: 5.0,
+            ..Node::new([90.0, 0.0, 20.0, 20.0])
+        });
+
+        let mut recorder = Recorder::default();
+        paint(&tree, tree.root().unwrap(), &mut recorder, 1.0);
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                [0.0, 0.0, 100.0, 100.0],
+                [10.0, 10.0, 10.0, 10.0],
+                [90.0, 0.0, 20.0, 20.0],
+                [50.0, 50.0, 50.0, 50.0]
+            ]
+        );
+        assert_eq!(recorder.1, vec![Some([0.0, 0.0, 100.0, 100.0]), None]);
+        // The shrunk quad has hard edges.
+        assert_eq!(recorder.2, vec![1.0, 1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn fractional_scale_factor() {
+        let mut tree = RetainedTree::new();
+        let mut root = tree.set_root(Node {
+            clip: Some(Rect::new(0.0, 0.0, 15.0, 15.0)),
+            ..Node::new([0.0, 0.0, 30.0, 30.0])
+        });
+
+        // Adjacent nodes remain adjacent after snapping to physical pixels.
+        let _ = root.push_child(Node::new([1.0, 0.0, 9.0, 10.0]));
+        let _ = root.push_child(Node::new([10.0, 0.0, 10.0, 10.0]));
+
+        let mut recorder = Recorder::default();
+        paint(&tree, tree.root().unwrap(), &mut recorder, 1.25);
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                [0.0, 0.0, 38.0, 38.0],
+                [1.0, 0.0, 12.0, 13.0],
+                [13.0, 0.0, 6.0, 13.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn images_and_text() {
+        struct Text;
+
+        impl Paint<Recorder> for Text {
+            fn paint(&self, painter: &mut Painter<'_, Recorder>) {
+                let image = Image(40, 20);
+                painter.draw_image(&image, DrawImage::new(0.0, 0.0, 20.0, 10.0));
+
+                let glyph = Image(4, 6);
+                let glyphs = [
+                    Glyph {
+                        image: &glyph,
+                        position: Point::new(2.3, 10.0),
+                        offset: [1, -5],
+                    },
+                    // Entirely outside of the clip.
+                    Glyph {
+                        image: &glyph,
+                        position: Point::new(50.0, 10.0),
+                        offset: [1, -5],
+                    },
+                ];
+                painter.draw_text_run(&TextRun {
+                    glyphs: &glyphs,
+                    color: [0.0, 0.0, 0.0, 1.0],
+                });
+            }
+        }
+
+        let mut tree = RetainedTree::new();
+        let _ = tree.set_root(Text);
+
+        // Clip the right half of the image away.
+        let mut recorder = Recorder::default();
+        super::paint_clipped(
+            &tree,
+            tree.root().unwrap(),
+            &mut recorder,
+            Rect::new(0.0, 0.0, 10.0, 20.0),
+            2.0,
+        );
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                [0.0, 0.0, 20.0, 20.0],
+                [0.0, 0.0, 0.5, 1.0],
+                // The glyph is drawn at it's size in physical pixels, offset from the pixel containing it's position.
+                [5.0, 15.0, 4.0, 6.0],
+                [0.0, 0.0, 1.0, 1.0],
+            ]
+        );
+    }
+}
@@ -1,22 +1,301 @@
-use core::marker::PhantomData;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
 
-use crate::view::View;
+use crate::{
+    layout::Direction,
+    tree::{NodeId, RetainedTree},
+    view::View,
+};
 
+/// A scene is used by a [`View`] to add nodes to the retained tree.
+///
+/// A scene either builds a root of the tree or the children of a [`Node`].
+///
+/// Building again reuses the existing nodes: each node built replaces the data of the node at the same position,
+/// keeping the id of the node, and nodes which were not built again are removed. Nodes therefore keep their ids
+/// across rebuilds as long as the structure of the views does not change.
+///
+/// A scene also carries the environment views are built in, which is inherited by the children of the nodes
+/// built in the scene.
 pub struct Scene<'a, N> {
-    _marker: PhantomData<&'a N>,
+    tree: &'a mut RetainedTree<N>,
+    /// Where nodes built in this scene are placed.
+    target: Target<'a>,
+    direction: Direction,
+    /// Collects the previous data of reused nodes, if the caller compares the data.
+    replaced: Option<&'a mut Vec<(NodeId, N)>>,
 }
 
-impl<N> Scene<'_, N> {
+/// Where the nodes built in a [`Scene`] are placed in the tree.
+enum Target<'a> {
+    /// The built node replaces an existing root, or becomes a new root if [`None`].
+    Root(Option<NodeId>),
+
+    /// The built node replaces the next existing child of a node, or becomes a new child if every existing child
+    /// was replaced.
+    Children {
+        parent: NodeId,
+        /// The next existing child, shared with the [`Node`] of the parent.
+        next: &'a mut Option<NodeId>,
+    },
+}
+
+impl<'a, N> Scene<'a, N> {
+    /// Creates a scene which builds one of the roots of the tree, leaving the other roots intact.
+    ///
+    /// If `root` is [`None`], a new root is added.
+    pub(crate) fn root(tree: &'a mut RetainedTree<N>, root: Option<NodeId>) -> Self {
+        Self {
+            tree,
+            target: Target::Root(root),
+            direction: Direction::default(),
+            replaced: None,
+        }
+    }
+
+    /// Creates a scene which builds one of the roots of the tree, collecting the previous data of every reused
+    /// node into `replaced`.
+    pub(crate) fn rebuild(
+        tree: &'a mut RetainedTree<N>,
+        root: Option<NodeId>,
+        replaced: &'a mut Vec<(NodeId, N)>,
+    ) -> Self {
+        Self {
+            replaced: Some(replaced),
+            ..Self::root(tree, root)
+        }
+    }
+
+    /// Returns the [layout direction](crate::layout) of the views built in this scene.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Sets the layout direction of the views built in this scene afterwards.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Returns the root built by this scene, or the root which would be replaced if nothing was built.
+    pub(crate) fn built_root(&self) -> Option<NodeId> {
+        match self.target {
+            Target::Root(root) => root,
+            Target::Children { .. } => None,
+        }
+    }
+
+    /// Adds a node created from the state of a view to the tree.
+    ///
+    /// If a node exists at the position of the built node, the data of the node is replaced and it's children are
+    /// reused by the children built with the returned [`Node`].
     pub fn build_node<V, T>(&mut self, state: V::State) -> Node<'_, N>
     where
         V: View<T>,
         N: From<V::State>,
     {
         let node = N::from(state);
-        todo!()
+
+        let existing = match &mut self.target {
+            Target::Root(root) => *root,
+            Target::Children { next, .. } => {
+                let existing = **next;
+                **next = existing.and_then(|id| self.tree.next_sibling(id));
+                existing
+            }
+        };
+
+        let id = match (existing, &mut self.target) {
+            (Some(id), _) => {
+                let previous = self.tree.update(id, node);
+
+                if let Some(replaced) = &mut self.replaced {
+                    replaced.push((id, previous));
+                }
+
+                id
+            }
+
+            (None, Target::Root(root)) => {
+                let id = self.tree.push_root(node).id();
+                // Building again replaces the new root.
+                *root = Some(id);
+                id
+            }
+
+            (None, Target::Children { parent, .. }) => self.tree.push_child(*parent, node).id(),
+        };
+
+        Node {
+            next: self.tree.children(id).next().map(|(id, _)| id),
+            tree: self.tree,
+            id,
+            direction: self.direction,
+            replaced: self.replaced.as_deref_mut(),
+        }
     }
 }
 
+/// A handle to a node that was built in a [`Scene`].
+///
+/// When the handle is dropped, the existing children of the node which were not built again are removed.
 pub struct Node<'a, N> {
-    _marker: PhantomData<&'a N>,
+    tree: &'a mut RetainedTree<N>,
+    id: NodeId,
+    /// The layout direction of the children.
+    direction: Direction,
+    /// The next existing child which is reused when building a child.
+    next: Option<NodeId>,
+    replaced: Option<&'a mut Vec<(NodeId, N)>>,
+}
+
+impl<'a, N> Node<'a, N> {
+    /// Returns the id of the node in the retained tree.
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Returns the layout direction of the children of this node.
+    ///
+    /// This is the direction of the scene the node was built in, unless changed with [`Node::set_direction`].
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Sets the layout direction of the children built afterwards, such as for a node containing text in another
+    /// language.
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Returns a scene used to build the children of this node.
+    pub fn children(&mut self) -> Scene<'_, N> {
+        Scene {
+            tree: self.tree,
+            target: Target::Children {
+                parent: self.id,
+                next: &mut self.next,
+            },
+            direction: self.direction,
+            replaced: self.replaced.as_deref_mut(),
+        }
+    }
+
+    /// Builds a view as the next child of this node.
+    pub fn build_child<V, T>(&mut self, view: &V) -> NodeId
+    where
+        V: View<T>,
+        N: From<V::State>,
+    {
+        view.build(&mut self.children()).id()
+    }
+}
+
+impl<N> Deref for Node<'_, N> {
+    type Target = N;
+
+    fn deref(&self) -> &Self::Target {
+        self.tree.get(self.id).unwrap()
+    }
+}
+
+impl<N> DerefMut for Node<'_, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.tree.get_mut(self.id).unwrap()
+    }
+}
+
+impl<N> Drop for Node<'_, N> {
+    fn drop(&mut self) {
+        while let Some(child) = self.next {
+            self.next = self.tree.next_sibling(child);
+            self.tree.remove(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        scene::{Node, Scene},
+        tree::RetainedTree,
+        view::View,
+    };
+
+    struct Label(u32);
+
+    impl View<()> for Label {
+        type State = u32;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            scene.build_node::<Self, ()>(self.0)
+        }
+    }
+
+    struct Column(u32, Vec<Label>);
+
+    impl View<()> for Column {
+        type State = u32;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            let mut node = scene.build_node::<Self, ()>(self.0);
+
+            for child in &self.1 {
+                node.build_child(child);
+            }
+
+            node
+        }
+    }
+
+    #[test]
+    fn build_nested() {
+        let view = Column(0, vec![Label(1), Label(2), Label(3)]);
+        let mut tree = RetainedTree::<u32>::new();
+        let root = view.build(&mut Scene::root(&mut tree, None)).id();
+
+        assert_eq!(tree.root(), Some(root));
+        assert_eq!(
+            tree.iter().map(|(_, &n)| n).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            tree.children(root).map(|(_, &n)| n).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+
+        // Building another root leaves the existing roots intact.
+        let other = Label(4).build(&mut Scene::root(&mut tree, None)).id();
+        assert_eq!(tree.roots().count(), 2);
+        assert_eq!(tree.root_of(other), Some(other));
+
+        // Building a root again reuses the nodes at the same positions, keeping their ids.
+        let children = tree.children(root).map(|(id, _)| id).collect::<Vec<_>>();
+        let view = Column(6, vec![Label(7), Label(8)]);
+        view.build(&mut Scene::root(&mut tree, Some(root)));
+        assert_eq!(
+            tree.children(root)
+                .map(|(id, &n)| (id, n))
+                .collect::<Vec<_>>(),
+            [(children[0], 7), (children[1], 8)]
+        );
+        assert_eq!(tree.get(root), Some(&6));
+        assert_eq!(tree.get(children[2]), None);
+
+        // Children which are not built again are removed.
+        Label(5).build(&mut Scene::root(&mut tree, Some(root)));
+        assert_eq!(tree.iter().map(|(_, &n)| n).collect::<Vec<_>>(), [5, 4]);
+        assert_eq!(tree.root(), Some(root));
+
+        tree.remove(root);
+        assert_eq!(tree.root(), Some(other));
+        assert_eq!(tree.len(), 1);
+    }
 }
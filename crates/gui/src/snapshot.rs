@@ -0,0 +1,275 @@
+//! Snapshots of the state of a [`Context`](crate::Context).
+//!
+//! A [`Snapshot`] captures the data of a context together with state local to nodes, such as whether a section is
+//! expanded, and which node has focus. Snapshots may be serialized to recover after a crash or to keep the state
+//! of the user interface while reloading code during development.
+//!
+//! Node ids are not stable between runs, so nodes are identified by their [`NodePath`], the position of the node
+//! below the root of it's surface. Each step of the path is the [key](crate::ViewNode::key) of the node if the
+//! view supplied one, or the index of the node among it's siblings otherwise. Restoring a snapshot with [`Context::restore`](crate::Context::restore)
+//! replaces the data, and the local state is restored into nodes at the same paths when their surfaces are built
+//! afterwards. Building the same views from the restored data places the nodes at the same paths.
+//!
+//! Nodes opt into snapshots by implementing [`ViewNode::save_state`](crate::ViewNode::save_state) and
+//! [`ViewNode::restore_state`](crate::ViewNode::restore_state). State which lives in the data, such as a
+//! [`Scroller`](crate::view::scroll::Scroller), is captured with the data.
+
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    geometry::Point,
+    surface::SurfaceId,
+    tree::{NodeId, RetainedTree},
+};
+
+/// State local to a node which is not derived from the data of the context.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LocalState {
+    Bool(bool),
+    Number(f64),
+    Point(Point),
+    Text(String),
+    List(Vec<LocalState>),
+}
+
+/// Identifies a node by it's position in the tree, which is stable when the tree is built again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NodePath {
+    /// The surface displaying the node.
+    pub surface: SurfaceId,
+    /// Identifies each node among it's siblings, starting with a child of the root of the surface.
+    pub steps: Vec<PathStep>,
+}
+
+/// Identifies a node among it's siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PathStep {
+    /// The [key](crate::ViewNode::key) supplied by the node.
+    Key(u64),
+    /// The index of a node without a key among it's siblings.
+    Index(u32),
+}
+
+/// The data of a context and the state of it's nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot<Data> {
+    pub data: Data,
+    pub(crate) nodes: Vec<(NodePath, LocalState)>,
+    pub(crate) focus: Option<NodePath>,
+}
+
+impl<Data> Snapshot<Data> {
+    /// Returns the local state of the nodes in the snapshot.
+    pub fn nodes(&self) -> &[(NodePath, LocalState)] {
+        &self.nodes
+    }
+
+    /// Returns the node which had focus.
+    pub fn focus(&self) -> Option<&NodePath> {
+        self.focus.as_ref()
+    }
+}
+
+/// Returns the path of every node of a surface in depth first order.
+///
+/// `key` returns the key of a node, see [`ViewNode::key`](crate::ViewNode::key).
+pub(crate) fn paths<N, F>(
+    tree: &RetainedTree<N>,
+    surface: SurfaceId,
+    root: NodeId,
+    key: F,
+) -> Vec<(NodeId, NodePath)>
+where
+    F: Fn(&N) -> Option<u64>,
+{
+    let mut paths = Vec::new();
+    let mut stack = Vec::from([(
+        root,
+        NodePath {
+            surface,
+            steps: Vec::new(),
+        },
+    )]);
+
+    while let Some((id, path)) = stack.pop() {
+        // Push in reverse so the children are visited in order.
+        let children = tree.children(id).collect::<Vec<_>>();
+
+        for (index, &(child, node)) in children.iter().enumerate().rev() {
+            let mut steps = path.steps.clone();
+            steps.push(match key(node) {
+                Some(key) => PathStep::Key(key),
+                None => PathStep::Index(index as u32),
+            });
+
+            stack.push((child, NodePath { surface, steps }));
+        }
+
+        paths.push((id, path));
+    }
+
+    paths
+}
+
+/// Returns the node at a path below the root of a surface.
+pub(crate) fn find<N, F>(
+    tree: &RetainedTree<N>,
+    root: NodeId,
+    path: &NodePath,
+    key: F,
+) -> Option<NodeId>
+where
+    F: Fn(&N) -> Option<u64>,
+{
+    path.steps.iter().try_fold(root, |id, step| {
+        let mut children = tree.children(id);
+
+        let child = match *step {
+            PathStep::Key(step) => children.find(|(_, node)| key(node) == Some(step)),
+            PathStep::Index(index) => children.nth(index as usize),
+        };
+
+        child.map(|(child, _)| child)
+    })
+}
+
+// These tests only run with the `serde` feature, which enables this module.
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        event::{Event, EventContext},
+        geometry::{Point, Rect, Size},
+        ime::ImeEvent,
+        scene::{Node, Scene},
+        surface::SurfaceId,
+        view::{scroll::Scroller, View},
+        Context, ViewNode,
+    };
+
+    use super::{LocalState, NodePath, PathStep, Snapshot};
+
+    #[derive(Clone, Serialize, Deserialize)]
+    struct Data {
+        /// The ids of the sections.
+        sections: Vec<u64>,
+        scroller: Scroller,
+    }
+
+    /// A section which may be expanded, which is not part of the data.
+    #[derive(Default)]
+    struct Section {
+        id: Option<u64>,
+        expanded: bool,
+    }
+
+    impl ViewNode<Data> for Section {
+        fn rebuild(&mut self) {}
+
+        fn event(&mut self, _: &mut EventContext<'_, Data>, event: &Event<'_>) -> bool {
+            match event {
+                Event::Ime(ImeEvent::Commit { text: "+" }) => self.expanded = true,
+                _ => return false,
+            }
+
+            true
+        }
+
+        fn key(&self) -> Option<u64> {
+            self.id
+        }
+
+        fn save_state(&self) -> Option<LocalState> {
+            self.expanded.then_some(LocalState::Bool(true))
+        }
+
+        fn restore_state(&mut self, state: &LocalState) {
+            self.expanded = *state == LocalState::Bool(true);
+        }
+    }
+
+    struct Sections<'a>(&'a [u64]);
+
+    impl View<Data> for Sections<'_> {
+        type State = Section;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            let mut root = scene.build_node::<Self, _>(Section::default());
+
+            for &id in self.0 {
+                let _ = root.children().build_node::<Self, _>(Section {
+                    id: Some(id),
+                    expanded: false,
+                });
+            }
+
+            root
+        }
+    }
+
+    fn sections(data: &Data, scene: &mut Scene<'_, Section>) {
+        let _ = Sections(&data.sections).build(scene);
+    }
+
+    fn child(cx: &Context<Data, Section>, index: usize) -> crate::tree::NodeId {
+        let root = cx.surface_root(SurfaceId::MAIN).unwrap();
+        cx.tree().children(root).nth(index).unwrap().0
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut scroller = Scroller::new();
+        scroller.set_viewport(Rect::new(0.0, 0.0, 100.0, 100.0), Size::new(100.0, 1000.0));
+        scroller.scroll_to(Point::new(0.0, 250.0));
+
+        let mut cx = Context::new(Data {
+            sections: vec![1, 2, 3],
+            scroller,
+        });
+        cx.build(sections);
+        cx.set_focus(Some(child(&cx, 1)));
+        cx.ime(ImeEvent::Commit { text: "+" });
+        cx.set_focus(Some(child(&cx, 2)));
+
+        let json = serde_json::to_string(&cx.snapshot()).unwrap();
+        let snapshot: Snapshot<Data> = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            snapshot.focus(),
+            Some(&NodePath {
+                surface: SurfaceId::MAIN,
+                steps: Vec::from([PathStep::Key(3)]),
+            })
+        );
+
+        // Restore into a fresh context, as after a crash.
+        let mut cx = Context::new(Data {
+            sections: Vec::new(),
+            scroller: Scroller::new(),
+        });
+        cx.restore(snapshot);
+
+        // Sections are found by their keys, so inserting a section before them restores the state of the same
+        // sections.
+        cx.data_mut().sections.insert(0, 0);
+        cx.build(sections);
+
+        assert_eq!(cx.data().scroller.offset(), Point::new(0.0, 250.0));
+        let expanded = (0..4)
+            .map(|index| cx.tree().get(child(&cx, index)).unwrap().expanded)
+            .collect::<Vec<_>>();
+        assert_eq!(expanded, [false, false, true, false]);
+        assert_eq!(cx.focus(), Some(child(&cx, 3)));
+
+        // Building again does not restore the snapshot again, so the sections have the state they are built with.
+        cx.build(sections);
+        assert!(!cx.tree().get(child(&cx, 2)).unwrap().expanded);
+    }
+}
@@ -0,0 +1,458 @@
+//! Surfaces which display parts of the tree.
+//!
+//! A [`Context`](crate::Context) always has a main window, [`SurfaceId::MAIN`]. Additional surfaces, such as
+//! secondary windows, popups and tooltips, each have their own root in the retained tree.
+//!
+//! The host creates and destroys platform surfaces in response to the [`SurfaceEvent`]s returned by
+//! [`Context::take_surface_events`](crate::Context::take_surface_events).
+//!
+//! Popups and tooltips are positioned relative to an anchor rectangle using a [`Positioner`], which is modelled
+//! after `xdg_positioner`. On platforms where the host positions surfaces itself, [`Positioner::place`] computes
+//! the position of the surface.
+
+use crate::geometry::{Point, Rect, Size};
+
+/// Identifies a surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceId(pub(crate) u64);
+
+impl SurfaceId {
+    /// The main window of the context.
+    pub const MAIN: Self = Self(0);
+}
+
+/// The kind of a surface.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SurfaceKind {
+    /// A top level window.
+    Window,
+
+    /// A popup, such as a menu, which is dismissed when the user clicks outside of it.
+    Popup(Positioner),
+
+    /// A tooltip, which does not take input.
+    Tooltip(Positioner),
+}
+
+impl SurfaceKind {
+    /// Returns the positioner if the surface is positioned relative to another surface.
+    pub fn positioner(&self) -> Option<&Positioner> {
+        match self {
+            SurfaceKind::Window => None,
+            SurfaceKind::Popup(positioner) | SurfaceKind::Tooltip(positioner) => Some(positioner),
+        }
+    }
+
+    pub(crate) fn positioner_mut(&mut self) -> Option<&mut Positioner> {
+        match self {
+            SurfaceKind::Window => None,
+            SurfaceKind::Popup(positioner) | SurfaceKind::Tooltip(positioner) => Some(positioner),
+        }
+    }
+}
+
+/// A change to the surfaces of a context.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SurfaceEvent {
+    /// A surface should be created.
+    Created {
+        id: SurfaceId,
+        /// The surface this surface is positioned relative to.
+        ///
+        /// This is [`None`] for windows.
+        parent: Option<SurfaceId>,
+        kind: SurfaceKind,
+    },
+
+    /// The anchor of a popup or tooltip moved and the surface should be repositioned.
+    Repositioned {
+        id: SurfaceId,
+        positioner: Positioner,
+    },
+
+    /// A surface should be destroyed.
+    Destroyed { id: SurfaceId },
+}
+
+/// A point on the edge or the center of a rectangle.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    #[default]
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the horizontal and vertical components, where -1 is left or top and 1 is right or bottom.
+    fn components(self) -> (i8, i8) {
+        match self {
+            Anchor::Center => (0, 0),
+            Anchor::Top => (0, -1),
+            Anchor::Bottom => (0, 1),
+            Anchor::Left => (-1, 0),
+            Anchor::Right => (1, 0),
+            Anchor::TopLeft => (-1, -1),
+            Anchor::TopRight => (1, -1),
+            Anchor::BottomLeft => (-1, 1),
+            Anchor::BottomRight => (1, 1),
+        }
+    }
+
+    fn from_components(x: i8, y: i8) -> Self {
+        match (x, y) {
+            (0, 0) => Anchor::Center,
+            (0, -1) => Anchor::Top,
+            (0, 1) => Anchor::Bottom,
+            (-1, 0) => Anchor::Left,
+            (1, 0) => Anchor::Right,
+            (-1, -1) => Anchor::TopLeft,
+            (1, -1) => Anchor::TopRight,
+            (-1, 1) => Anchor::BottomLeft,
+            (1, 1) => Anchor::BottomRight,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Describes where a popup or tooltip is placed relative to an anchor rectangle.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Positioner {
+    /// The size of the surface.
+    pub size: Size,
+
+    /// The rectangle the surface is positioned relative to, in the coordinates of the parent surface.
+    ///
+    /// When a surface is opened for an anchor node, this is the bounds of the anchor node.
+    pub anchor_rect: Rect,
+
+    /// The point on the anchor rectangle the surface is placed at.
+    pub anchor: Anchor,
+
+    /// The direction the surface extends from the anchor point.
+    ///
+    /// For example [`Anchor::BottomRight`] places the top left corner of the surface at the anchor point.
+    pub gravity: Anchor,
+
+    /// Moves the surface after it was placed at the anchor point.
+    pub offset: Point,
+
+    /// Whether the anchor and gravity are flipped on an axis if the surface would not fit.
+    pub flip: bool,
+
+    /// Whether the surface is moved along an axis to fit if the surface would not fit.
+    pub slide: bool,
+}
+
+impl Positioner {
+    /// Computes the area of the surface, constrained to the bounds in the coordinates of the parent surface.
+    pub fn place(&self, bounds: Rect) -> Rect {
+        let (anchor_x, anchor_y) = self.anchor.components();
+        let (gravity_x, gravity_y) = self.gravity.components();
+
+        let mut x = self.place_axis(
+            anchor_x,
+            gravity_x,
+            self.anchor_rect.x,
+            self.anchor_rect.width,
+            self.size.width,
+            self.offset.x,
+        );
+        let mut y = self.place_axis(
+            anchor_y,
+            gravity_y,
+            self.anchor_rect.y,
+            self.anchor_rect.height,
+            self.size.height,
+            self.offset.y,
+        );
+
+        if self.flip {
+            if !fits(x, self.size.width, bounds.x, bounds.width) {
+                let flipped = self.place_axis(
+                    -anchor_x,
+                    -gravity_x,
+                    self.anchor_rect.x,
+                    self.anchor_rect.width,
+                    self.size.width,
+                    -self.offset.x,
+                );
+
+                if fits(flipped, self.size.width, bounds.x, bounds.width) {
+                    x = flipped;
+                }
+            }
+
+            if !fits(y, self.size.height, bounds.y, bounds.height) {
+                let flipped = self.place_axis(
+                    -anchor_y,
+                    -gravity_y,
+                    self.anchor_rect.y,
+                    self.anchor_rect.height,
+                    self.size.height,
+                    -self.offset.y,
+                );
+
+                if fits(flipped, self.size.height, bounds.y, bounds.height) {
+                    y = flipped;
+                }
+            }
+        }
+
+        if self.slide {
+            x = slide(x, self.size.width, bounds.x, bounds.width);
+            y = slide(y, self.size.height, bounds.y, bounds.height);
+        }
+
+        Rect::new(x, y, self.size.width, self.size.height)
+    }
+
+    /// Returns the flipped anchor and gravity, used by hosts which implement flipping themselves.
+    pub fn flipped(&self, horizontal: bool, vertical: bool) -> Self {
+        let flip = |anchor: Anchor| {
+            let (x, y) = anchor.components();
+            Anchor::from_components(
+                if horizontal { -x } else { x },
+                if vertical { -y } else { y },
+            )
+        };
+
+        Self {
+            anchor: flip(self.anchor),
+            gravity: flip(self.gravity),
+            ..*self
+        }
+    }
+
+    /// Places the surface on one axis.
+    fn place_axis(
+        &self,
+        anchor: i8,
+        gravity: i8,
+        anchor_start: f32,
+        anchor_length: f32,
+        length: f32,
+        offset: f32,
+    ) -> f32 {
+        let point = anchor_start + anchor_length * (f32::from(anchor) + 1.0) / 2.0;
+        // A gravity of -1 places the surface before the point, 0 centers it and 1 places it after the point.
+        point - length * (1.0 - f32::from(gravity)) / 2.0 + offset
+    }
+}
+
+fn fits(start: f32, length: f32, bounds_start: f32, bounds_length: f32) -> bool {
+    start >= bounds_start && start + length <= bounds_start + bounds_length
+}
+
+fn slide(start: f32, length: f32, bounds_start: f32, bounds_length: f32) -> f32 {
+    let end = bounds_start + bounds_length;
+
+    if start + length > end {
+        (end - length).max(bounds_start)
+    } else if start < bounds_start {
+        bounds_start
+    } else {
+        start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::time::Duration;
+
+    use crate::{
+        event::{PointerEvent, PointerId, PointerKind},
+        geometry::{Point, Rect, Size},
+        scene::{Node, Scene},
+        view::View,
+        Context, ViewNode,
+    };
+
+    use super::{Anchor, Positioner, SurfaceEvent, SurfaceId, SurfaceKind};
+
+    impl ViewNode<()> for Rect {
+        fn rebuild(&mut self) {}
+
+        fn bounds(&self) -> Rect {
+            *self
+        }
+    }
+
+    /// Builds a root node with the rest of the rectangles as children.
+    struct Rects(Vec<Rect>);
+
+    impl View<()> for Rects {
+        type State = Rect;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            let mut root = scene.build_node::<Self, _>(self.0[0]);
+
+            for &child in &self.0[1..] {
+                let _ = root.children().build_node::<Self, _>(child);
+            }
+
+            root
+        }
+    }
+
+    const BOUNDS: Rect = Rect::new(0.0, 0.0, 800.0, 600.0);
+
+    #[test]
+    fn place() {
+        // A menu below a button.
+        let positioner = Positioner {
+            size: Size::new(200.0, 300.0),
+            anchor_rect: Rect::new(100.0, 100.0, 80.0, 20.0),
+            anchor: Anchor::BottomLeft,
+            gravity: Anchor::BottomRight,
+            offset: Point::new(0.0, 4.0),
+            flip: true,
+            slide: true,
+        };
+        assert_eq!(
+            positioner.place(BOUNDS),
+            Rect::new(100.0, 124.0, 200.0, 300.0)
+        );
+
+        // There is not enough space below the button, so the menu is flipped above the button.
+        let positioner = Positioner {
+            anchor_rect: Rect::new(100.0, 500.0, 80.0, 20.0),
+            ..positioner
+        };
+        assert_eq!(
+            positioner.place(BOUNDS),
+            Rect::new(100.0, 196.0, 200.0, 300.0)
+        );
+
+        // There is not enough space on the right, so the menu extends left from the right edge of the button.
+        let positioner = Positioner {
+            anchor_rect: Rect::new(700.0, 100.0, 80.0, 20.0),
+            ..positioner
+        };
+        assert_eq!(
+            positioner.place(BOUNDS),
+            Rect::new(580.0, 124.0, 200.0, 300.0)
+        );
+
+        // Without flipping, the menu slides left instead.
+        let positioner = Positioner {
+            flip: false,
+            ..positioner
+        };
+        assert_eq!(
+            positioner.place(BOUNDS),
+            Rect::new(600.0, 124.0, 200.0, 300.0)
+        );
+
+        // A tooltip centered above a button.
+        let positioner = Positioner {
+            size: Size::new(100.0, 20.0),
+            anchor_rect: Rect::new(100.0, 100.0, 80.0, 20.0),
+            anchor: Anchor::Top,
+            gravity: Anchor::Top,
+            ..Positioner::default()
+        };
+        assert_eq!(positioner.place(BOUNDS), Rect::new(90.0, 80.0, 100.0, 20.0));
+        assert_eq!(positioner.flipped(false, true).anchor, Anchor::Bottom);
+    }
+
+    #[test]
+    fn popup_lifecycle() {
+        let button = Rect::new(10.0, 10.0, 20.0, 10.0);
+        let mut context = Context::<(), Rect>::new(());
+        context.build(|_, scene| {
+            Rects(vec![BOUNDS, button]).build(scene);
+        });
+
+        let anchor = context.tree().iter().nth(1).unwrap().0;
+        let positioner = Positioner {
+            size: Size::new(100.0, 100.0),
+            ..Positioner::default()
+        };
+        let popup = context
+            .open_popup(anchor, positioner, |_, scene| {
+                Rects(vec![Rect::new(0.0, 0.0, 100.0, 100.0)]).build(scene);
+            })
+            .unwrap();
+        let tooltip = context
+            .open_tooltip(anchor, positioner, |_, scene| {
+                Rects(vec![Rect::new(0.0, 0.0, 10.0, 10.0)]).build(scene);
+            })
+            .unwrap();
+
+        let positioner = Positioner {
+            anchor_rect: button,
+            ..positioner
+        };
+        assert_eq!(
+            context.take_surface_events(),
+            [
+                SurfaceEvent::Created {
+                    id: popup,
+                    parent: Some(SurfaceId::MAIN),
+                    kind: SurfaceKind::Popup(positioner),
+                },
+                SurfaceEvent::Created {
+                    id: tooltip,
+                    parent: Some(SurfaceId::MAIN),
+                    kind: SurfaceKind::Tooltip(positioner),
+                },
+            ]
+        );
+        assert_eq!(context.tree().roots().count(), 3);
+        assert_eq!(
+            context.surface_of(context.surface_root(popup).unwrap()),
+            Some(popup)
+        );
+
+        // Moving the anchor repositions the popup and tooltip.
+        *context.view_tree.get_mut(anchor).unwrap() = button.translate(5.0, 0.0);
+        assert!(matches!(
+            context.take_surface_events()[..],
+            [
+                SurfaceEvent::Repositioned { id: a, .. },
+                SurfaceEvent::Repositioned { id: b, .. }
+            ] if a == popup && b == tooltip
+        ));
+
+        // Pressing outside of the popup closes the popup.
+        context.pointer(
+            SurfaceId::MAIN,
+            PointerEvent {
+                pointer: PointerId(0),
+                kind: PointerKind::Down,
+                position: Point::new(50.0, 50.0),
+                time: Duration::ZERO,
+            },
+        );
+        assert_eq!(
+            context.take_surface_events(),
+            [SurfaceEvent::Destroyed { id: popup }]
+        );
+        assert_eq!(
+            context.surfaces().collect::<Vec<_>>(),
+            [SurfaceId::MAIN, tooltip]
+        );
+
+        // Rebuilding the main window removes the anchor, which closes the tooltip.
+        context.build(|_, scene| {
+            Rects(vec![BOUNDS]).build(scene);
+        });
+        assert_eq!(
+            context.take_surface_events(),
+            [SurfaceEvent::Destroyed { id: tooltip }]
+        );
+        assert_eq!(context.tree().len(), 1);
+    }
+}
@@ -0,0 +1,645 @@
+//! A harness for testing user interfaces without a window.
+//!
+//! A [`Harness`] owns a [`Context`] and a function which builds the main window, like [`Context::build`]. Tests
+//! inject synthetic input, rebuild the main window and then query the tree and the data of the context:
+//!
+//! ```ignore
+//! let mut harness = Harness::new(0, |count, scene| {
+//!     counter(count).build(scene);
+//! });
+//!
+//! harness
+//!     .click(Query::label("Increment"))
+//!     .update()
+//!     .assert_exists(Query::label("Count: 1"));
+//!
+//! assert_eq!(*harness.data(), 1);
+//! ```
+//!
+//! Nodes are found using a [`Query`], which matches the [`role`](ViewNode::role), [`label`](ViewNode::label)
+//! or id of a node.
+//!
+//! Time does not pass on it's own. Input is delivered at the current time of the harness, which is moved forward
+//! using [`Harness::advance`].
+//!
+//! This module is available in tests of this crate and when the `testing` feature is enabled.
+
+use alloc::vec::Vec;
+use core::{marker::PhantomData, time::Duration};
+
+use crate::{
+    accessibility::Role,
+    event::{PointerEvent, PointerId, PointerKind},
+    geometry::Point,
+    ime::ImeEvent,
+    scene::Scene,
+    surface::SurfaceId,
+    tree::NodeId,
+    Context, ViewNode,
+};
+
+/// The pointer used for synthetic input.
+const POINTER: PointerId = PointerId(0);
+
+/// Describes which nodes to find.
+///
+/// Every condition of the query must match.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Query<'a> {
+    id: Option<NodeId>,
+    role: Option<Role>,
+    label: Option<&'a str>,
+}
+
+impl<'a> Query<'a> {
+    /// Matches every node.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Matches the node with the id.
+    pub fn id(id: NodeId) -> Self {
+        Self::any().with_id(id)
+    }
+
+    /// Matches nodes with the role.
+    pub fn role(role: Role) -> Self {
+        Self::any().with_role(role)
+    }
+
+    /// Matches nodes with the label.
+    pub fn label(label: &'a str) -> Self {
+        Self::any().with_label(label)
+    }
+
+    #[must_use]
+    pub fn with_id(self, id: NodeId) -> Self {
+        Self {
+            id: Some(id),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_role(self, role: Role) -> Self {
+        Self {
+            role: Some(role),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_label(self, label: &'a str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    /// Returns whether a node matches the query.
+    pub fn matches<T, N>(&self, id: NodeId, node: &N) -> bool
+    where
+        N: ViewNode<T>,
+    {
+        self.id.is_none_or(|expected| expected == id)
+            && self.role.is_none_or(|expected| expected == node.role())
+            && self
+                .label
+                .is_none_or(|expected| Some(expected) == node.label())
+    }
+}
+
+/// Drives a [`Context`] without a window.
+///
+/// The main window is built using the build function when the harness is created and each time the harness is
+/// [updated](Harness::update).
+pub struct Harness<Data, Node, F> {
+    context: Context<Data, Node>,
+    build: F,
+    /// The current time, used for synthetic input.
+    time: Duration,
+    _node: PhantomData<fn() -> Node>,
+}
+
+impl<Data, Node, F> Harness<Data, Node, F>
+where
+    Node: ViewNode<Data>,
+    F: Fn(&Data, &mut Scene<'_, Node>),
+{
+    /// Creates a context with the data and builds the main window.
+    pub fn new(data: Data, build: F) -> Self {
+        let mut context = Context::new(data);
+        context.build(&build);
+
+        Self {
+            context,
+            build,
+            time: Duration::ZERO,
+            _node: PhantomData,
+        }
+    }
+
+    /// Rebuilds the main window from the current data.
+    ///
+    /// Nodes built at the same position as before keep their ids, other node ids returned before the update refer to
+    /// removed nodes.
+    pub fn update(&mut self) -> &mut Self {
+        self.context.build(&self.build);
+        self
+    }
+
+    /// Moves the time forward, delivering any gestures which depend on time passing such as a long press.
+    pub fn advance(&mut self, duration: Duration) -> &mut Self {
+        self.time += duration;
+
+        while let Some(deadline) = self.context.deadline() {
+            if deadline > self.time {
+                break;
+            }
+
+            self.context.tick(deadline);
+        }
+
+        self
+    }
+
+    /// Returns the current time of the harness.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Delivers a pointer event on the main window at the current time.
+    pub fn pointer(&mut self, kind: PointerKind, position: Point) -> &mut Self {
+        self.context.pointer(
+            SurfaceId::MAIN,
+            PointerEvent {
+                pointer: POINTER,
+                kind,
+                position,
+                time: self.time,
+            },
+        );
+        self
+    }
+
+    /// Presses the pointer at a position on the main window.
+    pub fn press(&mut self, position: Point) -> &mut Self {
+        self.pointer(PointerKind::Down, position)
+    }
+
+    /// Moves the pointer to a position on the main window.
+    pub fn move_to(&mut self, position: Point) -> &mut Self {
+        self.pointer(PointerKind::Move, position)
+    }
+
+    /// Releases the pointer at a position on the main window.
+    pub fn release(&mut self, position: Point) -> &mut Self {
+        self.pointer(PointerKind::Up, position)
+    }
+
+    /// Presses and releases the pointer at the center of the first node matching the query.
+    ///
+    /// # Panics
+    ///
+    /// If no node matches the query.
+    pub fn click(&mut self, query: Query<'_>) -> &mut Self {
+        let position = self.get(query).bounds().center();
+        self.press(position).release(position)
+    }
+
+    /// Moves the keyboard focus to the first node matching the query.
+    ///
+    /// # Panics
+    ///
+    /// If no node matches the query.
+    pub fn focus(&mut self, query: Query<'_>) -> &mut Self {
+        let id = self.expect(query);
+        self.context.set_focus(Some(id));
+        self
+    }
+
+    /// Commits text to the focused node, as if the text was typed.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.context.ime(ImeEvent::Commit { text });
+        self
+    }
+
+    /// Returns the id of the first node matching the query in depth first order.
+    pub fn find(&self, query: Query<'_>) -> Option<NodeId> {
+        self.context
+            .tree()
+            .iter()
+            .find(|&(id, node)| query.matches(id, node))
+            .map(|(id, _)| id)
+    }
+
+    /// Returns the ids of every node matching the query in depth first order.
+    pub fn find_all(&self, query: Query<'_>) -> Vec<NodeId> {
+        self.context
+            .tree()
+            .iter()
+            .filter(|&(id, node)| query.matches(id, node))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Returns the id of the first node matching the query.
+    ///
+    /// # Panics
+    ///
+    /// If no node matches the query.
+    pub fn expect(&self, query: Query<'_>) -> NodeId {
+        self.find(query)
+            .unwrap_or_else(|| panic!("no node matches {:?}", query))
+    }
+
+    /// Returns the first node matching the query.
+    ///
+    /// # Panics
+    ///
+    /// If no node matches the query.
+    pub fn get(&self, query: Query<'_>) -> &Node {
+        let id = self.expect(query);
+        self.context.tree().get(id).unwrap()
+    }
+
+    /// Asserts that a node matches the query.
+    pub fn assert_exists(&mut self, query: Query<'_>) -> &mut Self {
+        self.expect(query);
+        self
+    }
+
+    /// Asserts that no node matches the query.
+    pub fn assert_missing(&mut self, query: Query<'_>) -> &mut Self {
+        assert!(
+            self.find(query).is_none(),
+            "a node unexpectedly matches {:?}",
+            query
+        );
+        self
+    }
+
+    /// Asserts a condition on the data of the context.
+    pub fn assert_data<P>(&mut self, predicate: P) -> &mut Self
+    where
+        P: FnOnce(&Data) -> bool,
+    {
+        assert!(predicate(self.context.data()), "data does not match");
+        self
+    }
+
+    pub fn data(&self) -> &Data {
+        self.context.data()
+    }
+
+    pub fn context(&self) -> &Context<Data, Node> {
+        &self.context
+    }
+
+    /// Returns the context, which may be used to deliver input the harness does not provide.
+    pub fn context_mut(&mut self) -> &mut Context<Data, Node> {
+        &mut self.context
+    }
+
+    /// Consumes the harness, returning the data of the context.
+    pub fn into_inner(self) -> Data {
+        self.context.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+    use core::{any::Any, time::Duration};
+
+    use crate::{
+        accessibility::Role,
+        event::{Event, EventContext},
+        geometry::{Point, Rect},
+        gesture::{Gesture, GestureArena, LongPress, Tap},
+        history::Command,
+        ime::{ContentPurpose, ImeEvent, TextBuffer, TextInputState},
+        scene::{Node, Scene},
+        surface::SurfaceId,
+        view::View,
+        ViewNode,
+    };
+
+    use super::{Harness, Query};
+
+    #[derive(Default)]
+    struct Counter {
+        count: u32,
+        resets: u32,
+        name: TextBuffer,
+    }
+
+    /// A node which increments the count when tapped and resets the count when long pressed.
+    struct Widget {
+        role: Role,
+        label: String,
+        bounds: Rect,
+    }
+
+    impl ViewNode<Counter> for Widget {
+        fn rebuild(&mut self) {}
+
+        fn bounds(&self) -> Rect {
+            self.bounds
+        }
+
+        fn role(&self) -> Role {
+            self.role
+        }
+
+        fn label(&self) -> Option<&str> {
+            Some(&self.label)
+        }
+
+        fn event(&mut self, cx: &mut EventContext<'_, Counter>, event: &Event<'_>) -> bool {
+            match event {
+                Event::Gesture(Gesture::Tap { .. }) => cx.data_mut().count += 1,
+                Event::Gesture(Gesture::LongPress { .. }) => {
+                    cx.data_mut().count = 0;
+                    cx.data_mut().resets += 1;
+                }
+                Event::Ime(event) => cx.data_mut().name.apply(event),
+                _ => return false,
+            }
+
+            true
+        }
+
+        fn text_input(&self) -> Option<TextInputState> {
+            (self.role == Role::TextInput)
+                .then(|| TextBuffer::new("").state(ContentPurpose::Normal, self.bounds))
+        }
+
+        fn gestures(&self) -> Option<GestureArena> {
+            let mut arena = GestureArena::new();
+            arena.add(Tap::default());
+            arena.add(LongPress::default());
+            Some(arena)
+        }
+
+        fn paints_same(&self, previous: &Self) -> bool {
+            self.role == previous.role
+                && self.label == previous.label
+                && self.bounds == previous.bounds
+        }
+    }
+
+    struct Widgets(Vec<Widget>);
+
+    impl View<Counter> for Widgets {
+        type State = Widget;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            let widget = |widget: &Widget| Widget {
+                label: widget.label.clone(),
+                ..*widget
+            };
+
+            let mut root = scene.build_node::<Self, _>(widget(&self.0[0]));
+
+            for child in &self.0[1..] {
+                let _ = root.children().build_node::<Self, _>(widget(child));
+            }
+
+            root
+        }
+    }
+
+    fn counter(data: &Counter, scene: &mut Scene<'_, Widget>) {
+        let widget = |role, label: &str, bounds| Widget {
+            role,
+            label: label.to_string(),
+            bounds,
+        };
+
+        Widgets(vec![
+            widget(Role::Window, "Counter", Rect::new(0.0, 0.0, 100.0, 100.0)),
+            widget(Role::Label, &format!("Count: {}", data.count), Rect::ZERO),
+            widget(Role::Button, "Increment", Rect::new(10.0, 10.0, 20.0, 20.0)),
+            widget(Role::TextInput, "Name", Rect::new(10.0, 50.0, 80.0, 20.0)),
+        ])
+        .build(scene);
+    }
+
+    #[test]
+    fn counter_harness() {
+        let mut harness = Harness::new(Counter::default(), counter);
+
+        harness
+            .assert_exists(Query::label("Count: 0"))
+            .click(Query::role(Role::Button).with_label("Increment"))
+            .click(Query::label("Increment"))
+            .assert_data(|data| data.count == 2)
+            // The view is not rebuilt until the harness is updated.
+            .assert_missing(Query::label("Count: 2"))
+            .update()
+            .assert_exists(Query::label("Count: 2"));
+
+        // Holding the button resets the count.
+        let button = harness.get(Query::label("Increment")).bounds().center();
+        harness
+            .press(button)
+            .advance(Duration::from_secs(1))
+            .release(button)
+            .update()
+            .assert_exists(Query::label("Count: 0"))
+            .focus(Query::role(Role::TextInput))
+            .type_text("Counter");
+
+        assert_eq!(harness.find_all(Query::any()).len(), 4);
+        assert_eq!(
+            harness.context().focus(),
+            Some(harness.expect(Query::label("Name")))
+        );
+        assert_eq!(harness.data().resets, 1);
+        assert_eq!(harness.into_inner().name.text(), "Counter");
+    }
+
+    #[test]
+    #[should_panic(expected = "no node matches")]
+    fn missing_node() {
+        Harness::new(Counter::default(), counter).click(Query::label("Decrement"));
+    }
+
+    #[test]
+    fn query() {
+        let harness = Harness::new(Counter::default(), counter);
+        let root = harness.context().tree().root().unwrap();
+
+        assert_eq!(harness.find(Query::id(root)), Some(root));
+        assert_eq!(harness.find(Query::id(root).with_role(Role::Button)), None);
+        assert_eq!(harness.find_all(Query::role(Role::Label)).len(), 1);
+        assert_eq!(
+            harness
+                .get(Query::any().with_label("Count: 0"))
+                .bounds
+                .center(),
+            Point::new(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn rebuild_damage() {
+        let count = Rect::new(10.0, 40.0, 80.0, 10.0);
+        let mut harness = Harness::new(
+            Counter::default(),
+            |data: &Counter, scene: &mut Scene<'_, Widget>| {
+                let widget = |role, label: &str, bounds| Widget {
+                    role,
+                    label: label.to_string(),
+                    bounds,
+                };
+
+                Widgets(vec![
+                    widget(Role::Window, "Counter", Rect::new(0.0, 0.0, 100.0, 100.0)),
+                    widget(Role::Label, &format!("Count: {}", data.count), count),
+                    widget(Role::Button, "Increment", Rect::new(10.0, 10.0, 20.0, 20.0)),
+                ])
+                .build(scene);
+            },
+        );
+
+        let bounds = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let label = harness.expect(Query::label("Count: 0"));
+        harness.context_mut().mark_painted(SurfaceId::MAIN);
+
+        // Rebuilding without changes keeps the nodes and damages nothing.
+        harness.update();
+        assert!(harness.context().damage(SurfaceId::MAIN, bounds).is_empty());
+
+        // Only the label showing the count changes.
+        harness.click(Query::label("Increment")).update();
+        assert_eq!(harness.expect(Query::label("Count: 1")), label);
+        assert_eq!(
+            harness.context().damage(SurfaceId::MAIN, bounds).rects(),
+            [count]
+        );
+    }
+
+    /// Text appended to one of two fields.
+    struct Append(usize, String);
+
+    impl Command<[String; 2]> for Append {
+        fn apply(&mut self, data: &mut [String; 2]) {
+            data[self.0].push_str(&self.1);
+        }
+
+        fn revert(&mut self, data: &mut [String; 2]) {
+            let field = &mut data[self.0];
+            field.truncate(field.len() - self.1.len());
+        }
+
+        fn merge(&mut self, next: &dyn Any) -> bool {
+            match next.downcast_ref::<Append>() {
+                Some(next) if next.0 == self.0 => {
+                    self.1.push_str(&next.1);
+                    true
+                }
+
+                _ => false,
+            }
+        }
+    }
+
+    /// A node which appends committed text to a field with an undoable command.
+    struct Field(usize, Rect);
+
+    impl ViewNode<[String; 2]> for Field {
+        fn rebuild(&mut self) {}
+
+        fn bounds(&self) -> Rect {
+            self.1
+        }
+
+        fn label(&self) -> Option<&str> {
+            Some(["First", "Second"][self.0])
+        }
+
+        fn event(&mut self, cx: &mut EventContext<'_, [String; 2]>, event: &Event<'_>) -> bool {
+            match event {
+                Event::Ime(ImeEvent::Commit { text }) => {
+                    cx.execute(Append(self.0, text.to_string()))
+                }
+                _ => return false,
+            }
+
+            true
+        }
+
+        fn paints_same(&self, previous: &Self) -> bool {
+            self.0 == previous.0 && self.1 == previous.1
+        }
+    }
+
+    struct Fields;
+
+    impl View<[String; 2]> for Fields {
+        type State = Field;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            let mut root = scene.build_node::<Self, _>(Field(0, Rect::new(0.0, 0.0, 100.0, 20.0)));
+            let _ = root
+                .children()
+                .build_node::<Self, _>(Field(1, Rect::new(0.0, 50.0, 100.0, 20.0)));
+            root
+        }
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut harness = Harness::new(
+            Default::default(),
+            |_: &[String; 2], scene: &mut Scene<'_, Field>| {
+                Fields.build(scene);
+            },
+        );
+
+        // Typing into another field seals the history, so the fields are undone separately.
+        harness
+            .focus(Query::label("First"))
+            .type_text("a")
+            .type_text("b")
+            .focus(Query::label("Second"))
+            .type_text("c")
+            .update();
+        assert_eq!(harness.data(), &["ab", "c"]);
+
+        let bounds = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let second = harness.get(Query::label("Second")).1;
+        harness.context_mut().mark_painted(SurfaceId::MAIN);
+
+        // Undoing damages the field which executed the command, which keeps it's id after the rebuild.
+        assert!(harness.context_mut().undo());
+        assert_eq!(harness.data(), &["ab", ""]);
+        assert_eq!(
+            harness.context().damage(SurfaceId::MAIN, bounds).rects(),
+            [second]
+        );
+
+        assert!(harness.context_mut().undo());
+        assert_eq!(harness.data(), &["", ""]);
+        assert!(!harness.context_mut().undo());
+
+        assert!(harness.context_mut().redo());
+        assert_eq!(harness.data(), &["ab", ""]);
+        assert!(harness.context_mut().redo());
+        assert!(!harness.context_mut().redo());
+        assert_eq!(harness.into_inner(), ["ab", "c"]);
+    }
+}
@@ -0,0 +1,402 @@
+//! Data transfer using the clipboard and drag and drop.
+//!
+//! Data is transferred as [`Content`], a set of payloads each tagged with a MIME type. Offering the same data in
+//! multiple formats allows the receiver to pick the format it understands best.
+//!
+//! Drag and drop is modelled after `DragSource` and `DropTarget` in gtk4:
+//!
+//! - A node becomes draggable by returning a [`DragSource`] from
+//!   [`ViewNode::drag_source`](crate::ViewNode::drag_source).
+//! - A node accepts drops by returning a [`DropTarget`] from
+//!   [`ViewNode::drop_target`](crate::ViewNode::drop_target).
+//!
+//! While a drag is in progress, the drop target under the pointer receives [`DragEvent`]s. A drag may start from
+//! a node in the tree or be offered by the host, such as when a file is dragged from another application.
+
+use alloc::{
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+use crate::{geometry::Point, tree::NodeId};
+
+/// The MIME type used for UTF-8 text.
+pub const TEXT_PLAIN: &str = "text/plain;charset=utf-8";
+
+/// Data in a single format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payload {
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Data offered in one or more formats.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Content {
+    payloads: Vec<Payload>,
+}
+
+impl Content {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates content containing UTF-8 text.
+    pub fn text(text: &str) -> Self {
+        Self::new().with(TEXT_PLAIN, text.as_bytes().to_vec())
+    }
+
+    /// Adds a payload in another format.
+    ///
+    /// If a payload with the same MIME type exists, the payload is replaced.
+    #[must_use]
+    pub fn with(mut self, mime_type: &str, data: Vec<u8>) -> Self {
+        self.payloads
+            .retain(|payload| payload.mime_type != mime_type);
+        self.payloads.push(Payload {
+            mime_type: mime_type.to_string(),
+            data,
+        });
+        self
+    }
+
+    /// Returns the MIME types of every payload, in the order they were added.
+    pub fn mime_types(&self) -> impl Iterator<Item = &str> {
+        self.payloads
+            .iter()
+            .map(|payload| payload.mime_type.as_str())
+    }
+
+    /// Returns the data in the given format.
+    pub fn get(&self, mime_type: &str) -> Option<&[u8]> {
+        self.payloads
+            .iter()
+            .find(|payload| payload.mime_type == mime_type)
+            .map(|payload| &payload.data[..])
+    }
+
+    /// Returns the UTF-8 text in the content.
+    pub fn get_text(&self) -> Option<&str> {
+        self.get(TEXT_PLAIN)
+            .and_then(|data| core::str::from_utf8(data).ok())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+}
+
+/// A clipboard.
+///
+/// The host provides an implementation which transfers data to and from the system clipboard.
+pub trait Clipboard {
+    /// Returns the content of the clipboard.
+    fn read(&mut self) -> Option<Content>;
+
+    /// Replaces the content of the clipboard.
+    fn write(&mut self, content: Content);
+
+    /// Removes the content of the clipboard.
+    fn clear(&mut self);
+}
+
+/// A clipboard shared between multiple contexts on the same thread.
+impl<C> Clipboard for Rc<RefCell<C>>
+where
+    C: Clipboard + ?Sized,
+{
+    fn read(&mut self) -> Option<Content> {
+        self.borrow_mut().read()
+    }
+
+    fn write(&mut self, content: Content) {
+        self.borrow_mut().write(content);
+    }
+
+    fn clear(&mut self) {
+        self.borrow_mut().clear();
+    }
+}
+
+/// A clipboard shared between multiple contexts, which may be on different threads.
+#[cfg(feature = "std")]
+impl<C> Clipboard for std::sync::Arc<std::sync::Mutex<C>>
+where
+    C: Clipboard + ?Sized,
+{
+    fn read(&mut self) -> Option<Content> {
+        lock(self).read()
+    }
+
+    fn write(&mut self, content: Content) {
+        lock(self).write(content);
+    }
+
+    fn clear(&mut self) {
+        lock(self).clear();
+    }
+}
+
+/// Locks a shared clipboard, ignoring poisoning since the clipboard remains usable after a panic.
+#[cfg(feature = "std")]
+fn lock<C>(clipboard: &std::sync::Mutex<C>) -> std::sync::MutexGuard<'_, C>
+where
+    C: ?Sized,
+{
+    clipboard
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// A clipboard which stores it's content in memory.
+///
+/// This is useful for tests or when no system clipboard is available.
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    content: Option<Content>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn read(&mut self) -> Option<Content> {
+        self.content.clone()
+    }
+
+    fn write(&mut self, content: Content) {
+        self.content = Some(content);
+    }
+
+    fn clear(&mut self) {
+        self.content = None;
+    }
+}
+
+/// Describes the data a node provides when dragged.
+#[derive(Debug, Clone)]
+pub struct DragSource {
+    pub content: Content,
+}
+
+/// Describes which data a node accepts when dropped.
+#[derive(Debug, Clone, Default)]
+pub struct DropTarget {
+    /// The accepted MIME types.
+    ///
+    /// If empty, any data is accepted.
+    pub mime_types: Vec<String>,
+}
+
+impl DropTarget {
+    /// Returns whether the target accepts any of the formats in the content.
+    pub fn accepts(&self, content: &Content) -> bool {
+        self.mime_types.is_empty()
+            || content
+                .mime_types()
+                .any(|mime_type| self.mime_types.iter().any(|accepted| accepted == mime_type))
+    }
+}
+
+/// A drag and drop event.
+#[derive(Debug)]
+pub enum DragEvent<'a> {
+    /// The drag entered the bounds of the drop target.
+    Enter {
+        position: Point,
+        content: &'a Content,
+    },
+
+    /// The drag moved inside the bounds of the drop target.
+    Over { position: Point },
+
+    /// The drag left the drop target or was cancelled.
+    Leave,
+
+    /// The content was dropped on the drop target.
+    Drop {
+        position: Point,
+        content: &'a Content,
+    },
+
+    /// The drag which started from this node finished.
+    ///
+    /// This is delivered to the node the drag started from.
+    End {
+        /// Whether a drop target accepted the drop.
+        accepted: bool,
+    },
+}
+
+/// The state of a drag in progress.
+#[derive(Debug)]
+pub(crate) struct Drag {
+    pub content: Content,
+    /// The node the drag started from, if the drag started in the tree.
+    pub source: Option<NodeId>,
+    /// The drop target currently under the drag.
+    pub target: Option<NodeId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, rc::Rc, string::ToString, vec, vec::Vec};
+    use core::cell::RefCell;
+
+    use crate::{
+        event::{Event, EventContext},
+        geometry::{Point, Rect},
+        scene::{Node, Scene},
+        surface::SurfaceId,
+        view::View,
+        Context, ViewNode,
+    };
+
+    use super::{Clipboard, Content, DragEvent, DragSource, DropTarget, MemoryClipboard};
+
+    #[derive(Clone)]
+    struct TestNode {
+        name: &'static str,
+        bounds: Rect,
+        source: Option<DragSource>,
+        target: Option<DropTarget>,
+    }
+
+    impl ViewNode<Vec<&'static str>> for TestNode {
+        fn rebuild(&mut self) {}
+
+        fn bounds(&self) -> Rect {
+            self.bounds
+        }
+
+        fn event(
+            &mut self,
+            cx: &mut EventContext<'_, Vec<&'static str>>,
+            event: &Event<'_>,
+        ) -> bool {
+            let name = match event {
+                Event::Drag(DragEvent::Enter { .. }) => "enter",
+                Event::Drag(DragEvent::Over { .. }) => "over",
+                Event::Drag(DragEvent::Leave) => "leave",
+                Event::Drag(DragEvent::Drop { content, .. }) => {
+                    assert_eq!(content.get_text(), Some("dropped"));
+                    "drop"
+                }
+                Event::Drag(DragEvent::End { accepted: true }) => "accepted",
+                Event::Drag(DragEvent::End { accepted: false }) => "rejected",
+                _ => return false,
+            };
+
+            cx.data_mut().push(self.name);
+            cx.data_mut().push(name);
+            true
+        }
+
+        fn drag_source(&self) -> Option<&DragSource> {
+            self.source.as_ref()
+        }
+
+        fn drop_target(&self) -> Option<&DropTarget> {
+            self.target.as_ref()
+        }
+    }
+
+    /// Builds a root node with the rest of the nodes as children.
+    struct Nodes(Vec<TestNode>);
+
+    impl View<Vec<&'static str>> for Nodes {
+        type State = TestNode;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            let mut root = scene.build_node::<Self, _>(self.0[0].clone());
+
+            for child in &self.0[1..] {
+                let _ = root.children().build_node::<Self, _>(child.clone());
+            }
+
+            root
+        }
+    }
+
+    #[test]
+    fn content_formats() {
+        let content = Content::text("hello").with("text/html", b"<b>hello</b>".to_vec());
+
+        assert_eq!(content.get_text(), Some("hello"));
+        assert_eq!(content.get("text/html"), Some(&b"<b>hello</b>"[..]));
+        assert_eq!(content.get("image/png"), None);
+
+        let target = DropTarget {
+            mime_types: vec!["text/html".to_string()],
+        };
+        assert!(target.accepts(&content));
+        assert!(!target.accepts(&Content::text("hello")));
+
+        let mut clipboard = MemoryClipboard::new();
+        clipboard.write(content.clone());
+        assert_eq!(clipboard.read(), Some(content));
+        clipboard.clear();
+        assert_eq!(clipboard.read(), None);
+
+        // Contexts sharing a clipboard see each other's content.
+        let shared = Rc::new(RefCell::new(MemoryClipboard::new()));
+        let mut first = Context::<_, TestNode>::new(Vec::new());
+        let mut second = Context::<_, TestNode>::new(Vec::new());
+        first.set_clipboard(Box::new(shared.clone()));
+        second.set_clipboard(Box::new(shared));
+        first.clipboard().write(Content::text("shared"));
+        assert_eq!(second.clipboard().read(), Some(Content::text("shared")));
+    }
+
+    #[test]
+    fn drag_and_drop() {
+        let node = |name, bounds| TestNode {
+            name,
+            bounds,
+            source: None,
+            target: None,
+        };
+
+        let mut context = Context::<_, TestNode>::new(Vec::new());
+        context.build(|_, scene| {
+            Nodes(vec![
+                node("root", Rect::new(0.0, 0.0, 100.0, 100.0)),
+                TestNode {
+                    source: Some(DragSource {
+                        content: Content::text("dropped"),
+                    }),
+                    ..node("source", Rect::new(0.0, 0.0, 10.0, 10.0))
+                },
+                TestNode {
+                    target: Some(DropTarget::default()),
+                    ..node("target", Rect::new(50.0, 50.0, 10.0, 10.0))
+                },
+            ])
+            .build(scene);
+        });
+
+        let source = context.tree().iter().nth(1).unwrap().0;
+        assert!(context.begin_drag(source, Point::new(5.0, 5.0)));
+
+        context.drag_motion(SurfaceId::MAIN, Point::new(55.0, 55.0));
+        context.drag_motion(SurfaceId::MAIN, Point::new(56.0, 56.0));
+        context.drag_motion(SurfaceId::MAIN, Point::new(80.0, 80.0));
+        assert!(context.drop(SurfaceId::MAIN, Point::new(55.0, 55.0)));
+
+        assert_eq!(
+            context.into_inner(),
+            [
+                "target", "enter", "target", "over", "target", "leave", "target", "enter",
+                "target", "drop", "source", "accepted"
+            ]
+        );
+    }
+}
@@ -1,13 +1,23 @@
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut};
 
 use thunderdome::{Arena, Index};
 
 /// A tree with manages a retained state of `T` for each element.
+///
+/// A tree may have multiple roots. The roots are siblings of each other and are visited in the order they were
+/// added.
 pub struct RetainedTree<T> {
     inner: Arena<NodeInner<T>>,
-    root: Index,
+    root: Option<Index>,
+    last_root: Option<Index>,
 }
 
+/// An identifier for a node in a [`RetainedTree`].
+///
+/// Identifiers are not reused after a node is removed from the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(Index);
+
 // Build tree
 // Visit
 // Specify action to rebuild part of tree
@@ -22,35 +32,242 @@ pub enum VisitAction {
 }
 
 impl<T> RetainedTree<T> {
-    pub fn new<Context, Init, Visit>(context: &Context, init: Init, visit: Visit) -> Self
-    where
-        Init: FnOnce(&Context) -> T,
-        Visit: Fn(&Context, Node<'_, T>) -> VisitAction,
-    {
-        let mut inner = Arena::new();
-        let root = init(context);
+    /// Creates an empty tree.
+    pub fn new() -> Self {
+        Self {
+            inner: Arena::new(),
+            root: None,
+            last_root: None,
+        }
+    }
+
+    /// Returns the id of the first root node.
+    ///
+    /// Returns [`None`] if the tree is empty.
+    pub fn root(&self) -> Option<NodeId> {
+        self.root.map(NodeId)
+    }
+
+    /// Returns an iterator over every root node.
+    pub fn roots(&self) -> Children<'_, T> {
+        Children {
+            tree: self,
+            next: self.root,
+        }
+    }
 
-        let node = NodeInner {
-            data: root,
+    /// Replaces the root node of the tree.
+    ///
+    /// Any existing nodes in the tree are removed.
+    pub fn set_root(&mut self, data: T) -> Node<'_, T> {
+        self.inner.clear();
+        self.root = None;
+        self.last_root = None;
+        self.push_root(data)
+    }
+
+    /// Adds a new root after the existing roots.
+    pub fn push_root(&mut self, data: T) -> Node<'_, T> {
+        let index = self.inner.insert(NodeInner {
+            data,
             parent: None,
-            prev_sibling: None,
+            prev_sibling: self.last_root,
             next_sibling: None,
             first_child: None,
             last_child: None,
-        };
+        });
+
+        match self.last_root {
+            Some(last) => self.inner.get_mut(last).unwrap().next_sibling = Some(index),
+            None => self.root = Some(index),
+        }
+
+        self.last_root = Some(index);
+
+        Node {
+            inner: &mut self.inner,
+            index,
+        }
+    }
+
+    /// Replaces the data of a node and removes all of it's children.
+    ///
+    /// The node keeps it's position in the tree and it's id.
+    ///
+    /// # Panics
+    ///
+    /// If the node is not in the tree.
+    pub fn replace(&mut self, id: NodeId, data: T) -> Node<'_, T> {
+        remove_children(&mut self.inner, id.0);
+        self.inner
+            .get_mut(id.0)
+            .expect("node is not in the tree")
+            .data = data;
+
+        Node {
+            inner: &mut self.inner,
+            index: id.0,
+        }
+    }
+
+    /// Replaces the data of a node, keeping it's children, position in the tree and id.
+    ///
+    /// Returns the previous data of the node.
+    ///
+    /// # Panics
+    ///
+    /// If the node is not in the tree.
+    pub fn update(&mut self, id: NodeId, data: T) -> T {
+        let node = self.inner.get_mut(id.0).expect("node is not in the tree");
+        core::mem::replace(&mut node.data, data)
+    }
+
+    /// Removes a node and all of it's children from the tree.
+    ///
+    /// Returns the data of the node if the node was in the tree.
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        let node = self.inner.get(id.0)?;
+        let (parent, prev_sibling, next_sibling) =
+            (node.parent, node.prev_sibling, node.next_sibling);
+
+        // Unlink the node from it's siblings and parent.
+        match prev_sibling {
+            Some(prev) => self.inner.get_mut(prev).unwrap().next_sibling = next_sibling,
+            None => match parent {
+                Some(parent) => self.inner.get_mut(parent).unwrap().first_child = next_sibling,
+                None => self.root = next_sibling,
+            },
+        }
+
+        match next_sibling {
+            Some(next) => self.inner.get_mut(next).unwrap().prev_sibling = prev_sibling,
+            None => match parent {
+                Some(parent) => self.inner.get_mut(parent).unwrap().last_child = prev_sibling,
+                None => self.last_root = prev_sibling,
+            },
+        }
+
+        remove_children(&mut self.inner, id.0);
+        self.inner.remove(id.0).map(|node| node.data)
+    }
+
+    /// Adds a new child to the end of the children of the `parent`.
+    ///
+    /// # Panics
+    ///
+    /// If the parent node is not in the tree.
+    pub fn push_child(&mut self, parent: NodeId, data: T) -> Node<'_, T> {
+        assert!(
+            self.inner.get(parent.0).is_some(),
+            "parent node is not in the tree"
+        );
+
+        let index = insert_new_child(&mut self.inner, data, parent.0);
+
+        Node {
+            inner: &mut self.inner,
+            index,
+        }
+    }
+
+    /// Returns the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.inner.get(id.0).map(|node| &node.data)
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.inner.get_mut(id.0).map(|node| &mut node.data)
+    }
+
+    /// Returns a handle to a node which may be used to add children to the node.
+    pub fn node_mut(&mut self, id: NodeId) -> Option<Node<'_, T>> {
+        self.inner.get(id.0)?;
+
+        Some(Node {
+            inner: &mut self.inner,
+            index: id.0,
+        })
+    }
+
+    /// Returns the parent of a node.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.inner.get(id.0)?.parent.map(NodeId)
+    }
+
+    /// Returns the sibling after a node.
+    pub fn next_sibling(&self, id: NodeId) -> Option<NodeId> {
+        self.inner.get(id.0)?.next_sibling.map(NodeId)
+    }
+
+    /// Returns the root of the tree that contains a node.
+    pub fn root_of(&self, mut id: NodeId) -> Option<NodeId> {
+        self.inner.get(id.0)?;
+
+        while let Some(parent) = self.parent(id) {
+            id = parent;
+        }
+
+        Some(id)
+    }
 
-        let root = inner.insert(node);
+    /// Returns an iterator over the direct children of a node.
+    pub fn children(&self, id: NodeId) -> Children<'_, T> {
+        Children {
+            tree: self,
+            next: self.inner.get(id.0).and_then(|node| node.first_child),
+        }
+    }
 
-        let mut tree = Self { inner, root };
+    /// Returns a depth first iterator over every node in the tree.
+    pub fn iter(&self) -> DepthFirst<'_, T> {
+        DepthFirst {
+            tree: self,
+            next: self.root,
+            end: None,
+        }
+    }
+
+    /// Returns a depth first iterator over a node and all of it's descendants.
+    pub fn descendants(&self, id: NodeId) -> DepthFirst<'_, T> {
+        let exists = self.inner.get(id.0).is_some();
 
-        tree.visit(context, visit);
-        tree
+        DepthFirst {
+            tree: self,
+            next: exists.then_some(id.0),
+            end: get_next_sibling(&self.inner, id.0),
+        }
     }
 
+    /// Visits every node in the tree in depth first order.
+    ///
+    /// The visitor may add children to the node being visited. Children which are added are visited unless the
+    /// node is pruned.
     pub fn visit<Context, Visit>(&mut self, context: &Context, visit: Visit)
     where
         Visit: Fn(&Context, Node<'_, T>) -> VisitAction,
     {
+        let mut next = self.root;
+
+        while let Some(current) = next {
+            let node = Node {
+                inner: &mut self.inner,
+                index: current,
+            };
+
+            next = match visit(context, node) {
+                VisitAction::Continue => get_next_node(&self.inner, current),
+                VisitAction::Prune => get_next_sibling(&self.inner, current),
+            };
+        }
     }
 
     // TODO: Pruning during iteration and generation of tree components.
@@ -82,12 +299,23 @@ impl<T> RetainedTree<T> {
     //   - UNDECIDED: Invalidate a node on completion of a Future?
 }
 
+impl<T> Default for RetainedTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct Node<'a, T> {
     inner: &'a mut Arena<NodeInner<T>>,
     index: Index,
 }
 
 impl<'a, T> Node<'a, T> {
+    /// Returns the id of this node.
+    pub fn id(&self) -> NodeId {
+        NodeId(self.index)
+    }
+
     #[must_use]
     pub fn push_child<'b>(&'b mut self, data: T) -> Node<'b, T>
     where
@@ -110,21 +338,46 @@ impl<T> Deref for Node<'_, T> {
     }
 }
 
+impl<T> DerefMut for Node<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner.get_mut(self.index).unwrap().data
+    }
+}
+
 pub struct DepthFirst<'a, T> {
     tree: &'a RetainedTree<T>,
     next: Option<Index>,
+    /// The node after the last node to visit.
+    end: Option<Index>,
 }
 
 impl<'a, T> Iterator for DepthFirst<'a, T> {
-    type Item = &'a T;
+    type Item = (NodeId, &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
         let current = self.next.take()?;
         let node = self.tree.inner.get(current)?;
 
         // Get the next node
-        self.next = get_next_node(&self.tree.inner, current);
-        Some(&node.data)
+        self.next = get_next_node(&self.tree.inner, current).filter(|&next| Some(next) != self.end);
+        Some((NodeId(current), &node.data))
+    }
+}
+
+pub struct Children<'a, T> {
+    tree: &'a RetainedTree<T>,
+    next: Option<Index>,
+}
+
+impl<'a, T> Iterator for Children<'a, T> {
+    type Item = (NodeId, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        let node = self.tree.inner.get(current)?;
+
+        self.next = node.next_sibling;
+        Some((NodeId(current), &node.data))
     }
 }
 
@@ -137,72 +390,72 @@ struct NodeInner<T> {
     last_child: Option<Index>,
 }
 
-impl<T> RetainedTree<T> {
-    /// Returns the index of the last node in the tree.
-    fn get_last(&self) -> Option<Index> {
-        self.root.map(|mut next| {
+fn get_next_node<T>(arena: &Arena<NodeInner<T>>, index: Index) -> Option<Index> {
+    // Try the child of the node, otherwise the next node is the same as if the children were pruned.
+    arena
+        .get(index)?
+        .first_child
+        .or_else(|| get_next_sibling(arena, index))
+}
+
+/// Returns the next node in depth first order, skipping the children of the node.
+fn get_next_sibling<T>(arena: &Arena<NodeInner<T>>, index: Index) -> Option<Index> {
+    let node = arena.get(index)?;
+
+    // Try the next sibling
+    node.next_sibling.or_else(|| {
+        // Check if this node has a parent
+        if let Some(mut parent) = node.parent {
             loop {
-                let node = self.inner.get(next).unwrap();
+                let parent_node = arena.get(parent).expect("Parent must exist");
+
+                // Try the next sibling of the parent
+                //                grandparent
+                //               /           \
+                //         parent ----------> next
+                //        /
+                // current
+                if let Some(next) = parent_node.next_sibling {
+                    return Some(next);
+                }
 
-                // Get the next last child
-                if let Some(child) = node.last_child {
-                    next = child;
+                // grandparent
+                // ^
+                // |
+                // parent
+                // ^
+                // |
+                // current
+                if let Some(next) = parent_node.parent {
+                    parent = next;
+                    // The next iteration of the loop will try the sibling and next grandparent.
                     continue;
                 }
 
-                // Reached the last node in the tree.
-                break next;
+                // We have reached the end of the tree and therefore no more nodes exist.
+                break;
             }
-        })
-    }
+        }
+
+        // We have reached the end of the tree
+        None
+    })
 }
 
-fn get_next_node<T>(arena: &Arena<NodeInner<T>>, index: Index) -> Option<Index> {
-    // Try the child of the node
-    let node = arena.get(index)?;
+/// Removes every descendant of a node.
+fn remove_children<T>(arena: &mut Arena<NodeInner<T>>, index: Index) {
+    let node = match arena.get_mut(index) {
+        Some(node) => node,
+        None => return,
+    };
 
-    node
-        // First child
-        .first_child
-        // Otherwise try the next sibling
-        .or(node.next_sibling)
-        .or_else(|| {
-            // Check if this node has a parent
-            if let Some(mut parent) = node.parent {
-                loop {
-                    let parent_node = arena.get(parent).expect("Parent must exist");
-
-                    // Try the next sibling of the parent
-                    //                grandparent
-                    //               /           \
-                    //         parent ----------> next
-                    //        /
-                    // current
-                    if let Some(next) = parent_node.next_sibling {
-                        return Some(next);
-                    }
-
-                    // grandparent
-                    // ^
-                    // |
-                    // parent
-                    // ^
-                    // |
-                    // current
-                    if let Some(next) = parent_node.parent {
-                        parent = next;
-                        // The next iteration of the loop will try the sibling and next grandparent.
-                        continue;
-                    }
-
-                    // We have reached the end of the tree and therefore no more nodes exist.
-                    break;
-                }
-            }
+    let mut next = node.first_child.take();
+    node.last_child = None;
 
-            // We have reached the end of the tree
-            None
-        })
+    while let Some(child) = next {
+        remove_children(arena, child);
+        next = arena.remove(child).and_then(|child| child.next_sibling);
+    }
 }
 
 fn insert_new_child<T>(arena: &mut Arena<NodeInner<T>>, data: T, parent: Index) -> Index {
@@ -0,0 +1,944 @@
+//! Virtualized lists for large numbers of items.
+//!
+//! A [`List`] only builds nodes for the items inside the viewport and a few items around the viewport (the
+//! overscan). The scroll position and the heights of the items are kept in a [`Virtualizer`], which is usually
+//! stored in the data of the [`Context`](crate::Context).
+//!
+//! Items which were not measured yet are assumed to have the estimated height. Once an item is built and it's
+//! height is known, the height should be reported using [`Virtualizer::measure`].
+//!
+//! The heights are kept in a balanced tree, so scrolling, measuring, inserting and removing items takes
+//! logarithmic time in the number of items.
+//!
+//! The scroll position is anchored to the first visible item. Measuring, inserting or removing items above the
+//! viewport does not move the visible items.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{
+    geometry::Rect,
+    scene::{Node, Scene},
+};
+
+use super::View;
+
+/// The number of items built above and below the viewport by default.
+pub const DEFAULT_OVERSCAN: usize = 3;
+
+/// The scroll position and item heights of a virtualized list.
+///
+/// With [multiple columns](List::columns), each item of the virtualizer is a line of the grid.
+#[derive(Debug, Clone)]
+pub struct Virtualizer {
+    heights: Heights,
+    estimated_height: f32,
+    viewport_height: f32,
+    /// The number of items built outside of the viewport in each direction.
+    overscan: usize,
+    /// The first visible item.
+    anchor: usize,
+    /// How far the anchor is scrolled past the top of the viewport.
+    anchor_offset: f64,
+    /// The items which should be built.
+    visible: Vec<VisibleItem>,
+    /// Slots which are not used by a visible item.
+    free_slots: Vec<usize>,
+    next_slot: usize,
+}
+
+/// An item of a [`Virtualizer`] which should be built.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibleItem {
+    pub index: usize,
+    /// An identifier which is reused for another item once this item is no longer built.
+    ///
+    /// Slots are small integers. Expensive state of an item, such as a text layout, may be cached by slot
+    /// and recycled when the item is scrolled out of view.
+    pub slot: usize,
+    /// The offset of the top of the item from the top of the viewport.
+    pub offset: f32,
+    pub height: f32,
+}
+
+impl Virtualizer {
+    /// Creates a virtualizer for a number of items which are estimated to have the same height.
+    pub fn new(len: usize, estimated_height: f32) -> Self {
+        let mut virtualizer = Self {
+            heights: Heights::new(len, f64::from(estimated_height)),
+            estimated_height,
+            viewport_height: 0.0,
+            overscan: DEFAULT_OVERSCAN,
+            anchor: 0,
+            anchor_offset: 0.0,
+            visible: Vec::new(),
+            free_slots: Vec::new(),
+            next_slot: 0,
+        };
+
+        virtualizer.update();
+        virtualizer
+    }
+
+    /// Sets the number of items built above and below the viewport.
+    pub fn set_overscan(&mut self, overscan: usize) {
+        self.overscan = overscan;
+        self.update();
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn viewport_height(&self) -> f32 {
+        self.viewport_height
+    }
+
+    /// Sets the height of the viewport, such as when the list is resized.
+    pub fn set_viewport_height(&mut self, height: f32) {
+        self.viewport_height = height.max(0.0);
+        self.scroll_to(self.scroll_offset());
+    }
+
+    /// Returns the height of every item, using the estimated height for items which were not measured.
+    pub fn content_height(&self) -> f32 {
+        self.heights.total() as f32
+    }
+
+    /// Returns the distance the list is scrolled from the top.
+    pub fn scroll_offset(&self) -> f32 {
+        (self.heights.prefix(self.anchor) + self.anchor_offset) as f32
+    }
+
+    /// Scrolls to an offset from the top of the list.
+    ///
+    /// The offset is clamped so the viewport does not extend past the end of the list.
+    pub fn scroll_to(&mut self, offset: f32) {
+        let max = (self.heights.total() - f64::from(self.viewport_height)).max(0.0);
+        let offset = f64::from(offset).clamp(0.0, max);
+
+        self.anchor = self.heights.find(offset).min(self.len().saturating_sub(1));
+        self.anchor_offset = offset - self.heights.prefix(self.anchor);
+        self.update();
+    }
+
+    /// Scrolls by a distance, where a positive distance scrolls towards the end of the list.
+    pub fn scroll_by(&mut self, delta: f32) {
+        self.scroll_to(self.scroll_offset() + delta);
+    }
+
+    /// Scrolls so the top of an item is at the top of the viewport.
+    pub fn scroll_to_item(&mut self, index: usize) {
+        self.scroll_to(self.heights.prefix(index.min(self.len())) as f32);
+    }
+
+    /// Returns the offset of an item from the top of the list.
+    pub fn item_offset(&self, index: usize) -> f32 {
+        self.heights.prefix(index.min(self.len())) as f32
+    }
+
+    /// Returns the height of an item, which is the estimated height if the item was not measured.
+    pub fn item_height(&self, index: usize) -> f32 {
+        self.heights.get(index) as f32
+    }
+
+    /// Returns whether the height of an item was measured.
+    pub fn is_measured(&self, index: usize) -> bool {
+        self.heights.is_measured(index)
+    }
+
+    /// Sets the measured height of an item.
+    pub fn measure(&mut self, index: usize, height: f32) {
+        if index >= self.len() {
+            return;
+        }
+
+        self.heights.set(index, f64::from(height.max(0.0)));
+
+        if index == self.anchor {
+            self.anchor_offset = self.anchor_offset.min(f64::from(height));
+        }
+
+        self.scroll_to(self.scroll_offset());
+    }
+
+    /// Inserts items with the estimated height.
+    ///
+    /// Inserting items above the first visible item does not move the visible items, unless the list is
+    /// scrolled to the top and the items are inserted at the start of the list.
+    ///
+    /// # Panics
+    ///
+    /// If the index is greater than the number of items.
+    pub fn insert(&mut self, index: usize, count: usize) {
+        assert!(index <= self.len(), "index out of bounds");
+
+        self.heights
+            .insert(index, count, f64::from(self.estimated_height));
+
+        if index <= self.anchor && !(self.anchor == 0 && self.anchor_offset == 0.0 && index == 0) {
+            self.anchor += count;
+        }
+
+        self.scroll_to(self.scroll_offset());
+    }
+
+    /// Removes items.
+    ///
+    /// Removing items above the first visible item does not move the visible items.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    pub fn remove(&mut self, range: Range<usize>) {
+        assert!(range.end <= self.len(), "range out of bounds");
+
+        self.heights.remove(range.clone());
+
+        if self.anchor >= range.end {
+            self.anchor -= range.len();
+        } else if self.anchor >= range.start {
+            self.anchor = range.start;
+            self.anchor_offset = 0.0;
+        }
+
+        self.scroll_to(self.scroll_offset());
+    }
+
+    /// Returns the items which should be built, in order.
+    pub fn visible(&self) -> &[VisibleItem] {
+        &self.visible
+    }
+
+    /// Returns the range of items which should be built.
+    pub fn visible_range(&self) -> Range<usize> {
+        match (self.visible.first(), self.visible.last()) {
+            (Some(first), Some(last)) => first.index..last.index + 1,
+            _ => 0..0,
+        }
+    }
+
+    /// Computes the visible items, keeping the slots of items which remain visible.
+    fn update(&mut self) {
+        if self.is_empty() {
+            self.free_slots
+                .extend(self.visible.drain(..).map(|item| item.slot));
+            return;
+        }
+
+        let scroll = self.heights.prefix(self.anchor) + self.anchor_offset;
+        let end = scroll + f64::from(self.viewport_height);
+        let mut last = self.heights.find(end).min(self.len() - 1);
+
+        // An item starting at the bottom edge of the viewport is not visible.
+        if last > self.anchor && self.heights.prefix(last) >= end {
+            last -= 1;
+        }
+
+        let range =
+            self.anchor.saturating_sub(self.overscan)..(last + self.overscan + 1).min(self.len());
+
+        let previous = core::mem::take(&mut self.visible);
+        let mut slots = Vec::with_capacity(range.len());
+
+        // Release the slots of items which are no longer visible first so they may be reused.
+        for item in &previous {
+            if !range.contains(&item.index) {
+                self.free_slots.push(item.slot);
+            }
+        }
+
+        for index in range {
+            let slot = match previous.iter().find(|item| item.index == index) {
+                Some(item) => item.slot,
+                None => match self.free_slots.pop() {
+                    Some(slot) => slot,
+                    None => {
+                        let slot = self.next_slot;
+                        self.next_slot += 1;
+                        slot
+                    }
+                },
+            };
+
+            slots.push(VisibleItem {
+                index,
+                slot,
+                offset: (self.heights.prefix(index) - scroll) as f32,
+                height: self.heights.get(index) as f32,
+            });
+        }
+
+        self.visible = slots;
+    }
+}
+
+/// The heights of the items in a [`Virtualizer`].
+///
+/// This is an implicit treap of runs of items with the same height, ordered by the index of the items. Each node
+/// stores the number of items and the sum of the heights in it's subtree, so the offset of an item, the item at an
+/// offset and inserting, removing or measuring items take logarithmic time. Items which were not measured stay in
+/// runs, so a list of estimated items only needs a single node.
+#[derive(Debug, Clone)]
+struct Heights {
+    runs: Vec<Run>,
+    /// Runs which were removed and may be reused.
+    free: Vec<usize>,
+    root: Option<usize>,
+    /// The state of the generator of the priorities of the runs.
+    seed: u32,
+}
+
+/// A node of [`Heights`].
+#[derive(Debug, Clone)]
+struct Run {
+    /// The height of each item in the run.
+    height: f64,
+    len: usize,
+    measured: bool,
+    /// A random priority, which is greater than the priorities of the children to keep the tree balanced.
+    priority: u32,
+    left: Option<usize>,
+    right: Option<usize>,
+    /// The number of items in the subtree.
+    count: usize,
+    /// The sum of the heights in the subtree.
+    sum: f64,
+}
+
+impl Heights {
+    fn new(len: usize, height: f64) -> Self {
+        let mut heights = Self {
+            runs: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            seed: 0x9e37_79b9,
+        };
+
+        heights.insert(0, len, height);
+        heights
+    }
+
+    fn len(&self) -> usize {
+        self.count(self.root)
+    }
+
+    fn count(&self, run: Option<usize>) -> usize {
+        run.map_or(0, |run| self.runs[run].count)
+    }
+
+    fn sum(&self, run: Option<usize>) -> f64 {
+        run.map_or(0.0, |run| self.runs[run].sum)
+    }
+
+    /// Returns the sum of the heights of the items before `index`.
+    fn prefix(&self, mut index: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut next = self.root;
+
+        while let Some(run) = next {
+            let node = &self.runs[run];
+            let before = self.count(node.left);
+
+            if index <= before {
+                next = node.left;
+            } else if index < before + node.len {
+                return sum + self.sum(node.left) + node.height * (index - before) as f64;
+            } else {
+                sum += self.sum(node.left) + node.height * node.len as f64;
+                index -= before + node.len;
+                next = node.right;
+            }
+        }
+
+        sum
+    }
+
+    fn total(&self) -> f64 {
+        self.sum(self.root)
+    }
+
+    /// Returns the run containing an item.
+    fn run(&self, mut index: usize) -> Option<&Run> {
+        let mut next = self.root;
+
+        while let Some(run) = next {
+            let node = &self.runs[run];
+            let before = self.count(node.left);
+
+            if index < before {
+                next = node.left;
+            } else if index < before + node.len {
+                return Some(node);
+            } else {
+                index -= before + node.len;
+                next = node.right;
+            }
+        }
+
+        None
+    }
+
+    fn get(&self, index: usize) -> f64 {
+        self.run(index).map_or(0.0, |run| run.height)
+    }
+
+    fn is_measured(&self, index: usize) -> bool {
+        self.run(index).is_some_and(|run| run.measured)
+    }
+
+    /// Sets the measured height of an item.
+    fn set(&mut self, index: usize, height: f64) {
+        let (before, rest) = self.split(self.root, index);
+        let (item, after) = self.split(rest, 1);
+
+        // The item is a single run without children, which gets a new priority so it's position in the tree does
+        // not depend on the run it was split from.
+        if let Some(item) = item {
+            let priority = self.priority();
+            let run = &mut self.runs[item];
+            run.height = height;
+            run.measured = true;
+            run.priority = priority;
+            self.update(item);
+        }
+
+        let before = self.merge(before, item);
+        self.root = self.merge(before, after);
+    }
+
+    /// Inserts a run of items which were not measured.
+    fn insert(&mut self, index: usize, len: usize, height: f64) {
+        if len == 0 {
+            return;
+        }
+
+        let run = Run {
+            height,
+            len,
+            measured: false,
+            priority: self.priority(),
+            left: None,
+            right: None,
+            count: len,
+            sum: height * len as f64,
+        };
+
+        let run = self.push(run);
+        let (before, after) = self.split(self.root, index);
+        let before = self.merge(before, Some(run));
+        self.root = self.merge(before, after);
+    }
+
+    fn remove(&mut self, range: Range<usize>) {
+        let (before, rest) = self.split(self.root, range.start);
+        let (removed, after) = self.split(rest, range.len());
+        self.root = self.merge(before, after);
+
+        let mut stack = Vec::from_iter(removed);
+
+        while let Some(run) = stack.pop() {
+            stack.extend(self.runs[run].left);
+            stack.extend(self.runs[run].right);
+            self.free.push(run);
+        }
+    }
+
+    /// Returns the index of the item containing an offset.
+    ///
+    /// Returns the number of items if the offset is past the end.
+    fn find(&self, offset: f64) -> usize {
+        let mut index = 0;
+        let mut remaining = offset;
+        let mut next = self.root;
+
+        while let Some(run) = next {
+            let node = &self.runs[run];
+            let left = self.sum(node.left);
+
+            if remaining < left {
+                next = node.left;
+                continue;
+            }
+
+            remaining -= left;
+            index += self.count(node.left);
+            let height = node.height * node.len as f64;
+
+            if remaining < height {
+                return index + ((remaining / node.height) as usize).min(node.len - 1);
+            }
+
+            remaining -= height;
+            index += node.len;
+            next = node.right;
+        }
+
+        index
+    }
+
+    /// Returns a random priority for a new run.
+    fn priority(&mut self) -> u32 {
+        // A xorshift generator is random enough to balance the tree.
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed
+    }
+
+    /// Adds a run to the arena, reusing a removed run if possible.
+    fn push(&mut self, run: Run) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.runs[index] = run;
+                index
+            }
+
+            None => {
+                self.runs.push(run);
+                self.runs.len() - 1
+            }
+        }
+    }
+
+    /// Recomputes the count and sum of a run from it's children.
+    fn update(&mut self, run: usize) {
+        let node = &self.runs[run];
+        let count = node.len + self.count(node.left) + self.count(node.right);
+        let sum = node.height * node.len as f64 + self.sum(node.left) + self.sum(node.right);
+
+        let node = &mut self.runs[run];
+        node.count = count;
+        node.sum = sum;
+    }
+
+    /// Splits a subtree into the first `index` items and the remaining items, splitting the run containing the
+    /// index if needed.
+    fn split(&mut self, run: Option<usize>, index: usize) -> (Option<usize>, Option<usize>) {
+        let run = match run {
+            Some(run) => run,
+            None => return (None, None),
+        };
+
+        let node = &self.runs[run];
+        let before = self.count(node.left);
+        let (left, right, len) = (node.left, node.right, node.len);
+
+        if index <= before {
+            let (first, second) = self.split(left, index);
+            self.runs[run].left = second;
+            self.update(run);
+            (first, Some(run))
+        } else if index >= before + len {
+            let (first, second) = self.split(right, index - before - len);
+            self.runs[run].right = first;
+            self.update(run);
+            (Some(run), second)
+        } else {
+            // The second part of the run gets a new priority, since splitting the same run again and again would
+            // otherwise add runs of equal priority in a line, and is merged with the right subtree.
+            let split = index - before;
+            let rest = Run {
+                len: len - split,
+                priority: self.priority(),
+                left: None,
+                right: None,
+                ..self.runs[run].clone()
+            };
+
+            let rest = self.push(rest);
+            self.update(rest);
+            self.runs[run].len = split;
+            self.runs[run].right = None;
+            self.update(run);
+            (Some(run), self.merge(Some(rest), right))
+        }
+    }
+
+    /// Joins two subtrees, where every item of `first` is before the items of `second`.
+    fn merge(&mut self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+        match (first, second) {
+            (None, run) | (run, None) => run,
+            (Some(first), Some(second)) => {
+                if self.runs[first].priority >= self.runs[second].priority {
+                    let right = self.runs[first].right;
+                    self.runs[first].right = self.merge(right, Some(second));
+                    self.update(first);
+                    Some(first)
+                } else {
+                    let left = self.runs[second].left;
+                    self.runs[second].left = self.merge(Some(first), left);
+                    self.update(second);
+                    Some(second)
+                }
+            }
+        }
+    }
+}
+
+/// The viewport of a [`List`], which is the parent of the built items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListViewport {
+    pub bounds: Rect,
+    /// The height of every item in the list.
+    pub content_height: f32,
+    pub scroll_offset: f32,
+    /// The items which were built.
+    pub visible: Range<usize>,
+}
+
+/// An item built by a [`List`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListItem {
+    pub index: usize,
+    /// The slot of the item, see [`VisibleItem::slot`].
+    pub slot: usize,
+    /// The area of the item on the surface.
+    pub bounds: Rect,
+}
+
+/// A view which only builds the visible items of a list.
+///
+/// The list builds a [`ListViewport`] with a child for each visible item. Because the viewport and the items are
+/// built in the same scene, the state of the item views must be created from a [`ListViewport`].
+///
+/// The items are built in the order of their [slots](VisibleItem::slot) rather than their indices, so rebuilding
+/// the list after scrolling recycles the retained nodes: an item keeps it's node while it remains visible, and the
+/// nodes of items scrolled out of view are reused for the items scrolled into view.
+pub struct List<'a, F> {
+    virtualizer: &'a Virtualizer,
+    bounds: Rect,
+    columns: usize,
+    len: usize,
+    item: F,
+}
+
+impl<'a, F> List<'a, F> {
+    /// Creates a list filling the bounds, which calls `item` to create the view of each visible item.
+    ///
+    /// The height of the bounds should be the [viewport height](Virtualizer::set_viewport_height) of the
+    /// virtualizer.
+    pub fn new(virtualizer: &'a Virtualizer, bounds: Rect, item: F) -> Self {
+        Self {
+            virtualizer,
+            bounds,
+            columns: 1,
+            len: virtualizer.len(),
+            item,
+        }
+    }
+
+    /// Lays out `len` items in a grid with a number of columns.
+    ///
+    /// Each item of the virtualizer is a line of the grid, so the virtualizer should have
+    /// `len.div_ceil(columns)` items. In a right-to-left [layout](crate::layout), the first column is on the
+    /// right.
+    #[must_use]
+    pub fn columns(self, columns: usize, len: usize) -> Self {
+        Self {
+            columns: columns.max(1),
+            len,
+            ..self
+        }
+    }
+}
+
+impl<T, F, V> View<T> for List<'_, F>
+where
+    F: Fn(ListItem) -> V,
+    V: View<T>,
+    V::State: From<ListViewport>,
+{
+    type State = V::State;
+
+    fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+    where
+        N: From<Self::State>,
+    {
+        let lines = self.virtualizer.visible_range();
+        let visible = lines.start * self.columns..(lines.end * self.columns).min(self.len);
+        let direction = scene.direction();
+
+        let mut viewport = scene.build_node::<Self, T>(V::State::from(ListViewport {
+            bounds: self.bounds,
+            content_height: self.virtualizer.content_height(),
+            scroll_offset: self.virtualizer.scroll_offset(),
+            visible: visible.clone(),
+        }));
+
+        let width = self.bounds.width / self.columns as f32;
+
+        // Building the lines in the order of their slots keeps the node of a line while it remains visible, and
+        // reuses the nodes of lines scrolled out of view for the lines scrolled into view.
+        let mut lines = self.virtualizer.visible().to_vec();
+        lines.sort_unstable_by_key(|line| line.slot);
+
+        for line in &lines {
+            for column in 0..self.columns {
+                let index = line.index * self.columns + column;
+
+                if index >= self.len {
+                    break;
+                }
+
+                let item = (self.item)(ListItem {
+                    index,
+                    slot: line.slot * self.columns + column,
+                    bounds: direction.mirror(
+                        Rect::new(
+                            self.bounds.x + width * column as f32,
+                            self.bounds.y + line.offset,
+                            width,
+                            line.height,
+                        ),
+                        self.bounds,
+                    ),
+                });
+
+                viewport.build_child(&item);
+            }
+        }
+
+        viewport
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use crate::{
+        geometry::Rect,
+        layout::Direction,
+        scene::{Node, Scene},
+        tree::RetainedTree,
+        view::View,
+    };
+
+    use super::{Heights, List, ListItem, ListViewport, Virtualizer};
+
+    #[derive(Debug, PartialEq)]
+    enum Cell {
+        Viewport(ListViewport),
+        Item(ListItem),
+    }
+
+    impl From<ListViewport> for Cell {
+        fn from(viewport: ListViewport) -> Self {
+            Cell::Viewport(viewport)
+        }
+    }
+
+    struct Item(ListItem);
+
+    impl View<()> for Item {
+        type State = Cell;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            scene.build_node::<Self, ()>(Cell::Item(self.0))
+        }
+    }
+
+    fn build<F>(list: &List<'_, F>) -> Vec<ListItem>
+    where
+        F: Fn(ListItem) -> Item,
+    {
+        build_in(list, Direction::LeftToRight)
+    }
+
+    fn build_in<F>(list: &List<'_, F>, direction: Direction) -> Vec<ListItem>
+    where
+        F: Fn(ListItem) -> Item,
+    {
+        let mut tree = RetainedTree::<Cell>::new();
+        let mut scene = Scene::root(&mut tree, None);
+        scene.set_direction(direction);
+        View::<()>::build(list, &mut scene);
+
+        tree.iter()
+            .filter_map(|(_, cell)| match cell {
+                Cell::Item(item) => Some(*item),
+                Cell::Viewport(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn virtualize() {
+        const BOUNDS: Rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        let mut virtualizer = Virtualizer::new(100_000, 20.0);
+        virtualizer.set_viewport_height(100.0);
+        assert_eq!(virtualizer.content_height(), 2_000_000.0);
+
+        // 5 items are visible, with an overscan of 3 items below the viewport.
+        let items = build(&List::new(&virtualizer, BOUNDS, Item));
+        assert_eq!(items.len(), 8);
+        assert_eq!(items[1].bounds, Rect::new(0.0, 20.0, 100.0, 20.0));
+
+        virtualizer.scroll_to(50_010.0);
+        assert_eq!(virtualizer.visible_range(), 2497..2509);
+        assert_eq!(virtualizer.visible()[3].offset, -10.0);
+
+        // Scrolling by less than the overscan keeps the slots of items which remain visible.
+        let slot = virtualizer.visible()[5].slot;
+        virtualizer.scroll_by(40.0);
+        assert_eq!(virtualizer.visible()[3].slot, slot);
+        let mut slots = virtualizer
+            .visible()
+            .iter()
+            .map(|item| item.slot)
+            .collect::<Vec<_>>();
+        slots.sort_unstable();
+        slots.dedup();
+        assert_eq!(slots.len(), 12);
+        assert!(slots.iter().all(|&slot| slot < 12));
+
+        // Measuring or inserting items above the viewport keeps the first visible item in place.
+        let first = virtualizer.visible()[3];
+        virtualizer.measure(0, 100.0);
+        virtualizer.insert(10, 5);
+        assert_eq!(virtualizer.visible()[3].index, first.index + 5);
+        assert_eq!(virtualizer.visible()[3].offset, first.offset);
+        assert_eq!(virtualizer.scroll_offset(), 50_050.0 + 80.0 + 100.0);
+
+        virtualizer.remove(0..20);
+        assert_eq!(virtualizer.visible()[3].index, first.index - 15);
+        assert_eq!(virtualizer.scroll_offset(), 50_050.0 - 300.0);
+
+        // Scrolling past the end is clamped.
+        virtualizer.scroll_to(f32::MAX);
+        assert_eq!(virtualizer.visible_range(), 99_977..99_985);
+    }
+
+    #[test]
+    fn grid() {
+        let mut virtualizer = Virtualizer::new(4, 50.0);
+        virtualizer.set_viewport_height(50.0);
+        virtualizer.set_overscan(0);
+
+        let list = List::new(&virtualizer, Rect::new(0.0, 0.0, 90.0, 50.0), Item).columns(3, 10);
+        let items = build(&list);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[2].bounds, Rect::new(60.0, 0.0, 30.0, 50.0));
+
+        // The columns are mirrored in a right-to-left layout.
+        let items = build_in(&list, Direction::RightToLeft);
+        assert_eq!(items[0].bounds, Rect::new(60.0, 0.0, 30.0, 50.0));
+        assert_eq!(items[2].bounds, Rect::new(0.0, 0.0, 30.0, 50.0));
+
+        virtualizer.scroll_to_item(3);
+        let list = List::new(&virtualizer, Rect::new(0.0, 0.0, 90.0, 50.0), Item).columns(3, 10);
+        let items = build(&list);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].index, 9);
+    }
+
+    #[test]
+    fn recycle_nodes() {
+        const BOUNDS: Rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+
+        let mut virtualizer = Virtualizer::new(1000, 20.0);
+        virtualizer.set_viewport_height(100.0);
+        virtualizer.set_overscan(0);
+
+        let mut tree = RetainedTree::<Cell>::new();
+        let root = View::<()>::build(
+            &List::new(&virtualizer, BOUNDS, Item),
+            &mut Scene::root(&mut tree, None),
+        )
+        .id();
+        let nodes = |tree: &RetainedTree<Cell>| {
+            tree.children(root)
+                .map(|(id, cell)| match cell {
+                    Cell::Item(item) => (item.index, id),
+                    Cell::Viewport(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let before = nodes(&tree);
+
+        // The items which remain visible keep their nodes, and the node of the item scrolled out of view is
+        // reused for the item scrolled into view.
+        virtualizer.scroll_by(20.0);
+        View::<()>::build(
+            &List::new(&virtualizer, BOUNDS, Item),
+            &mut Scene::root(&mut tree, Some(root)),
+        );
+        let after = nodes(&tree);
+
+        assert_eq!(tree.len(), 6);
+        assert_eq!(after[1..], before[1..]);
+        assert_eq!(after[0], (5, before[0].1));
+    }
+
+    #[test]
+    fn heights() {
+        let mut heights = Heights::new(10, 10.0);
+        let mut expected = vec![10.0; 10];
+
+        heights.set(3, 5.0);
+        expected[3] = 5.0;
+        heights.insert(5, 3, 20.0);
+        expected.splice(5..5, [20.0; 3]);
+        heights.set(6, 0.0);
+        expected[6] = 0.0;
+        heights.remove(2..5);
+        expected.drain(2..5);
+        heights.insert(10, 2, 1.0);
+        expected.splice(10..10, [1.0; 2]);
+
+        assert_eq!(heights.len(), expected.len());
+        assert!(heights.is_measured(3));
+        assert!(!heights.is_measured(2));
+
+        for index in 0..=expected.len() {
+            let prefix = expected[..index].iter().sum::<f64>();
+            assert_eq!(heights.prefix(index), prefix);
+            assert_eq!(
+                heights.get(index),
+                expected.get(index).copied().unwrap_or(0.0)
+            );
+
+            // The item with a height of zero is skipped.
+            if expected.get(index).is_some_and(|&height| height > 0.0) {
+                assert_eq!(heights.find(prefix), index);
+                assert_eq!(heights.find(prefix + 0.5), index);
+            }
+        }
+
+        assert_eq!(heights.find(heights.total()), expected.len());
+    }
+
+    #[test]
+    fn measure_in_order() {
+        // Scrolling through a list measures the items in order, which must keep the tree balanced.
+        let len = 100_000;
+        let mut heights = Heights::new(len, 10.0);
+
+        for index in 0..len {
+            heights.set(index, 20.0);
+        }
+
+        assert_eq!(heights.len(), len);
+        assert_eq!(heights.total(), 20.0 * len as f64);
+        assert_eq!(heights.prefix(len / 2), 20.0 * (len / 2) as f64);
+        assert_eq!(heights.find(20.0 * (len - 1) as f64 + 0.5), len - 1);
+        assert!(depth(&heights, heights.root) < 100);
+    }
+
+    fn depth(heights: &Heights, run: Option<usize>) -> usize {
+        run.map_or(0, |run| {
+            let run = &heights.runs[run];
+            1 + depth(heights, run.left).max(depth(heights, run.right))
+        })
+    }
+}
@@ -1,23 +1,18 @@
-use core::{marker::PhantomData, num::NonZeroU64};
+use crate::scene::{Node, Scene};
 
 // pub mod adapt;
+pub mod list;
+pub mod row;
+pub mod scroll;
 // pub mod memoize;
 
-pub struct SlotIdentity<T> {
-    id: NonZeroU64,
-    _marker: PhantomData<T>,
-}
-
-#[must_use]
-pub struct Slot<'a, V, T>
-where
-    V: View<T>,
-{
-    state: &'a mut Option<V::State>,
-}
-
 pub trait View<T>: Sized {
     type State: Sized;
 
-    fn build(&self, slot: Slot<'_, Self, T>) -> SlotIdentity<Self::State>;
+    /// Builds the node for this view in the scene.
+    ///
+    /// Views with children should build their children using the returned [`Node`].
+    fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+    where
+        N: From<Self::State>;
 }
@@ -0,0 +1,162 @@
+//! Horizontal stacks.
+
+use crate::{
+    geometry::Rect,
+    layout::{self, Alignment, Direction},
+    scene::{Node, Scene},
+};
+
+use super::View;
+
+/// The node of a [`Row`], which is the parent of the children of the row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RowNode {
+    pub bounds: Rect,
+    /// The layout direction the children were placed in.
+    pub direction: Direction,
+}
+
+/// A view which places children of known widths next to each other.
+///
+/// The first child is placed at the start of the [layout direction](crate::layout), so the row is mirrored in a
+/// right-to-left layout. The children are built in order, so the focus order follows the reading order. Because the
+/// row and it's children are built in the same scene, the state of the child views must be created from a
+/// [`RowNode`].
+pub struct Row<'a, F> {
+    bounds: Rect,
+    widths: &'a [f32],
+    spacing: f32,
+    alignment: Alignment,
+    child: F,
+}
+
+impl<'a, F> Row<'a, F> {
+    /// Creates a row with a child for each width, which calls `child` with the index and bounds of each child to
+    /// create it's view.
+    pub fn new(bounds: Rect, widths: &'a [f32], child: F) -> Self {
+        Self {
+            bounds,
+            widths,
+            spacing: 0.0,
+            alignment: Alignment::Start,
+            child,
+        }
+    }
+
+    /// Sets the space between children.
+    #[must_use]
+    pub fn spacing(self, spacing: f32) -> Self {
+        Self { spacing, ..self }
+    }
+
+    /// Sets how the children are aligned when they are narrower than the row.
+    #[must_use]
+    pub fn alignment(self, alignment: Alignment) -> Self {
+        Self { alignment, ..self }
+    }
+}
+
+impl<T, F, V> View<T> for Row<'_, F>
+where
+    F: Fn(usize, Rect) -> V,
+    V: View<T>,
+    V::State: From<RowNode>,
+{
+    type State = V::State;
+
+    fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+    where
+        N: From<Self::State>,
+    {
+        let direction = scene.direction();
+        let mut row = scene.build_node::<Self, T>(V::State::from(RowNode {
+            bounds: self.bounds,
+            direction,
+        }));
+
+        let children = layout::stack(
+            direction,
+            self.alignment,
+            self.bounds,
+            self.spacing,
+            self.widths,
+        );
+
+        for (index, bounds) in children.into_iter().enumerate() {
+            row.build_child(&(self.child)(index, bounds));
+        }
+
+        row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        geometry::Rect,
+        layout::{Alignment, Direction},
+        scene::{Node, Scene},
+        tree::RetainedTree,
+        view::View,
+    };
+
+    use super::{Row, RowNode};
+
+    #[derive(Debug, PartialEq)]
+    enum Cell {
+        Row(RowNode),
+        Child(usize, Rect),
+    }
+
+    impl From<RowNode> for Cell {
+        fn from(row: RowNode) -> Self {
+            Cell::Row(row)
+        }
+    }
+
+    struct Child(usize, Rect);
+
+    impl View<()> for Child {
+        type State = Cell;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            scene.build_node::<Self, ()>(Cell::Child(self.0, self.1))
+        }
+    }
+
+    #[test]
+    fn mirrored() {
+        let row = Row::new(Rect::new(0.0, 0.0, 100.0, 20.0), &[20.0, 30.0], Child)
+            .spacing(10.0)
+            .alignment(Alignment::End);
+
+        let mut tree = RetainedTree::<Cell>::new();
+        let mut scene = Scene::root(&mut tree, None);
+        scene.set_direction(Direction::RightToLeft);
+        let root = View::<()>::build(&row, &mut scene).id();
+
+        // Children remain in reading order, from right to left.
+        assert_eq!(
+            tree.children(root)
+                .map(|(_, cell)| cell)
+                .collect::<Vec<_>>(),
+            [
+                &Cell::Child(0, Rect::new(40.0, 0.0, 20.0, 20.0)),
+                &Cell::Child(1, Rect::new(0.0, 0.0, 30.0, 20.0)),
+            ]
+        );
+
+        // Children inherit the direction of the row unless the row changes it.
+        let mut scene = Scene::root(&mut tree, Some(root));
+        scene.set_direction(Direction::RightToLeft);
+        let mut node = View::<()>::build(&row, &mut scene);
+        assert_eq!(node.direction(), Direction::RightToLeft);
+        node.set_direction(Direction::LeftToRight);
+        assert_eq!(node.children().direction(), Direction::LeftToRight);
+    }
+}
@@ -0,0 +1,928 @@
+//! Scrollable views.
+//!
+//! A [`ScrollView`] builds a [`ScrollViewport`] which clips it's content, followed by overlay [`Scrollbar`]s.
+//! The scroll position is kept in a [`Scroller`], which is usually stored in the data of the
+//! [`Context`](crate::Context) so it survives rebuilding the view.
+//!
+//! The viewport handles [scroll events](crate::event::Event::Scroll) and [pan](Gesture::Pan) gestures:
+//!
+//! - Scroll wheels scroll immediately and never scroll past the edges of the content.
+//! - Touchpads and touch screens may scroll past the edges of the content with increasing resistance. When
+//!   released, scrolling continues with the velocity of the fingers and slows down (kinetic scrolling), bouncing
+//!   back if the content was scrolled past an edge.
+//!
+//! Kinetic scrolling, bouncing and fading out the scrollbars are animations. While
+//! [`Scroller::is_animating`] returns true, the host should call [`Scroller::animate`] every frame and rebuild
+//! the view.
+
+use core::time::Duration;
+
+use crate::{
+    event::{Event, EventContext, ScrollEvent, ScrollSource},
+    geometry::{Point, Rect, Size},
+    gesture::{Gesture, GestureArena, Pan, Phase},
+    layout::Direction,
+    scene::{Node, Scene},
+};
+
+use super::View;
+
+/// How quickly kinetic scrolling slows down, as the fraction of velocity lost per second on a logarithmic scale.
+const FRICTION: f32 = 4.0;
+
+/// Kinetic scrolling stops below this velocity, in units per second.
+const MIN_VELOCITY: f32 = 10.0;
+
+/// The stiffness of the spring which pulls the content back after scrolling past an edge.
+const STIFFNESS: f32 = 150.0;
+
+/// The length of a step of the animation, in seconds.
+///
+/// Longer frames are simulated in multiple steps so the spring remains stable.
+const STEP: f32 = 1.0 / 240.0;
+
+/// How long the scrollbars remain visible after scrolling stops.
+const SCROLLBAR_DELAY: Duration = Duration::from_millis(800);
+
+/// How long the scrollbars take to fade out.
+const SCROLLBAR_FADE: Duration = Duration::from_millis(200);
+
+/// The thickness of a scrollbar thumb.
+pub const SCROLLBAR_THICKNESS: f32 = 6.0;
+
+/// The distance between a scrollbar thumb and the edges of the viewport.
+const SCROLLBAR_MARGIN: f32 = 2.0;
+
+/// The smallest length of a scrollbar thumb.
+const SCROLLBAR_MIN_LENGTH: f32 = 24.0;
+
+/// The scroll position of a [`ScrollView`].
+#[derive(Debug, Clone)]
+pub struct Scroller {
+    x: Axis,
+    y: Axis,
+    /// The area of the viewport on the surface.
+    viewport: Rect,
+    content: Size,
+    /// The scroll offset without resistance while a touchpad or touch screen is scrolling.
+    drag: Option<Point>,
+    /// The translation of the pan in progress.
+    pan: Point,
+    /// The time of the last animation frame or scroll event.
+    time: Option<Duration>,
+    /// When scrolling last stopped, or [`None`] if the time is not known yet.
+    idle_since: Option<Duration>,
+    scrollbar_opacity: f32,
+}
+
+/// Only the offset is serialized. Scrolling in progress is stopped when deserializing.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Scroller {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.offset().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Scroller {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let offset = Point::deserialize(deserializer)?;
+        let mut scroller = Scroller::new();
+        scroller.x.offset = offset.x;
+        scroller.y.offset = offset.y;
+        Ok(scroller)
+    }
+}
+
+/// The scroll state of one axis.
+#[derive(Debug, Default, Clone, Copy)]
+struct Axis {
+    offset: f32,
+    velocity: f32,
+}
+
+impl Default for Scroller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scroller {
+    pub fn new() -> Self {
+        Self {
+            x: Axis::default(),
+            y: Axis::default(),
+            viewport: Rect::ZERO,
+            content: Size::ZERO,
+            drag: None,
+            pan: Point::ZERO,
+            time: None,
+            idle_since: None,
+            scrollbar_opacity: 0.0,
+        }
+    }
+
+    /// Sets the area of the viewport on the surface and the size of the content.
+    ///
+    /// The viewport updates this when handling events. This should be called when the layout changes, before
+    /// scrolling programmatically.
+    pub fn set_viewport(&mut self, viewport: Rect, content: Size) {
+        self.viewport = viewport;
+        self.content = content;
+
+        if self.drag.is_none() && !self.is_animating() {
+            self.scroll_to(self.offset());
+        }
+    }
+
+    /// Returns the distance the content is scrolled from the top left corner.
+    ///
+    /// This is outside of the [range](Scroller::max_offset) while scrolled past an edge.
+    pub fn offset(&self) -> Point {
+        Point::new(self.x.offset, self.y.offset)
+    }
+
+    /// Returns the largest offset which does not scroll past the edges of the content.
+    pub fn max_offset(&self) -> Point {
+        Point::new(
+            (self.content.width - self.viewport.width).max(0.0),
+            (self.content.height - self.viewport.height).max(0.0),
+        )
+    }
+
+    /// Scrolls to an offset, stopping any kinetic scrolling.
+    ///
+    /// The offset is clamped to the edges of the content.
+    pub fn scroll_to(&mut self, offset: Point) {
+        let max = self.max_offset();
+        self.x = Axis {
+            offset: offset.x.clamp(0.0, max.x),
+            velocity: 0.0,
+        };
+        self.y = Axis {
+            offset: offset.y.clamp(0.0, max.y),
+            velocity: 0.0,
+        };
+    }
+
+    pub fn scroll_by(&mut self, delta: Point) {
+        let offset = self.offset();
+        self.scroll_to(Point::new(offset.x + delta.x, offset.y + delta.y));
+        self.show_scrollbars();
+    }
+
+    /// Scrolls the least distance needed to make an area of the surface visible.
+    ///
+    /// This may be used to scroll to a node using it's [bounds](crate::ViewNode::bounds).
+    pub fn reveal(&mut self, area: Rect) {
+        let offset = self.offset();
+        let x = reveal(
+            offset.x,
+            self.viewport.x,
+            self.viewport.width,
+            area.x,
+            area.width,
+        );
+        let y = reveal(
+            offset.y,
+            self.viewport.y,
+            self.viewport.height,
+            area.y,
+            area.height,
+        );
+
+        self.scroll_to(Point::new(x, y));
+        self.show_scrollbars();
+    }
+
+    /// Returns whether the content is scrolled past an edge.
+    pub fn is_overscrolled(&self) -> bool {
+        let max = self.max_offset();
+        overscroll(self.x.offset, max.x) != 0.0 || overscroll(self.y.offset, max.y) != 0.0
+    }
+
+    /// Returns whether [`Scroller::animate`] should be called for the next frame.
+    pub fn is_animating(&self) -> bool {
+        self.drag.is_none()
+            && (self.x.velocity != 0.0
+                || self.y.velocity != 0.0
+                || self.is_overscrolled()
+                || self.scrollbar_opacity > 0.0)
+    }
+
+    /// Advances kinetic scrolling and bouncing to a point in time.
+    ///
+    /// Returns whether the scroll position or the scrollbars changed.
+    pub fn animate(&mut self, time: Duration) -> bool {
+        let elapsed = match self.time.replace(time) {
+            Some(previous) => time.saturating_sub(previous).as_secs_f32(),
+            None => 0.0,
+        };
+
+        if self.drag.is_some() || !self.is_animating() {
+            return false;
+        }
+
+        let max = self.max_offset();
+        let mut remaining = elapsed;
+
+        while remaining > 0.0 {
+            let step = remaining.min(STEP);
+            self.x.step(step, max.x);
+            self.y.step(step, max.y);
+            remaining -= step;
+        }
+
+        if self.x.velocity != 0.0 || self.y.velocity != 0.0 || self.is_overscrolled() {
+            self.show_scrollbars();
+        } else {
+            let idle_since = *self.idle_since.get_or_insert(time);
+            let idle = time.saturating_sub(idle_since);
+
+            self.scrollbar_opacity = if idle < SCROLLBAR_DELAY {
+                1.0
+            } else {
+                1.0 - (idle - SCROLLBAR_DELAY).as_secs_f32() / SCROLLBAR_FADE.as_secs_f32()
+            }
+            .max(0.0);
+        }
+
+        true
+    }
+
+    /// Returns the opacity of the overlay scrollbars, which are visible while scrolling.
+    pub fn scrollbar_opacity(&self) -> f32 {
+        self.scrollbar_opacity
+    }
+
+    /// Handles a scroll event.
+    pub fn scroll(&mut self, event: &ScrollEvent) {
+        let previous = self.time.replace(event.time);
+
+        match (event.source, event.phase) {
+            (ScrollSource::Wheel, _) => self.scroll_by(event.delta),
+
+            (ScrollSource::Touchpad, Phase::Began) => self.begin_drag(),
+
+            (ScrollSource::Touchpad, Phase::Changed) => {
+                // The scroll may have begun in a nested viewport which cannot scroll along this axis.
+                if self.drag.is_none() {
+                    self.begin_drag();
+                }
+
+                let elapsed = previous
+                    .map(|previous| event.time.saturating_sub(previous).as_secs_f32())
+                    .unwrap_or_default();
+
+                if elapsed > 0.0 {
+                    // Smooth the velocity, since touchpads report small and uneven steps.
+                    self.x.velocity = 0.8 * event.delta.x / elapsed + 0.2 * self.x.velocity;
+                    self.y.velocity = 0.8 * event.delta.y / elapsed + 0.2 * self.y.velocity;
+                }
+
+                self.drag_by(event.delta);
+            }
+
+            (ScrollSource::Touchpad, Phase::Ended) => {
+                let velocity = Point::new(self.x.velocity, self.y.velocity);
+                self.end_drag(velocity);
+            }
+
+            (ScrollSource::Touchpad, Phase::Cancelled) => self.end_drag(Point::ZERO),
+        }
+    }
+
+    /// Handles a pan gesture, such as dragging the content on a touch screen.
+    pub fn pan(&mut self, phase: Phase, translation: Point, velocity: Point) {
+        // Dragging the content down scrolls up.
+        match phase {
+            Phase::Began => {
+                self.begin_drag();
+                self.pan = translation;
+                self.drag_by(Point::new(-translation.x, -translation.y));
+            }
+
+            Phase::Changed => {
+                let delta = Point::new(self.pan.x - translation.x, self.pan.y - translation.y);
+                self.pan = translation;
+                self.drag_by(delta);
+            }
+
+            Phase::Ended => self.end_drag(Point::new(-velocity.x, -velocity.y)),
+            Phase::Cancelled => self.end_drag(Point::ZERO),
+        }
+    }
+
+    fn begin_drag(&mut self) {
+        self.x.velocity = 0.0;
+        self.y.velocity = 0.0;
+        self.drag = Some(self.offset());
+        self.show_scrollbars();
+    }
+
+    /// Moves the content while dragging, with resistance past the edges of the content.
+    fn drag_by(&mut self, delta: Point) {
+        let max = self.max_offset();
+        let drag = match &mut self.drag {
+            Some(drag) => drag,
+            None => return,
+        };
+
+        drag.x += delta.x;
+        drag.y += delta.y;
+
+        self.x.offset = resist(drag.x, max.x, self.viewport.width);
+        self.y.offset = resist(drag.y, max.y, self.viewport.height);
+    }
+
+    /// Stops dragging, continuing to scroll with a velocity.
+    fn end_drag(&mut self, velocity: Point) {
+        self.drag = None;
+        self.time = None;
+        // Only scroll kinetically along axes which can scroll.
+        let max = self.max_offset();
+        self.x.velocity = if max.x > 0.0 { velocity.x } else { 0.0 };
+        self.y.velocity = if max.y > 0.0 { velocity.y } else { 0.0 };
+        self.show_scrollbars();
+    }
+
+    fn show_scrollbars(&mut self) {
+        self.scrollbar_opacity = 1.0;
+        self.idle_since = None;
+    }
+}
+
+impl Axis {
+    /// Advances the animation of the axis by a step in seconds.
+    fn step(&mut self, step: f32, max: f32) {
+        let overscroll = overscroll(self.offset, max);
+
+        if overscroll == 0.0 {
+            self.velocity *= libm::expf(-FRICTION * step);
+            self.offset += self.velocity * step;
+
+            if self.velocity.abs() < MIN_VELOCITY {
+                self.velocity = 0.0;
+            }
+
+            return;
+        }
+
+        // A critically damped spring pulls the content back to the edge without oscillating.
+        let damping = 2.0 * libm::sqrtf(STIFFNESS);
+        let acceleration = -STIFFNESS * overscroll - damping * self.velocity;
+        self.velocity += acceleration * step;
+        self.offset += self.velocity * step;
+
+        let edge = self.offset.clamp(0.0, max);
+        let settled = (self.offset - edge).abs() < 0.5 && self.velocity.abs() < MIN_VELOCITY;
+
+        // Stop at the edge instead of crossing it.
+        if settled || overscroll.signum() != (self.offset - edge).signum() {
+            self.offset = edge;
+            self.velocity = 0.0;
+        }
+    }
+}
+
+/// Returns how far an offset is past the edges of the content.
+fn overscroll(offset: f32, max: f32) -> f32 {
+    if offset < 0.0 {
+        offset
+    } else if offset > max {
+        offset - max
+    } else {
+        0.0
+    }
+}
+
+/// Applies resistance to the part of an offset past the edges of the content.
+///
+/// The further the content is dragged past an edge, the slower the content moves, never moving further than the
+/// length of the viewport.
+fn resist(offset: f32, max: f32, length: f32) -> f32 {
+    let overscroll = overscroll(offset, max);
+
+    if overscroll == 0.0 || length <= 0.0 {
+        return offset.clamp(0.0, max);
+    }
+
+    let resisted = (1.0 - 1.0 / (overscroll.abs() * 0.55 / length + 1.0)) * length;
+    offset.clamp(0.0, max) + resisted.copysign(overscroll)
+}
+
+/// Returns the offset which makes an area visible on one axis.
+fn reveal(offset: f32, viewport: f32, length: f32, start: f32, area: f32) -> f32 {
+    // The start of the area relative to the start of the content.
+    let start = start - viewport + offset;
+
+    if start < offset || area > length {
+        start
+    } else if start + area > offset + length {
+        start + area - length
+    } else {
+        offset
+    }
+}
+
+/// The viewport of a [`ScrollView`], which clips the content.
+///
+/// Nodes created from a viewport should forward events to [`ScrollViewport::event`] and paint nothing outside of
+/// the [bounds](ScrollViewport::bounds).
+pub struct ScrollViewport<T> {
+    pub bounds: Rect,
+    pub content: Size,
+    pub offset: Point,
+    /// Returns the scroller of the viewport from the data of the context.
+    pub scroller: for<'r> fn(&'r mut T) -> &'r mut Scroller,
+}
+
+impl<T> ScrollViewport<T> {
+    /// The area the content is clipped to.
+    pub fn clip(&self) -> Rect {
+        self.bounds
+    }
+
+    /// Creates the recognizer for touch scrolling.
+    pub fn gestures(&self) -> GestureArena {
+        let mut arena = GestureArena::new();
+        arena.add(Pan::default());
+        arena
+    }
+
+    /// Scrolls the content in response to scroll events and pan gestures.
+    pub fn event(&self, cx: &mut EventContext<'_, T>, event: &Event<'_>) -> bool {
+        let scroller = (self.scroller)(cx.data_mut());
+        scroller.set_viewport(self.bounds, self.content);
+
+        match event {
+            Event::Scroll(event) => {
+                let max = scroller.max_offset();
+                let delta = event.delta;
+
+                let scrollable = if delta == Point::ZERO {
+                    max.x > 0.0 || max.y > 0.0
+                } else {
+                    (delta.x != 0.0 && max.x > 0.0) || (delta.y != 0.0 && max.y > 0.0)
+                };
+
+                // Let a parent scroll along axes this viewport cannot scroll.
+                if !scrollable {
+                    return false;
+                }
+
+                scroller.scroll(event);
+            }
+
+            &Event::Gesture(Gesture::Pan {
+                phase,
+                translation,
+                velocity,
+                ..
+            }) => scroller.pan(phase, translation, velocity),
+
+            _ => return false,
+        }
+
+        cx.mark_damaged();
+        true
+    }
+}
+
+impl<T> Clone for ScrollViewport<T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<T> core::fmt::Debug for ScrollViewport<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScrollViewport")
+            .field("bounds", &self.bounds)
+            .field("content", &self.content)
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An overlay scrollbar thumb of a [`ScrollView`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scrollbar {
+    /// The area of the thumb on the surface.
+    pub thumb: Rect,
+    pub opacity: f32,
+    pub color: [f32; 4],
+}
+
+impl Scrollbar {
+    /// The area of the scrollbar on the surface.
+    pub fn bounds(&self) -> Rect {
+        self.thumb
+    }
+
+    /// Paints the thumb, fading the color with the opacity of the scrollbar.
+    #[cfg(feature = "renderer")]
+    pub fn paint<R>(&self, painter: &mut crate::render::Painter<'_, R>)
+    where
+        R: renderer::Renderer + renderer::RenderQuad,
+    {
+        if self.opacity <= 0.0 {
+            return;
+        }
+
+        let [r, g, b, a] = self.color;
+
+        painter.draw_quad(renderer::Quad::new(
+            self.thumb.x,
+            self.thumb.y,
+            self.thumb.width,
+            self.thumb.height,
+            [r, g, b, a * self.opacity],
+        ));
+    }
+}
+
+/// A view which scrolls it's content.
+///
+/// The scroll view builds a [`ScrollViewport`] with the content and the visible [`Scrollbar`]s as children. The
+/// scrollbars are built after the content so they are above the content.
+pub struct ScrollView<'a, T, F> {
+    scroller: &'a Scroller,
+    lens: for<'r> fn(&'r mut T) -> &'r mut Scroller,
+    bounds: Rect,
+    content: Size,
+    scrollbar_color: [f32; 4],
+    view: F,
+}
+
+impl<'a, T, F> ScrollView<'a, T, F> {
+    /// Creates a scroll view filling the bounds.
+    ///
+    /// `scroller` returns the scroller from the data of the context, which must be the same scroller as
+    /// `current`. `view` is called with the position of the top left corner of the content on the surface to create
+    /// the view of the content, which has the size `content`.
+    pub fn new(
+        current: &'a Scroller,
+        scroller: for<'r> fn(&'r mut T) -> &'r mut Scroller,
+        bounds: Rect,
+        content: Size,
+        view: F,
+    ) -> Self {
+        Self {
+            scroller: current,
+            lens: scroller,
+            bounds,
+            content,
+            scrollbar_color: [0.0, 0.0, 0.0, 0.5],
+            view,
+        }
+    }
+
+    #[must_use]
+    pub fn scrollbar_color(self, color: [f32; 4]) -> Self {
+        Self {
+            scrollbar_color: color,
+            ..self
+        }
+    }
+
+    /// Returns the vertical and horizontal scrollbar thumbs, if the content is larger than the viewport.
+    ///
+    /// The vertical scrollbar is on the left in a right-to-left layout.
+    fn scrollbars(&self, direction: Direction) -> [Option<Scrollbar>; 2] {
+        let offset = self.scroller.offset();
+        let opacity = self.scroller.scrollbar_opacity();
+
+        let thumb = |offset: f32, length: f32, content: f32| {
+            if content <= length {
+                return None;
+            }
+
+            let track = length - 2.0 * SCROLLBAR_MARGIN;
+            let thumb = (track * length / content).clamp(SCROLLBAR_MIN_LENGTH.min(track), track);
+            let progress = (offset / (content - length)).clamp(0.0, 1.0);
+            Some((SCROLLBAR_MARGIN + progress * (track - thumb), thumb))
+        };
+
+        let bounds = self.bounds;
+        let edge = |far: f32| far - SCROLLBAR_MARGIN - SCROLLBAR_THICKNESS;
+        let scrollbar = |thumb| Scrollbar {
+            thumb,
+            opacity,
+            color: self.scrollbar_color,
+        };
+
+        [
+            thumb(offset.y, bounds.height, self.content.height).map(|(start, length)| {
+                let thumb = Rect::new(
+                    edge(bounds.right()),
+                    bounds.y + start,
+                    SCROLLBAR_THICKNESS,
+                    length,
+                );
+                scrollbar(direction.mirror(thumb, bounds))
+            }),
+            thumb(offset.x, bounds.width, self.content.width).map(|(start, length)| {
+                scrollbar(Rect::new(
+                    bounds.x + start,
+                    edge(bounds.bottom()),
+                    length,
+                    SCROLLBAR_THICKNESS,
+                ))
+            }),
+        ]
+    }
+}
+
+impl<T, F, V> View<T> for ScrollView<'_, T, F>
+where
+    F: Fn(Point) -> V,
+    V: View<T>,
+    V::State: From<ScrollViewport<T>> + From<Scrollbar>,
+{
+    type State = V::State;
+
+    fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+    where
+        N: From<Self::State>,
+    {
+        let offset = self.scroller.offset();
+        let direction = scene.direction();
+
+        let mut viewport = scene.build_node::<Self, T>(V::State::from(ScrollViewport {
+            bounds: self.bounds,
+            content: self.content,
+            offset,
+            scroller: self.lens,
+        }));
+
+        let origin = Point::new(self.bounds.x - offset.x, self.bounds.y - offset.y);
+        viewport.build_child(&(self.view)(origin));
+
+        if self.scroller.scrollbar_opacity() > 0.0 {
+            for scrollbar in self.scrollbars(direction).into_iter().flatten() {
+                let _ = viewport
+                    .children()
+                    .build_node::<Self, T>(V::State::from(scrollbar));
+            }
+        }
+
+        viewport
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::{
+        event::{Event, EventContext, ScrollEvent, ScrollSource},
+        geometry::{Point, Rect, Size},
+        gesture::{GestureArena, Phase},
+        scene::{Node, Scene},
+        surface::SurfaceId,
+        testing::{Harness, Query},
+        view::View,
+        ViewNode,
+    };
+
+    use super::{ScrollView, ScrollViewport, Scrollbar, Scroller};
+
+    const BOUNDS: Rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+    const CONTENT: Size = Size::new(100.0, 1000.0);
+
+    /// Runs the animation until it stops, returning the time it stopped.
+    fn settle(scroller: &mut Scroller) -> Duration {
+        let mut time = Duration::ZERO;
+        scroller.animate(time);
+
+        while scroller.is_animating() {
+            time += Duration::from_millis(16);
+            scroller.animate(time);
+            assert!(time < Duration::from_secs(10), "animation did not stop");
+        }
+
+        time
+    }
+
+    #[test]
+    fn kinetic() {
+        let mut scroller = Scroller::new();
+        scroller.set_viewport(BOUNDS, CONTENT);
+        assert_eq!(scroller.max_offset(), Point::new(0.0, 900.0));
+
+        // Flinging the content up keeps scrolling down and slows down.
+        scroller.pan(Phase::Began, Point::ZERO, Point::ZERO);
+        scroller.pan(Phase::Changed, Point::new(0.0, -50.0), Point::ZERO);
+        assert_eq!(scroller.offset(), Point::new(0.0, 50.0));
+        scroller.pan(
+            Phase::Ended,
+            Point::new(0.0, -50.0),
+            Point::new(0.0, -1000.0),
+        );
+
+        scroller.animate(Duration::ZERO);
+        scroller.animate(Duration::from_millis(100));
+        let offset = scroller.offset().y;
+        assert!(offset > 120.0 && offset < 150.0, "{}", offset);
+
+        settle(&mut scroller);
+        assert!(scroller.offset().y > 250.0 && scroller.offset().y < 300.0);
+        assert_eq!(scroller.scrollbar_opacity(), 0.0);
+
+        // Dragging past the top edge has resistance and bounces back.
+        scroller.scroll_to(Point::ZERO);
+        scroller.pan(Phase::Began, Point::ZERO, Point::ZERO);
+        scroller.pan(Phase::Changed, Point::new(0.0, 100.0), Point::ZERO);
+        let overscroll = scroller.offset().y;
+        assert!(overscroll < 0.0 && overscroll > -100.0);
+        assert!(scroller.is_overscrolled());
+        assert!(!scroller.is_animating());
+
+        scroller.pan(Phase::Ended, Point::new(0.0, 100.0), Point::ZERO);
+        settle(&mut scroller);
+        assert_eq!(scroller.offset(), Point::ZERO);
+
+        // Flinging past the bottom edge bounces back to the edge.
+        scroller.scroll_to(Point::new(0.0, 850.0));
+        scroller.pan(Phase::Began, Point::ZERO, Point::ZERO);
+        scroller.pan(Phase::Ended, Point::ZERO, Point::new(0.0, -3000.0));
+        settle(&mut scroller);
+        assert_eq!(scroller.offset(), Point::new(0.0, 900.0));
+    }
+
+    #[test]
+    fn scroll_input() {
+        let mut scroller = Scroller::new();
+        scroller.set_viewport(BOUNDS.translate(0.0, 50.0), CONTENT);
+
+        let event = |source, phase, delta, millis| ScrollEvent {
+            position: Point::ZERO,
+            delta: Point::new(0.0, delta),
+            source,
+            phase,
+            time: Duration::from_millis(millis),
+        };
+
+        // Scroll wheels do not scroll past the edges.
+        scroller.scroll(&event(ScrollSource::Wheel, Phase::Changed, -50.0, 0));
+        assert_eq!(scroller.offset(), Point::ZERO);
+        scroller.scroll(&event(ScrollSource::Wheel, Phase::Changed, 60.0, 0));
+        assert_eq!(scroller.offset(), Point::new(0.0, 60.0));
+
+        // A touchpad scroll continues after the fingers are lifted.
+        scroller.scroll(&event(ScrollSource::Touchpad, Phase::Began, 0.0, 100));
+        scroller.scroll(&event(ScrollSource::Touchpad, Phase::Changed, 10.0, 110));
+        scroller.scroll(&event(ScrollSource::Touchpad, Phase::Changed, 10.0, 120));
+        scroller.scroll(&event(ScrollSource::Touchpad, Phase::Ended, 0.0, 130));
+        assert_eq!(scroller.offset(), Point::new(0.0, 80.0));
+        assert!(scroller.is_animating());
+        scroller.animate(Duration::from_millis(130));
+        scroller.animate(Duration::from_millis(230));
+        assert!(scroller.offset().y > 150.0);
+
+        // Revealing an area scrolls the least distance, relative to the viewport on the surface.
+        scroller.scroll_to(Point::new(0.0, 100.0));
+        scroller.reveal(Rect::new(0.0, 110.0, 10.0, 10.0));
+        assert_eq!(scroller.offset(), Point::new(0.0, 100.0));
+        scroller.reveal(Rect::new(0.0, 400.0, 10.0, 10.0));
+        assert_eq!(scroller.offset(), Point::new(0.0, 360.0));
+        scroller.reveal(Rect::new(0.0, 0.0, 10.0, 10.0));
+        assert_eq!(scroller.offset(), Point::new(0.0, 310.0));
+    }
+
+    #[derive(Default)]
+    struct Data {
+        outer: Scroller,
+        inner: Scroller,
+    }
+
+    enum TestNode {
+        Viewport(ScrollViewport<Data>),
+        Scrollbar(Scrollbar),
+        Content(Rect),
+    }
+
+    impl From<ScrollViewport<Data>> for TestNode {
+        fn from(viewport: ScrollViewport<Data>) -> Self {
+            TestNode::Viewport(viewport)
+        }
+    }
+
+    impl From<Scrollbar> for TestNode {
+        fn from(scrollbar: Scrollbar) -> Self {
+            TestNode::Scrollbar(scrollbar)
+        }
+    }
+
+    impl ViewNode<Data> for TestNode {
+        fn rebuild(&mut self) {}
+
+        fn bounds(&self) -> Rect {
+            match self {
+                TestNode::Viewport(viewport) => viewport.bounds,
+                TestNode::Scrollbar(scrollbar) => scrollbar.bounds(),
+                TestNode::Content(bounds) => *bounds,
+            }
+        }
+
+        fn event(&mut self, cx: &mut EventContext<'_, Data>, event: &Event<'_>) -> bool {
+            match self {
+                TestNode::Viewport(viewport) => viewport.event(cx, event),
+                _ => false,
+            }
+        }
+
+        fn gestures(&self) -> Option<GestureArena> {
+            match self {
+                TestNode::Viewport(viewport) => Some(viewport.gestures()),
+                _ => None,
+            }
+        }
+    }
+
+    struct Content(Rect);
+
+    impl View<Data> for Content {
+        type State = TestNode;
+
+        fn build<'s, N>(&self, scene: &'s mut Scene<'_, N>) -> Node<'s, N>
+        where
+            N: From<Self::State>,
+        {
+            scene.build_node::<Self, Data>(TestNode::Content(self.0))
+        }
+    }
+
+    fn outer(data: &mut Data) -> &mut Scroller {
+        &mut data.outer
+    }
+
+    fn inner(data: &mut Data) -> &mut Scroller {
+        &mut data.inner
+    }
+
+    /// A vertically scrolling view containing a horizontally scrolling view.
+    fn nested(data: &Data, scene: &mut Scene<'_, TestNode>) {
+        let row = |origin| {
+            let bounds = Rect::from_origin_size(origin, Size::new(100.0, 50.0));
+            let content = Size::new(500.0, 50.0);
+
+            ScrollView::new(&data.inner, inner, bounds, content, move |origin| {
+                Content(Rect::from_origin_size(origin, content))
+            })
+        };
+
+        ScrollView::new(&data.outer, outer, BOUNDS, CONTENT, row).build(scene);
+    }
+
+    #[test]
+    fn nested_scroll_views() {
+        let mut harness = Harness::new(Data::default(), nested);
+
+        let wheel = |delta: Point| ScrollEvent {
+            position: Point::new(50.0, 25.0),
+            delta,
+            source: ScrollSource::Wheel,
+            phase: Phase::Changed,
+            time: Duration::ZERO,
+        };
+
+        // Vertical scrolling is not handled by the horizontal view, so the outer view scrolls.
+        assert!(harness
+            .context_mut()
+            .scroll(SurfaceId::MAIN, wheel(Point::new(0.0, 10.0))));
+        assert!(harness
+            .context_mut()
+            .scroll(SurfaceId::MAIN, wheel(Point::new(30.0, 0.0))));
+        assert_eq!(harness.data().outer.offset(), Point::new(0.0, 10.0));
+        assert_eq!(harness.data().inner.offset(), Point::new(30.0, 0.0));
+
+        // Both views show a scrollbar above the content.
+        harness.update();
+        let scrollbars = harness
+            .context()
+            .tree()
+            .iter()
+            .filter(|(_, node)| matches!(node, TestNode::Scrollbar(_)))
+            .count();
+        assert_eq!(scrollbars, 2);
+        assert_eq!(harness.find_all(Query::any()).len(), 5);
+
+        // Dragging the content scrolls the nearest view with recognizers.
+        harness
+            .press(Point::new(50.0, 20.0))
+            .advance(Duration::from_millis(16))
+            .move_to(Point::new(40.0, 20.0))
+            .advance(Duration::from_millis(16))
+            .move_to(Point::new(30.0, 20.0))
+            .release(Point::new(30.0, 20.0));
+        assert!(harness.data().inner.offset().x > 30.0);
+    }
+}
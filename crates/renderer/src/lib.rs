@@ -1,3 +1,16 @@
+//! Coordinates passed to a renderer are in physical pixels of the target surface. User interfaces are usually laid
+//! out in logical units, which are converted to physical pixels using the [scale factor](Viewport::scale_factor)
+//! of the surface and snapped to whole pixels before drawing. Renderers implementing [`RenderTransform`] apply the
+//! current transform to coordinates before they become physical pixels.
+//!
+//! Colors are red, green, blue and alpha components from 0 to 1 in the sRGB color space. Colors passed to a
+//! renderer are straight, meaning not premultiplied by their alpha, since that is how colors are usually
+//! specified. Renderers premultiply them first, and everything after that works with premultiplied colors:
+//! colors are interpolated premultiplied, which avoids dark fringes where translucent colors meet, the
+//! [blend modes](BlendMode) combine premultiplied colors, and images and the colors drawn to a surface are
+//! premultiplied by default. Colors are interpolated and blended in sRGB rather than in linear light, like most
+//! user interfaces and the web do, so gradients and translucent colors look the same across renderers.
+
 pub trait Renderer: Sized {
     type Error: std::error::Error;
     type Image: Image<Self>;
@@ -9,17 +22,258 @@ pub trait Image<R: Renderer> {
     fn height(&self) -> u32;
 }
 
-// TODO: Mesh/Triangles?
+/// The layout of the pixels of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Red, green, blue and alpha, one byte each.
+    Rgba8,
 
-pub struct Quad {
-    pub x: u32,
-    pub y: u32,
+    /// Blue, green, red and alpha, one byte each.
+    Bgra8,
+
+    /// A single byte of alpha, such as a mask for text.
+    ///
+    /// The image is drawn as white with the alpha of each pixel, so the tint determines the color.
+    A8,
+}
+
+impl Format {
+    /// Returns the size of a pixel in bytes.
+    pub fn bytes_per_pixel(&self) -> usize {
+        match self {
+            Format::Rgba8 | Format::Bgra8 => 4,
+            Format::A8 => 1,
+        }
+    }
+}
+
+/// How the alpha of colors relates to the other components.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Alpha {
+    /// The other components are multiplied by the alpha, as is usual for rendering.
+    #[default]
+    Premultiplied,
+
+    /// The other components are independent of the alpha, as is usual for image files such as PNG.
+    Straight,
+}
+
+/// Multiplies the red, green and blue components of a color by it's alpha.
+pub fn premultiply([r, g, b, a]: [f32; 4]) -> [f32; 4] {
+    [r * a, g * a, b * a, a]
+}
+
+/// Pixels of an image in memory.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageData<'a> {
+    pub width: u32,
+    pub height: u32,
+    /// The number of bytes from the start of one row to the start of the next row.
+    ///
+    /// This is at least the width multiplied by the size of a pixel, but rows may be padded.
+    pub stride: usize,
+    pub format: Format,
+    /// Whether the colors of the pixels are premultiplied. Renderers premultiply straight colors when creating
+    /// images.
+    pub alpha: Alpha,
+    pub data: &'a [u8],
+}
+
+impl<'a> ImageData<'a> {
+    /// Creates image data with premultiplied colors and rows which are not padded.
+    pub fn new(width: u32, height: u32, format: Format, data: &'a [u8]) -> Self {
+        Self {
+            width,
+            height,
+            stride: width as usize * format.bytes_per_pixel(),
+            format,
+            alpha: Alpha::Premultiplied,
+            data,
+        }
+    }
+
+    /// Returns the bytes of the pixels of a row, without padding.
+    ///
+    /// Returns [`None`] if the row is outside of the image or the data is too short.
+    pub fn row(&self, y: u32) -> Option<&'a [u8]> {
+        if y >= self.height {
+            return None;
+        }
+
+        let start = y as usize * self.stride;
+        self.data
+            .get(start..start + self.width as usize * self.format.bytes_per_pixel())
+    }
+
+    /// Returns whether the stride fits the rows and the data contains every row.
+    pub fn is_valid(&self) -> bool {
+        let row = self.width as usize * self.format.bytes_per_pixel();
+
+        self.stride >= row && (self.height == 0 || self.row(self.height - 1).is_some())
+    }
+}
+
+/// Renderers which can create images from pixels in memory.
+pub trait CreateImage: Renderer {
+    /// Creates an image from pixels in memory.
+    ///
+    /// The image is destroyed when dropped.
+    fn create_image(&mut self, data: ImageData<'_>) -> Result<Self::Image, Self::Error>;
+
+    /// Replaces an area of an image with the top left corner at `x` and `y`.
+    ///
+    /// The data must have the format the image was created with.
+    fn update_image(
+        &mut self,
+        image: &mut Self::Image,
+        x: u32,
+        y: u32,
+        data: ImageData<'_>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// The area of a surface being drawn to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    /// The width of the surface in physical pixels.
     pub width: u32,
+    /// The height of the surface in physical pixels.
     pub height: u32,
-    // TODO: Radius
-    // TODO: Fade
-    // TODO: Thickness
-    // TODO: Gradient instead of color?
+    /// The number of physical pixels per logical unit.
+    ///
+    /// This may be fractional, such as 1.25 or 1.5.
+    pub scale_factor: f32,
+}
+
+impl Viewport {
+    /// Returns the size of the surface in logical units.
+    pub fn logical_size(&self) -> (f32, f32) {
+        (
+            self.width as f32 / self.scale_factor,
+            self.height as f32 / self.scale_factor,
+        )
+    }
+}
+
+/// A rectangle, in physical pixels.
+///
+/// The rectangle may have rounded corners and a border, and may be filled with a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quad {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub fill: Fill,
+    /// The radii of the corners.
+    ///
+    /// Radii larger than half of the width or height are reduced to fit.
+    pub radii: CornerRadii,
+    /// The border drawn inside of the edges of the quad.
+    pub border: Option<Border>,
+    /// The width of the edge over which the quad fades out, which antialiases the edges.
+    ///
+    /// A softness of 0 produces hard edges, and larger values blur the edges.
+    pub softness: f32,
+}
+
+impl Quad {
+    /// The softness which antialiases the edges of a quad.
+    pub const ANTIALIASED: f32 = 1.0;
+
+    /// Creates a quad filled with a color, with square corners and no border.
+    pub fn new(x: f32, y: f32, width: f32, height: f32, color: [f32; 4]) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            fill: Fill::Solid(color),
+            radii: CornerRadii::ZERO,
+            border: None,
+            softness: Self::ANTIALIASED,
+        }
+    }
+
+    /// Returns whether the quad is a solid rectangle with square corners and no border.
+    ///
+    /// Such a quad may be clipped by shrinking it.
+    pub fn is_rectangle(&self) -> bool {
+        matches!(self.fill, Fill::Solid(_))
+            && self.radii == CornerRadii::ZERO
+            && self.border.is_none()
+    }
+}
+
+/// How the inside of a [`Quad`] is colored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fill {
+    Solid([f32; 4]),
+    LinearGradient(LinearGradient),
+    RadialGradient(RadialGradient),
+}
+
+/// A gradient between two colors along a line.
+///
+/// Positions are fractions of the size of the quad, where `[0.0, 0.0]` is the top left corner and `[1.0, 1.0]`
+/// is the bottom right corner. The colors extend past the ends of the line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearGradient {
+    pub start: [f32; 2],
+    pub end: [f32; 2],
+    pub start_color: [f32; 4],
+    pub end_color: [f32; 4],
+}
+
+/// A gradient between two colors from the center of an ellipse to it's edge.
+///
+/// The center and radii are fractions of the size of the quad, so a `center` of `[0.5, 0.5]` and `radius` of
+/// `[0.5, 0.5]` reaches the middle of each edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RadialGradient {
+    pub center: [f32; 2],
+    pub radius: [f32; 2],
+    pub inner_color: [f32; 4],
+    pub outer_color: [f32; 4],
+}
+
+/// The radius of each corner of a [`Quad`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    pub const ZERO: Self = Self::uniform(0.0);
+
+    /// Creates radii where every corner has the same radius.
+    pub const fn uniform(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    /// Multiplies every radius by a scale factor.
+    pub fn scale(&self, factor: f32) -> Self {
+        Self {
+            top_left: self.top_left * factor,
+            top_right: self.top_right * factor,
+            bottom_right: self.bottom_right * factor,
+            bottom_left: self.bottom_left * factor,
+        }
+    }
+}
+
+/// A border drawn inside of the edges of a [`Quad`], following the rounded corners.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Border {
+    pub width: f32,
     pub color: [f32; 4],
 }
 
@@ -28,4 +282,700 @@ pub trait RenderQuad: Sized {
     fn draw_quad(&mut self, quad: Quad);
 }
 
-// TODO: DrawImage
+/// Triangles with a color at each vertex, in physical pixels.
+///
+/// Every three indices form a triangle. The color of a triangle is interpolated between the colors of it's
+/// vertices.
+///
+/// A mesh is only drawn if it is [valid](Mesh::is_valid).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 2]>,
+    /// The color of each vertex.
+    pub colors: Vec<[f32; 4]>,
+    /// The position within an image of each vertex, as fractions of the size of the image, used when the mesh is
+    /// drawn with an image.
+    pub uvs: Option<Vec<[f32; 2]>>,
+    pub indices: Indices,
+}
+
+impl Mesh {
+    /// Returns the number of vertices.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Adds a vertex, returning it's index.
+    pub fn push_vertex(&mut self, position: [f32; 2], color: [f32; 4]) -> u32 {
+        let index = self.positions.len() as u32;
+        self.positions.push(position);
+        self.colors.push(color);
+
+        // Widen the indices once they no longer fit in 16 bits.
+        if let Indices::U16(indices) = &self.indices {
+            if index > u16::MAX as u32 {
+                self.indices = Indices::U32(indices.iter().map(|&index| index as u32).collect());
+            }
+        }
+
+        index
+    }
+
+    /// Adds a vertex with a position within an image, returning it's index.
+    ///
+    /// Either every vertex of a mesh has a position within an image or none has.
+    pub fn push_textured_vertex(
+        &mut self,
+        position: [f32; 2],
+        color: [f32; 4],
+        uv: [f32; 2],
+    ) -> u32 {
+        self.uvs.get_or_insert_with(Vec::new).push(uv);
+        self.push_vertex(position, color)
+    }
+
+    /// Adds a triangle between three vertices.
+    pub fn push_triangle(&mut self, a: u32, b: u32, c: u32) {
+        match &mut self.indices {
+            Indices::U16(indices) => indices.extend([a as u16, b as u16, c as u16]),
+            Indices::U32(indices) => indices.extend([a, b, c]),
+        }
+    }
+
+    /// Returns whether every vertex has a color and, if the mesh has positions within an image, a position within
+    /// the image, the indices form whole triangles and every index refers to a vertex.
+    pub fn is_valid(&self) -> bool {
+        let len = self.positions.len();
+        let in_range = match &self.indices {
+            Indices::U16(indices) => indices.iter().all(|&index| (index as usize) < len),
+            Indices::U32(indices) => indices.iter().all(|&index| (index as usize) < len),
+        };
+
+        let uvs = self.uvs.as_ref().is_none_or(|uvs| uvs.len() == len);
+
+        self.colors.len() == len && uvs && self.indices.len().is_multiple_of(3) && in_range
+    }
+
+    /// Returns the area containing every vertex as the left, top, right and bottom edges.
+    pub fn bounds(&self) -> Option<[f32; 4]> {
+        let (first, rest) = self.positions.split_first()?;
+
+        Some(rest.iter().fold(
+            [first[0], first[1], first[0], first[1]],
+            |[left, top, right, bottom], &[x, y]| {
+                [left.min(x), top.min(y), right.max(x), bottom.max(y)]
+            },
+        ))
+    }
+}
+
+/// The indices of the vertices of the triangles of a [`Mesh`].
+///
+/// 16 bit indices use less memory and are supported by every backend, but can only address 65536 vertices.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Indices {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Indices {
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        match self {
+            Indices::U16(indices) => indices.len(),
+            Indices::U32(indices) => indices.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an index as 32 bits.
+    pub fn get(&self, index: usize) -> Option<u32> {
+        match self {
+            Indices::U16(indices) => indices.get(index).map(|&index| index as u32),
+            Indices::U32(indices) => indices.get(index).copied(),
+        }
+    }
+}
+
+impl Default for Indices {
+    fn default() -> Self {
+        Indices::U16(Vec::new())
+    }
+}
+
+pub trait RenderMesh: Sized {
+    /// Draws a mesh.
+    ///
+    /// Meshes which are not [valid](Mesh::is_valid) are not drawn, and the renderer reports an error if it is able
+    /// to. The positions within an image are ignored.
+    fn draw_mesh(&mut self, mesh: Mesh);
+}
+
+/// Renderers which draw meshes with an image.
+pub trait RenderTexturedMesh: Renderer {
+    /// Draws a mesh, multiplying the color of each vertex with the image at the vertex's position within the
+    /// image. Positions outside of the image sample it's closest edge.
+    ///
+    /// Meshes which are not [valid](Mesh::is_valid) or have no positions within an image are not drawn, and the
+    /// renderer reports an error if it is able to. As with [`RenderImage::draw_image`], the image must not be
+    /// destroyed until the commands are submitted.
+    fn draw_textured_mesh(&mut self, mesh: Mesh, image: &Self::Image);
+}
+
+/// A segment of a [`Path`]. Points are in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// Begins a new sub-path at a point.
+    MoveTo([f32; 2]),
+
+    LineTo([f32; 2]),
+
+    /// A quadratic bézier curve.
+    QuadTo {
+        control: [f32; 2],
+        to: [f32; 2],
+    },
+
+    /// A cubic bézier curve.
+    CubicTo {
+        control1: [f32; 2],
+        control2: [f32; 2],
+        to: [f32; 2],
+    },
+
+    /// An elliptic arc around a center, beginning at `start_angle` and sweeping `sweep_angle` radians clockwise.
+    ///
+    /// A line connects the current point to the start of the arc.
+    Arc {
+        center: [f32; 2],
+        radii: [f32; 2],
+        start_angle: f32,
+        sweep_angle: f32,
+    },
+
+    /// Closes the sub-path with a line to it's start.
+    Close,
+}
+
+/// A shape made of lines and curves, which may be filled or stroked.
+///
+/// A path consists of sub-paths, each begun with [`Path::move_to`]. Segments added before the first sub-path is
+/// begun start at the origin, except for arcs which start at the start of the arc.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        self.segments.push(Segment::MoveTo([x, y]));
+    }
+
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        self.segments.push(Segment::LineTo([x, y]));
+    }
+
+    pub fn quad_to(&mut self, control: [f32; 2], to: [f32; 2]) {
+        self.segments.push(Segment::QuadTo { control, to });
+    }
+
+    pub fn cubic_to(&mut self, control1: [f32; 2], control2: [f32; 2], to: [f32; 2]) {
+        self.segments.push(Segment::CubicTo {
+            control1,
+            control2,
+            to,
+        });
+    }
+
+    /// Adds an elliptic arc, see [`Segment::Arc`].
+    pub fn arc(&mut self, center: [f32; 2], radii: [f32; 2], start_angle: f32, sweep_angle: f32) {
+        self.segments.push(Segment::Arc {
+            center,
+            radii,
+            start_angle,
+            sweep_angle,
+        });
+    }
+
+    pub fn close(&mut self) {
+        self.segments.push(Segment::Close);
+    }
+}
+
+/// How the inside of a path which intersects itself is determined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// Points are inside if the path winds around them more often in one direction than the other.
+    #[default]
+    NonZero,
+
+    /// Points are inside if a line from the point crosses the path an odd number of times.
+    EvenOdd,
+}
+
+/// The shape of the corners where two segments of a stroke meet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Extends the edges of the segments until they meet, up to the [miter limit](Stroke::miter_limit).
+    #[default]
+    Miter,
+
+    Round,
+
+    /// Cuts the corner off.
+    Bevel,
+}
+
+/// The shape of the ends of a stroke which is not closed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    /// Ends the stroke at the end of the path.
+    #[default]
+    Butt,
+
+    Round,
+
+    /// Extends the stroke past the end of the path by half of it's width.
+    Square,
+}
+
+/// How the outline of a path is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    /// The width of the stroke in physical pixels.
+    pub width: f32,
+    pub join: LineJoin,
+    /// How far miter joins may extend relative to the width before they are beveled instead.
+    pub miter_limit: f32,
+    pub cap: LineCap,
+    /// The lengths of alternating dashes and gaps in physical pixels, starting with a dash.
+    ///
+    /// An empty pattern draws a solid stroke. A pattern with an odd number of lengths is repeated twice.
+    pub dashes: Vec<f32>,
+    /// How far into the dash pattern each sub-path begins.
+    pub dash_offset: f32,
+}
+
+impl Stroke {
+    /// A solid stroke with miter joins and butt caps.
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            cap: LineCap::Butt,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+pub trait RenderPath: Sized {
+    /// Fills the inside of a path with a color.
+    fn fill_path(&mut self, path: &Path, rule: FillRule, color: [f32; 4]);
+
+    /// Draws the outline of a path with a color.
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke, color: [f32; 4]);
+}
+
+/// How an image is sampled when drawn at a different size than the image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Blends between neighbouring pixels, which is smooth but blurs scaled images.
+    #[default]
+    Linear,
+
+    /// Uses the nearest pixel, which keeps pixel art sharp.
+    Nearest,
+}
+
+/// Where and how an image is drawn by [`RenderImage::draw_image`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawImage {
+    /// The area the image is drawn to, in physical pixels, as the x, y, width and height.
+    pub destination: [f32; 4],
+    /// The area of the image which is drawn, as the x, y, width and height in fractions of the size of the image.
+    ///
+    /// `[0.0, 0.0, 1.0, 1.0]` draws the whole image.
+    pub source: [f32; 4],
+    /// A color each pixel of the image is multiplied with.
+    pub tint: [f32; 4],
+    /// The opacity of the image, from 0 for invisible to 1 for opaque.
+    pub opacity: f32,
+    pub filter: Filter,
+}
+
+impl DrawImage {
+    /// Draws the whole image into an area without tint.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            destination: [x, y, width, height],
+            source: [0.0, 0.0, 1.0, 1.0],
+            tint: [1.0; 4],
+            opacity: 1.0,
+            filter: Filter::Linear,
+        }
+    }
+}
+
+pub trait RenderImage: Renderer {
+    /// Draws an image.
+    ///
+    /// Renderers which record commands may sample the image later, so the image must not be destroyed until the
+    /// commands are submitted.
+    fn draw_image(&mut self, image: &Self::Image, draw: DrawImage);
+}
+
+/// A 2D affine transformation, such as a translation, scale or rotation.
+///
+/// A point `(x, y)` is mapped to `x_axis * x + y_axis * y + translation`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub x_axis: [f32; 2],
+    pub y_axis: [f32; 2],
+    pub translation: [f32; 2],
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        x_axis: [1.0, 0.0],
+        y_axis: [0.0, 1.0],
+        translation: [0.0, 0.0],
+    };
+
+    pub const fn translation(x: f32, y: f32) -> Self {
+        Self {
+            translation: [x, y],
+            ..Self::IDENTITY
+        }
+    }
+
+    pub const fn scale(x: f32, y: f32) -> Self {
+        Self {
+            x_axis: [x, 0.0],
+            y_axis: [0.0, y],
+            translation: [0.0, 0.0],
+        }
+    }
+
+    /// Rotates clockwise by an angle in radians, since the y axis points down.
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Self {
+            x_axis: [cos, sin],
+            y_axis: [-sin, cos],
+            translation: [0.0, 0.0],
+        }
+    }
+
+    /// Returns a transform which applies this transform followed by `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        Self {
+            x_axis: next.apply_vector(self.x_axis),
+            y_axis: next.apply_vector(self.y_axis),
+            translation: next.apply(self.translation),
+        }
+    }
+
+    /// Transforms a point.
+    pub fn apply(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        let [vx, vy] = self.apply_vector([x, y]);
+        [vx + self.translation[0], vy + self.translation[1]]
+    }
+
+    /// Transforms a direction, which is not affected by the translation.
+    pub fn apply_vector(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        [
+            self.x_axis[0] * x + self.y_axis[0] * y,
+            self.x_axis[1] * x + self.y_axis[1] * y,
+        ]
+    }
+
+    /// Returns the transform reversing this transform.
+    ///
+    /// Returns [`None`] if the transform collapses areas to a line or point, such as a scale of zero.
+    pub fn inverse(&self) -> Option<Self> {
+        let [a, b] = self.x_axis;
+        let [c, d] = self.y_axis;
+        let determinant = a * d - b * c;
+
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        let inverse = Self {
+            x_axis: [d / determinant, -b / determinant],
+            y_axis: [-c / determinant, a / determinant],
+            translation: [0.0, 0.0],
+        };
+        let [x, y] = inverse.apply_vector(self.translation);
+
+        Some(Self {
+            translation: [-x, -y],
+            ..inverse
+        })
+    }
+
+    /// Returns whether the edges of rectangles stay parallel to the axes, so the transform has no rotation or skew.
+    pub fn is_axis_aligned(&self) -> bool {
+        self.x_axis[1] == 0.0 && self.y_axis[0] == 0.0
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Renderers which transform what is drawn, such as to scroll or animate content.
+///
+/// Transforms are kept in a stack. Coordinates of anything drawn are transformed by every transform on the stack,
+/// starting with the transform pushed last, before they become physical pixels.
+pub trait RenderTransform: Sized {
+    fn push_transform(&mut self, transform: Transform);
+
+    /// Removes the transform pushed last.
+    fn pop_transform(&mut self);
+}
+
+/// Renderers which clip what is drawn to an area.
+///
+/// Clips are kept in a stack, and anything drawn is clipped to every clip on the stack. Clips are transformed by
+/// the transform at the time they are pushed, and are not affected by transforms pushed afterwards.
+///
+/// Rectangles which remain rectangles in physical pixels are always clipped to exactly. Renderers may only clip
+/// exactly to a limited number of the innermost of the other clips, those with rounded corners or rotated by their
+/// transform, and clip to the rectangles containing the outer ones instead. Nesting more such clips than that may
+/// then draw outside of the outermost clips, such as in their rounded corners.
+pub trait RenderClip: Sized {
+    /// Clips to a rectangle with rounded corners, as the x, y, width and height.
+    fn push_clip(&mut self, rect: [f32; 4], radii: CornerRadii);
+
+    /// Removes the clip pushed last.
+    fn pop_clip(&mut self);
+}
+
+/// How colors are combined with the colors drawn before.
+///
+/// The blend mode is set with [`RenderBlend::set_blend_mode`] or when pushing a layer. Blending works with
+/// premultiplied colors, which renderers convert the straight colors passed to them to. Below, `src` is the
+/// premultiplied color being drawn with the alpha `src.a`, and `dst` is the premultiplied color drawn before.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Draws over the colors drawn before, which show through where the colors are translucent.
+    /// `src + dst * (1 - src.a)`.
+    #[default]
+    Normal,
+
+    /// Multiplies the colors, which darkens.
+    /// `src * dst + dst * (1 - src.a)`.
+    ///
+    /// The alpha of the colors drawn before is not taken into account, so this is only exact over opaque colors.
+    Multiply,
+
+    /// Multiplies the inverse of the colors, which lightens.
+    /// `src + dst * (1 - src)`.
+    Screen,
+
+    /// Adds the colors.
+    /// `src + dst`.
+    Additive,
+
+    /// Replaces the colors drawn before, including their alpha.
+    /// `src`.
+    SourceCopy,
+}
+
+/// Renderers which draw with blend modes other than [`BlendMode::Normal`].
+pub trait RenderBlend: Sized {
+    /// Sets the blend mode of the commands drawn afterwards.
+    fn set_blend_mode(&mut self, blend_mode: BlendMode);
+}
+
+/// Renderers which draw groups of commands as a whole.
+///
+/// Anything drawn between pushing and popping a layer is drawn into the layer, which is then drawn with an
+/// opacity and blend mode. Fading a layer fades the group as one, so overlapping content does not show through
+/// itself as it would when fading each command.
+pub trait RenderLayer: Sized {
+    /// Begins drawing into a layer drawn with an opacity, from 0 for invisible to 1 for opaque.
+    fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode);
+
+    /// Ends the layer pushed last and draws it.
+    fn pop_layer(&mut self);
+}
+
+/// The shadow of a rectangle with rounded corners, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxShadow {
+    /// The rectangle casting the shadow, as the x, y, width and height.
+    pub rect: [f32; 4],
+    pub radii: CornerRadii,
+    /// How far the shadow is moved from the rectangle.
+    pub offset: [f32; 2],
+    /// How far the edge of the shadow is blurred, which is twice the standard deviation of the blur.
+    pub blur_radius: f32,
+    /// How far the shadow is grown beyond the rectangle before it is blurred, or shrunk if negative.
+    pub spread: f32,
+    pub color: [f32; 4],
+    /// Whether the shadow is cast inside of the rectangle, as if the rectangle was a hole, rather than around it.
+    ///
+    /// Shadows which are not inset are not drawn below the rectangle, so they may be drawn below translucent
+    /// rectangles.
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    /// Creates a shadow directly below a rectangle without blur.
+    pub fn new(x: f32, y: f32, width: f32, height: f32, color: [f32; 4]) -> Self {
+        Self {
+            rect: [x, y, width, height],
+            radii: CornerRadii::ZERO,
+            offset: [0.0, 0.0],
+            blur_radius: 0.0,
+            spread: 0.0,
+            color,
+            inset: false,
+        }
+    }
+}
+
+pub trait RenderShadow: Sized {
+    fn draw_shadow(&mut self, shadow: BoxShadow);
+}
+
+/// The area behind a layer in which what was drawn before the layer is blurred, such as behind a translucent panel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backdrop {
+    /// The blurred area as the x, y, width and height, which is transformed and clipped like a clip.
+    pub rect: [f32; 4],
+    pub radii: CornerRadii,
+    /// How far what was drawn before is blurred, which is twice the standard deviation of the blur.
+    pub blur_radius: f32,
+}
+
+/// Renderers which blur layers.
+pub trait RenderBlur: RenderLayer {
+    /// Begins drawing into a layer which is blurred with a Gaussian blur before it is drawn.
+    ///
+    /// The layer is ended with [`RenderLayer::pop_layer`]. The blur radius is in physical pixels and is twice the
+    /// standard deviation of the blur.
+    fn push_blurred_layer(&mut self, blur_radius: f32, opacity: f32, blend_mode: BlendMode);
+
+    /// Begins drawing into a layer which is drawn over a blurred copy of what was drawn before it within the area
+    /// of the backdrop.
+    ///
+    /// The layer is ended with [`RenderLayer::pop_layer`]. The backdrop is blurred when the layer is popped, even
+    /// if nothing was drawn into the layer, and is drawn with [`BlendMode::Normal`] regardless of the opacity
+    /// and blend mode of the layer.
+    fn push_backdrop_layer(&mut self, backdrop: Backdrop, opacity: f32, blend_mode: BlendMode);
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::FRAC_PI_2;
+
+    use super::{Indices, Mesh, Transform};
+
+    fn assert_near(a: [f32; 2], b: [f32; 2]) {
+        assert!(
+            (a[0] - b[0]).abs() < 1e-4 && (a[1] - b[1]).abs() < 1e-4,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn transform_order() {
+        let translate = Transform::translation(10.0, 0.0);
+        let scale = Transform::scale(2.0, 3.0);
+
+        // The transform `then` is called on is applied first.
+        assert_eq!(translate.then(&scale).apply([1.0, 1.0]), [22.0, 3.0]);
+        assert_eq!(scale.then(&translate).apply([1.0, 1.0]), [12.0, 3.0]);
+
+        // Directions are not translated.
+        assert_eq!(translate.then(&scale).apply_vector([1.0, 1.0]), [2.0, 3.0]);
+
+        // Rotating clockwise turns the x axis into the y axis.
+        let rotate = Transform::rotation(FRAC_PI_2);
+        assert_near(rotate.then(&translate).apply([1.0, 0.0]), [10.0, 1.0]);
+        assert!(!rotate.is_axis_aligned());
+        assert!(translate.then(&scale).is_axis_aligned());
+    }
+
+    #[test]
+    fn transform_inverse() {
+        let transform = Transform::rotation(FRAC_PI_2)
+            .then(&Transform::translation(5.0, -3.0))
+            .then(&Transform::scale(2.0, 4.0));
+        let inverse = transform.inverse().unwrap();
+
+        for point in [[0.0, 0.0], [1.0, 2.0], [-7.5, 3.25]] {
+            assert_near(inverse.apply(transform.apply(point)), point);
+            assert_near(transform.apply(inverse.apply(point)), point);
+        }
+
+        // Collapsing areas to a line can not be reversed.
+        assert_eq!(Transform::scale(0.0, 1.0).inverse(), None);
+        assert_eq!(Transform::IDENTITY.inverse(), Some(Transform::IDENTITY));
+    }
+
+    #[test]
+    fn mesh_validity() {
+        let mut mesh = Mesh::default();
+        let a = mesh.push_vertex([0.0, 0.0], [1.0; 4]);
+        let b = mesh.push_vertex([10.0, 0.0], [1.0; 4]);
+        let c = mesh.push_vertex([0.0, 10.0], [1.0; 4]);
+        mesh.push_triangle(a, b, c);
+        assert!(mesh.is_valid());
+
+        // A vertex without a color.
+        let mut invalid = mesh.clone();
+        invalid.positions.push([10.0, 10.0]);
+        assert!(!invalid.is_valid());
+
+        // An incomplete triangle.
+        let mut invalid = mesh.clone();
+        invalid.indices = Indices::U16(vec![0, 1]);
+        assert!(!invalid.is_valid());
+
+        // An index past the vertices.
+        let mut invalid = mesh.clone();
+        invalid.indices = Indices::U32(vec![0, 1, 3]);
+        assert!(!invalid.is_valid());
+
+        // A vertex without a position within the image.
+        let mut textured = Mesh::default();
+        textured.push_textured_vertex([0.0, 0.0], [1.0; 4], [0.0, 0.0]);
+        textured.push_textured_vertex([10.0, 0.0], [1.0; 4], [1.0, 0.0]);
+        textured.push_textured_vertex([0.0, 10.0], [1.0; 4], [0.0, 1.0]);
+        textured.indices = mesh.indices;
+        assert!(textured.is_valid());
+
+        textured.push_vertex([10.0, 10.0], [1.0; 4]);
+        assert!(!textured.is_valid());
+    }
+}
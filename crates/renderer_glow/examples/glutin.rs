@@ -12,6 +12,11 @@ use raw_window_handle::{
     HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
 };
 
+use renderer::{
+    Backdrop, BlendMode, Border, BoxShadow, CornerRadii, Fill, FillRule, LineCap, LineJoin,
+    LinearGradient, Mesh, Path, Quad, RadialGradient, RenderBlur, RenderClip, RenderLayer,
+    RenderMesh, RenderPath, RenderQuad, RenderShadow, RenderTransform, Stroke, Transform, Viewport,
+};
 use renderer_glow::GlowRenderer;
 use winit::{
     dpi::LogicalSize,
@@ -81,19 +86,130 @@ fn main() {
                 // window.request_redraw();
 
                 let context = renderer.context();
+                let size = window.inner_size();
+
                 unsafe {
-                    let size = window.inner_size();
                     // Set viewport
                     context.viewport(0, 0, size.width as i32, size.height as i32);
                     // context.clear_color(0., 0.3, 0.3, 0.8);
                     context.clear_color(1.0, 1.0, 1.0, 1.0);
                     context.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
-                    context.enable(glow::BLEND);
-                    context.blend_func(glow::ONE, glow::ONE_MINUS_SRC_ALPHA);
                     // context.enable(glow::DEPTH_TEST);
-                    renderer.temp_draw(size.width, size.height);
                 }
 
+                let viewport = Viewport {
+                    width: size.width,
+                    height: size.height,
+                    scale_factor: window.scale_factor() as f32,
+                };
+                let mut encoder = renderer.create_encoder(viewport).unwrap();
+                encoder.draw_mesh(demo_mesh(viewport.scale_factor));
+
+                // Fade the quads as a group, so overlapping quads do not show through each other.
+                encoder.push_layer(0.8, BlendMode::Normal);
+
+                for quad in demo_quads(viewport.scale_factor) {
+                    encoder.draw_quad(quad);
+                }
+
+                encoder.pop_layer();
+
+                // Tilt the star around it's center and cut off it's points with a rounded clip.
+                let [x, y] = [
+                    1000.0 * viewport.scale_factor,
+                    300.0 * viewport.scale_factor,
+                ];
+                let size = 220.0 * viewport.scale_factor;
+                encoder.push_clip(
+                    [x - size / 2.0, y - size / 2.0, size, size],
+                    CornerRadii::uniform(40.0 * viewport.scale_factor),
+                );
+                encoder.push_transform(
+                    Transform::translation(-x, -y)
+                        .then(&Transform::rotation(0.25))
+                        .then(&Transform::translation(x, y)),
+                );
+
+                let star = demo_star(viewport.scale_factor);
+                encoder.fill_path(&star, FillRule::EvenOdd, [1.0, 0.8, 0.0, 1.0]);
+
+                let mut stroke = Stroke::new(4.0 * viewport.scale_factor);
+                stroke.join = LineJoin::Round;
+                stroke.cap = LineCap::Round;
+                stroke.dashes = vec![12.0 * viewport.scale_factor, 8.0 * viewport.scale_factor];
+                encoder.stroke_path(&star, &stroke, [0.2, 0.2, 0.2, 1.0]);
+
+                encoder.pop_transform();
+                encoder.pop_clip();
+
+                // A card floating above blurred shapes, with a drop shadow and an inner shadow.
+                encoder.push_blurred_layer(16.0 * viewport.scale_factor, 1.0, BlendMode::Normal);
+
+                for quad in [
+                    Quad::new(900.0, 600.0, 150.0, 150.0, [0.9, 0.3, 0.2, 1.0]),
+                    Quad::new(1050.0, 650.0, 150.0, 150.0, [0.2, 0.6, 0.9, 1.0]),
+                ] {
+                    encoder.draw_quad(Quad {
+                        x: quad.x * viewport.scale_factor,
+                        y: quad.y * viewport.scale_factor,
+                        width: quad.width * viewport.scale_factor,
+                        height: quad.height * viewport.scale_factor,
+                        radii: CornerRadii::uniform(75.0 * viewport.scale_factor),
+                        ..quad
+                    });
+                }
+
+                encoder.pop_layer();
+
+                let card = Quad {
+                    radii: CornerRadii::uniform(16.0),
+                    ..Quad::new(950.0, 650.0, 200.0, 120.0, [1.0, 1.0, 1.0, 0.6])
+                };
+                let card = Quad {
+                    x: card.x * viewport.scale_factor,
+                    y: card.y * viewport.scale_factor,
+                    width: card.width * viewport.scale_factor,
+                    height: card.height * viewport.scale_factor,
+                    radii: card.radii.scale(viewport.scale_factor),
+                    ..card
+                };
+                let shadow = BoxShadow {
+                    radii: card.radii,
+                    offset: [0.0, 8.0 * viewport.scale_factor],
+                    blur_radius: 24.0 * viewport.scale_factor,
+                    ..BoxShadow::new(
+                        card.x,
+                        card.y,
+                        card.width,
+                        card.height,
+                        [0.0, 0.0, 0.0, 0.4],
+                    )
+                };
+
+                encoder.draw_shadow(shadow);
+
+                // Frost the shapes behind the translucent card.
+                encoder.push_backdrop_layer(
+                    Backdrop {
+                        rect: [card.x, card.y, card.width, card.height],
+                        radii: card.radii,
+                        blur_radius: 12.0 * viewport.scale_factor,
+                    },
+                    1.0,
+                    BlendMode::Normal,
+                );
+                encoder.draw_quad(card);
+                encoder.pop_layer();
+                encoder.draw_shadow(BoxShadow {
+                    offset: [0.0, 2.0 * viewport.scale_factor],
+                    blur_radius: 4.0 * viewport.scale_factor,
+                    color: [1.0, 1.0, 1.0, 0.8],
+                    inset: true,
+                    ..shadow
+                });
+
+                encoder.submit().unwrap();
+
                 surface.swap_buffers(&gl_context).unwrap();
             }
             _ => (),
@@ -101,6 +217,104 @@ fn main() {
     });
 }
 
+/// Returns a triangle blending between the colors of it's corners, scaled to physical pixels.
+fn demo_mesh(scale_factor: f32) -> Mesh {
+    let mut mesh = Mesh::default();
+    let vertex = |x: f32, y: f32| [x * scale_factor, y * scale_factor];
+
+    let a = mesh.push_vertex(vertex(100.0, 100.0), [1.0, 0.0, 0.0, 1.0]);
+    let b = mesh.push_vertex(vertex(800.0, 800.0), [0.0, 1.0, 0.0, 1.0]);
+    let c = mesh.push_vertex(vertex(100.0, 800.0), [0.0, 0.0, 1.0, 1.0]);
+    mesh.push_triangle(a, b, c);
+
+    mesh
+}
+
+/// Returns a five pointed star, which has a hole in the middle when filled with the even-odd rule.
+fn demo_star(scale_factor: f32) -> Path {
+    let mut path = Path::new();
+
+    for point in 0..5 {
+        // Connect every second point of a pentagon.
+        let angle = (point * 2) as f32 * std::f32::consts::TAU / 5.0 - std::f32::consts::FRAC_PI_2;
+        let x = (1000.0 + angle.cos() * 150.0) * scale_factor;
+        let y = (300.0 + angle.sin() * 150.0) * scale_factor;
+
+        match point {
+            0 => path.move_to(x, y),
+            _ => path.line_to(x, y),
+        }
+    }
+
+    path.close();
+    path
+}
+
+/// Returns the quads of the demo laid out in logical units, scaled to physical pixels.
+fn demo_quads(scale_factor: f32) -> Vec<Quad> {
+    let quads = [
+        Quad {
+            radii: CornerRadii::uniform(40.0),
+            border: Some(Border {
+                width: 8.0,
+                color: [0.9, 0.9, 0.9, 1.0],
+            }),
+            ..Quad::new(100.0, 100.0, 400.0, 400.0, [0.3, 0.2, 0.9, 1.0])
+        },
+        Quad {
+            fill: Fill::LinearGradient(LinearGradient {
+                start: [0.0, 0.0],
+                end: [1.0, 0.0],
+                start_color: [0.2, 0.2, 0.2, 1.0],
+                end_color: [0.6, 0.6, 0.6, 1.0],
+            }),
+            ..Quad::new(500.0, 400.0, 400.0, 100.0, [0.0; 4])
+        },
+        Quad {
+            radii: CornerRadii {
+                top_left: 50.0,
+                ..CornerRadii::ZERO
+            },
+            ..Quad::new(500.0, 600.0, 100.0, 100.0, [0.7, 0.2, 0.9, 1.0])
+        },
+        Quad {
+            fill: Fill::RadialGradient(RadialGradient {
+                center: [0.5, 0.5],
+                radius: [0.5, 0.5],
+                inner_color: [1.0, 1.0, 1.0, 1.0],
+                outer_color: [0.7, 0.2, 0.9, 1.0],
+            }),
+            radii: CornerRadii::uniform(50.0),
+            ..Quad::new(600.0, 600.0, 100.0, 100.0, [0.0; 4])
+        },
+        Quad::new(500.0, 500.0, 200.0, 100.0, [0.7, 0.2, 0.1, 1.0]),
+        Quad::new(700.0, 500.0, 100.0, 200.0, [0.7, 0.1, 0.6, 1.0]),
+    ];
+
+    let scale = |value: f32| (value * scale_factor).round();
+
+    quads
+        .into_iter()
+        .map(|quad| {
+            // Snap the edges so adjacent quads remain adjacent at fractional scale factors.
+            let (x, y) = (scale(quad.x), scale(quad.y));
+
+            Quad {
+                x,
+                y,
+                width: scale(quad.x + quad.width) - x,
+                height: scale(quad.y + quad.height) - y,
+                radii: quad.radii.scale(scale_factor),
+                border: quad.border.map(|border| Border {
+                    width: border.width * scale_factor,
+                    ..border
+                }),
+                ..quad
+            }
+        })
+        .collect()
+}
+
 fn create_display(raw_display: RawDisplayHandle, _raw_window_handle: RawWindowHandle) -> Display {
     unsafe { Display::from_raw(raw_display, DisplayApiPreference::Egl) }.unwrap()
 }
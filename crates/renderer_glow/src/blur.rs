@@ -0,0 +1,133 @@
+use glow::HasContext;
+
+use crate::{create_program, create_shader, ShaderType};
+
+/// Blurs a texture along one direction while drawing it over the whole framebuffer.
+///
+/// A Gaussian blur is separable, so a blur in two dimensions is a horizontal pass followed by a vertical pass.
+pub struct Pipeline {
+    pub program: glow::Program,
+    pub attrib_position: u32,
+    pub uniform_image: glow::UniformLocation,
+    pub uniform_direction: glow::UniformLocation,
+    pub uniform_sigma: glow::UniformLocation,
+}
+
+const VERTEX_SHADER: &str = include_str!("shader/blur.vert");
+const FRAGMENT_SHADER: &str = include_str!("shader/blur.frag");
+
+impl Pipeline {
+    pub unsafe fn new(context: &glow::Context) -> Result<Self, String> {
+        let vertex = create_shader(context, ShaderType::Vertex, VERTEX_SHADER)?;
+        let fragment = create_shader(context, ShaderType::Fragment, FRAGMENT_SHADER);
+
+        // If the fragment shader failed to compile, ensure the vertex shader is freed.
+        if fragment.is_err() {
+            context.delete_shader(vertex);
+        }
+
+        let fragment = fragment?;
+        let program = create_program(context, &[vertex, fragment], &[(0, "position")])?;
+
+        // Get the attribute location of the position
+        let attrib_position = context
+            .get_attrib_location(program, "position")
+            .ok_or("Failed to get location of attribute \"position\"")?;
+
+        let uniform = |name: &str| {
+            context
+                .get_uniform_location(program, name)
+                .ok_or_else(|| format!("Failed to get location of uniform \"{}\"", name))
+        };
+
+        Ok(Self {
+            program,
+            attrib_position,
+            uniform_image: uniform("image")?,
+            uniform_direction: uniform("direction")?,
+            uniform_sigma: uniform("sigma")?,
+        })
+    }
+
+    pub unsafe fn bind(&self, context: &glow::Context) {
+        context.use_program(Some(self.program));
+        context.enable_vertex_attrib_array(self.attrib_position);
+
+        // Sample the texture from the first texture unit.
+        context.active_texture(glow::TEXTURE0);
+        context.uniform_1_i32(Some(&self.uniform_image), 0);
+    }
+
+    pub unsafe fn unbind(&self, context: &glow::Context) {
+        context.disable_vertex_attrib_array(self.attrib_position);
+        context.use_program(None);
+    }
+
+    /// Draws a texture blurred along a direction into the bound framebuffer, which must be the size of the texture.
+    ///
+    /// The direction is the distance between two texels in texture coordinates, and the standard deviation of the
+    /// blur is in texels.
+    ///
+    /// SAFETY: The pipeline must be bound.
+    pub unsafe fn draw(
+        &self,
+        context: &glow::Context,
+        texture: glow::Texture,
+        direction: [f32; 2],
+        sigma: f32,
+    ) {
+        // Two triangles covering the framebuffer.
+        #[rustfmt::skip]
+        let verts: [f32; 12] = [
+            -1.0, -1.0,
+            1.0, -1.0,
+            1.0, 1.0,
+            //
+            -1.0, -1.0,
+            1.0, 1.0,
+            -1.0, 1.0,
+        ];
+
+        context.bind_texture(glow::TEXTURE_2D, Some(texture));
+        // Samples between texels are interpolated, and samples past the edges repeat the edges.
+        context.tex_parameter_i32(
+            glow::TEXTURE_2D,
+            glow::TEXTURE_MIN_FILTER,
+            glow::LINEAR as i32,
+        );
+        context.tex_parameter_i32(
+            glow::TEXTURE_2D,
+            glow::TEXTURE_MAG_FILTER,
+            glow::LINEAR as i32,
+        );
+        context.tex_parameter_i32(
+            glow::TEXTURE_2D,
+            glow::TEXTURE_WRAP_S,
+            glow::CLAMP_TO_EDGE as i32,
+        );
+        context.tex_parameter_i32(
+            glow::TEXTURE_2D,
+            glow::TEXTURE_WRAP_T,
+            glow::CLAMP_TO_EDGE as i32,
+        );
+
+        context.uniform_2_f32_slice(Some(&self.uniform_direction), &direction);
+        context.uniform_1_f32(Some(&self.uniform_sigma), sigma);
+
+        // Allocate a buffer to draw into
+        // TODO: Preallocation of buffers.
+        let buffer = context.create_buffer().unwrap();
+        context.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
+        context.buffer_data_u8_slice(
+            glow::ARRAY_BUFFER,
+            bytemuck::cast_slice(&verts),
+            glow::STATIC_DRAW,
+        );
+
+        context.vertex_attrib_pointer_f32(self.attrib_position, 2, glow::FLOAT, false, 0, 0);
+        context.draw_arrays(glow::TRIANGLES, 0, (verts.len() / 2) as i32);
+
+        context.delete_buffer(buffer);
+        context.bind_texture(glow::TEXTURE_2D, None);
+    }
+}
@@ -0,0 +1,980 @@
+use ::renderer::{
+    Backdrop, BlendMode, BoxShadow, CornerRadii, DrawImage, FillRule, Mesh, Path, Quad,
+    RenderBlend, RenderBlur, RenderClip, RenderImage, RenderLayer, RenderMesh, RenderPath,
+    RenderQuad, RenderShadow, RenderTexturedMesh, RenderTransform, Stroke, Transform, Viewport,
+};
+use glow::HasContext;
+
+use crate::{
+    create_framebuffer,
+    pool::RenderTexture,
+    shadow,
+    state::{self, Clip, State, Target},
+    Error, GlowImage, GlowRenderer, Pipeline,
+};
+
+/// Records draw commands for a frame, which are issued to OpenGL when the encoder is submitted.
+///
+/// Recording the commands first allows the encoder to reorder commands which do not overlap, so commands using the
+/// same pipeline are drawn together without switching programs in between.
+#[must_use = "Dropping an encoder without submitting it does nothing"]
+pub struct GlowEncoder<'a> {
+    renderer: &'a mut GlowRenderer,
+    viewport: Viewport,
+    /// The image drawn into, or [`None`] to draw into the default framebuffer.
+    image: Option<&'a GlowImage>,
+    commands: Vec<Draw>,
+    /// The states the commands are drawn with.
+    states: Vec<State>,
+    /// The transforms which were pushed, combined with the transforms pushed before.
+    transforms: Vec<Transform>,
+    /// The clips which were pushed, clipped to the clips pushed before.
+    clips: Vec<Clip>,
+    /// The layers which were pushed and are being recorded.
+    layers: Vec<Layer>,
+    blend_mode: BlendMode,
+    /// The first error while recording, which is returned when the encoder is submitted.
+    error: Option<Error>,
+}
+
+impl<'a> GlowEncoder<'a> {
+    pub(crate) fn new(
+        renderer: &'a mut GlowRenderer,
+        viewport: Viewport,
+        image: Option<&'a GlowImage>,
+    ) -> Self {
+        Self {
+            renderer,
+            viewport,
+            image,
+            commands: Vec::new(),
+            states: Vec::new(),
+            transforms: Vec::new(),
+            clips: Vec::new(),
+            layers: Vec::new(),
+            blend_mode: BlendMode::Normal,
+            error: None,
+        }
+    }
+
+    /// Returns the area of the surface being drawn to.
+    pub fn viewport(&self) -> Viewport {
+        self.viewport
+    }
+
+    /// Issues the recorded commands.
+    ///
+    /// Layers which were not popped are popped first. Blending is enabled to draw premultiplied colors, and is left
+    /// enabled with [`BlendMode::Normal`].
+    ///
+    /// If a command was rejected while recording, such as an invalid mesh or a path which failed to tessellate, the
+    /// other commands are issued and the first error is returned. Errors reported by OpenGL while drawing are
+    /// returned as [`Error::Gl`].
+    pub fn submit(mut self) -> Result<(), Error> {
+        while !self.layers.is_empty() {
+            self.pop_layer();
+        }
+
+        let context = &self.renderer.context;
+        let Viewport { width, height, .. } = self.viewport;
+
+        unsafe {
+            context.enable(glow::BLEND);
+
+            let result = match self.image {
+                Some(image) => {
+                    let mut viewport = [0; 4];
+                    context.get_parameter_i32_slice(glow::VIEWPORT, &mut viewport);
+
+                    let result = create_framebuffer(context, image.id).and_then(|framebuffer| {
+                        let target = Target {
+                            framebuffer: Some(framebuffer),
+                            width,
+                            height,
+                            top_down: true,
+                        };
+
+                        context.viewport(0, 0, width as i32, height as i32);
+                        clear(context);
+                        let result = self.draw_commands(&target, &self.commands);
+
+                        context.bind_framebuffer(glow::FRAMEBUFFER, None);
+                        context.delete_framebuffer(framebuffer);
+                        result
+                    });
+
+                    let [x, y, width, height] = viewport;
+                    context.viewport(x, y, width, height);
+                    result
+                }
+
+                None => {
+                    let target = Target {
+                        framebuffer: None,
+                        width,
+                        height,
+                        top_down: false,
+                    };
+
+                    self.draw_commands(&target, &self.commands)
+                }
+            };
+
+            context.disable(glow::SCISSOR_TEST);
+            state::set_blend_mode(context, BlendMode::Normal);
+
+            let mut errors = Vec::new();
+
+            loop {
+                let error = context.get_error();
+
+                if error == glow::NO_ERROR {
+                    break;
+                }
+
+                errors.push(format!("{:#x}", error));
+            }
+
+            let result = match self.error.take() {
+                Some(error) => result.and(Err(error)),
+                None => result,
+            };
+
+            if errors.is_empty() {
+                result
+            } else {
+                result.and(Err(Error::Gl(errors.join(", "))))
+            }
+        }
+    }
+
+    /// Draws commands into the bound framebuffer of a target.
+    unsafe fn draw_commands(&self, target: &Target, commands: &[Draw]) -> Result<(), Error> {
+        let context = &self.renderer.context;
+
+        for batch in batch(commands, &self.states) {
+            match batch.pipeline {
+                PipelineKind::Quad => {
+                    let pipeline = &self.renderer.quad;
+                    self.draw_batch(pipeline, target, commands, &batch, |draw| {
+                        if let Command::Quad(quad) = draw {
+                            pipeline.draw(context, quad);
+                        }
+                    });
+                }
+
+                PipelineKind::Mesh => {
+                    let pipeline = &self.renderer.triangle;
+                    self.draw_batch(pipeline, target, commands, &batch, |draw| {
+                        if let Command::Mesh {
+                            mesh,
+                            texture,
+                            mask,
+                        } = draw
+                        {
+                            pipeline.draw(context, mesh, *texture, *mask);
+                        }
+                    });
+                }
+
+                PipelineKind::Shadow => {
+                    let pipeline = &self.renderer.shadow;
+                    self.draw_batch(pipeline, target, commands, &batch, |draw| {
+                        if let Command::Shadow(shadow) = draw {
+                            pipeline.draw(context, shadow);
+                        }
+                    });
+                }
+
+                PipelineKind::Image { external } => {
+                    let pipeline = match external {
+                        false => &self.renderer.image.regular,
+                        // External images can only be created if the extension is supported.
+                        true => self.renderer.image.external.as_ref().unwrap(),
+                    };
+                    self.draw_batch(pipeline, target, commands, &batch, |draw| {
+                        if let Command::Image {
+                            texture,
+                            mask,
+                            draw,
+                            ..
+                        } = draw
+                        {
+                            pipeline.draw(context, *texture, *mask, draw);
+                        }
+                    });
+                }
+
+                PipelineKind::Layer => {
+                    for &index in &batch.commands {
+                        if let Command::Layer(layer) = &commands[index].command {
+                            self.draw_layer(target, layer)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws the commands of a batch, setting the state of each command which is drawn with another state than
+    /// the previous command.
+    unsafe fn draw_batch<P: Pipeline>(
+        &self,
+        pipeline: &P,
+        target: &Target,
+        commands: &[Draw],
+        batch: &Batch,
+        mut draw: impl FnMut(&Command),
+    ) {
+        let context = &self.renderer.context;
+        let mut current = None;
+
+        pipeline.bind(context);
+
+        for &index in &batch.commands {
+            let command = &commands[index];
+
+            if current != Some(command.state) {
+                self.set_state(pipeline, target, &self.states[command.state]);
+                current = Some(command.state);
+            }
+
+            draw(&command.command);
+        }
+
+        pipeline.unbind(context);
+    }
+
+    /// SAFETY: The pipeline must be bound.
+    unsafe fn set_state<P: Pipeline>(&self, pipeline: &P, target: &Target, state: &State) {
+        let context = &self.renderer.context;
+
+        pipeline.state().set(context, target, state);
+        state::set_scissor(context, target, &state.clip);
+        state::set_blend_mode(context, state.blend_mode);
+    }
+
+    /// Draws the commands of a layer into a texture the size of the target, blurs the texture if the layer is
+    /// blurred, and then draws the texture with the clip the layer was pushed with. The backdrop of the layer is
+    /// drawn first.
+    ///
+    /// The textures are taken from the texture pool of the renderer and returned afterwards, so they are reused by
+    /// other layers and frames.
+    unsafe fn draw_layer(&self, target: &Target, layer: &Layer) -> Result<(), Error> {
+        if let Some(backdrop) = &layer.backdrop {
+            self.draw_backdrop(target, backdrop)?;
+        }
+
+        if layer.commands.is_empty() {
+            return Ok(());
+        }
+
+        let context = &self.renderer.context;
+        let Target { width, height, .. } = *target;
+
+        let texture = self
+            .renderer
+            .textures
+            .borrow_mut()
+            .acquire(context, width, height)?;
+        let layer_target = Target {
+            framebuffer: Some(texture.framebuffer),
+            width,
+            height,
+            // The texture is drawn like an image.
+            top_down: true,
+        };
+
+        context.disable(glow::SCISSOR_TEST);
+        clear(context);
+        let result = self.draw_commands(&layer_target, &layer.commands);
+        context.bind_framebuffer(glow::FRAMEBUFFER, target.framebuffer);
+
+        let result = match layer.blur_radius > 0.0 {
+            true => result.and_then(|_| self.blur(target, &texture, layer.blur_radius / 2.0)),
+            false => result,
+        };
+
+        if result.is_ok() {
+            let state = State {
+                clip: layer.clip,
+                blend_mode: layer.blend_mode,
+                ..State::default()
+            };
+            self.draw_texture(target, &texture, true, layer.bounds, layer.opacity, &state);
+        }
+
+        self.renderer
+            .textures
+            .borrow_mut()
+            .release(context, texture);
+        result
+    }
+
+    /// Copies what was drawn into the target around the area of a backdrop into a texture the size of the target,
+    /// blurs the texture, and then draws it within the area.
+    unsafe fn draw_backdrop(
+        &self,
+        target: &Target,
+        backdrop: &BlurredBackdrop,
+    ) -> Result<(), Error> {
+        let context = &self.renderer.context;
+        let Target { width, height, .. } = *target;
+
+        let texture = self
+            .renderer
+            .textures
+            .borrow_mut()
+            .acquire(context, width, height)?;
+
+        // Pixels outside of the copied area are blurred as transparent.
+        context.disable(glow::SCISSOR_TEST);
+        clear(context);
+        context.bind_framebuffer(glow::FRAMEBUFFER, target.framebuffer);
+
+        let [left, top, right, bottom] = backdrop.bounds();
+        let left = left.floor().clamp(0.0, width as f32) as i32;
+        let top = top.floor().clamp(0.0, height as f32) as i32;
+        let right = (right.ceil().clamp(0.0, width as f32) as i32).max(left);
+        let bottom = (bottom.ceil().clamp(0.0, height as f32) as i32).max(top);
+
+        // The rows of the texture are in the same order as the rows of the target.
+        let y = match target.top_down {
+            true => top,
+            false => height as i32 - bottom,
+        };
+
+        context.bind_texture(glow::TEXTURE_2D, Some(texture.texture));
+        context.copy_tex_sub_image_2d(
+            glow::TEXTURE_2D,
+            0,
+            left,
+            y,
+            left,
+            y,
+            right - left,
+            bottom - top,
+        );
+        context.bind_texture(glow::TEXTURE_2D, None);
+
+        let result = match backdrop.blur_radius > 0.0 {
+            true => self.blur(target, &texture, backdrop.blur_radius / 2.0),
+            false => Ok(()),
+        };
+
+        if result.is_ok() {
+            let state = State {
+                clip: backdrop.clip,
+                ..State::default()
+            };
+            let bounds = backdrop.clip.scissor.unwrap_or_default();
+            self.draw_texture(target, &texture, target.top_down, bounds, 1.0, &state);
+        }
+
+        self.renderer
+            .textures
+            .borrow_mut()
+            .release(context, texture);
+        result
+    }
+
+    /// Draws an area of a texture the size of the target, as the left, top, right and bottom edges in physical
+    /// pixels, into the same area of the target.
+    ///
+    /// `top_down` is whether the first row of the texture is the top, like for images.
+    unsafe fn draw_texture(
+        &self,
+        target: &Target,
+        texture: &RenderTexture,
+        top_down: bool,
+        bounds: [f32; 4],
+        opacity: f32,
+        state: &State,
+    ) {
+        let context = &self.renderer.context;
+
+        // Sampling past the edges of the texture would repeat the edges.
+        let [left, top, right, bottom] = bounds;
+        let (width, height) = (target.width as f32, target.height as f32);
+        let (left, top) = (left.clamp(0.0, width), top.clamp(0.0, height));
+        let (right, bottom) = (right.clamp(left, width), bottom.clamp(top, height));
+
+        if left >= right || top >= bottom {
+            return;
+        }
+
+        let source = match top_down {
+            true => [top / height, (bottom - top) / height],
+            // The image is flipped, so it's first row is the bottom.
+            false => [1.0 - top / height, (top - bottom) / height],
+        };
+
+        let draw = DrawImage {
+            source: [left / width, source[0], (right - left) / width, source[1]],
+            opacity,
+            ..DrawImage::new(left, top, right - left, bottom - top)
+        };
+
+        let pipeline = &self.renderer.image.regular;
+        pipeline.bind(context);
+        self.set_state(pipeline, target, state);
+        pipeline.draw(context, texture.texture, false, &draw);
+        pipeline.unbind(context);
+    }
+
+    /// Blurs a texture the size of the target with a Gaussian, with a horizontal pass into another texture and a
+    /// vertical pass back into the texture.
+    unsafe fn blur(
+        &self,
+        target: &Target,
+        texture: &RenderTexture,
+        sigma: f32,
+    ) -> Result<(), Error> {
+        let context = &self.renderer.context;
+        let pipeline = &self.renderer.blur;
+        let Target { width, height, .. } = *target;
+
+        let horizontal = self
+            .renderer
+            .textures
+            .borrow_mut()
+            .acquire(context, width, height)?;
+
+        // Every pixel is replaced by the blurred pixel.
+        context.disable(glow::SCISSOR_TEST);
+        context.disable(glow::BLEND);
+        pipeline.bind(context);
+
+        let passes = [
+            (&horizontal, texture, [1.0 / width as f32, 0.0]),
+            (texture, &horizontal, [0.0, 1.0 / height as f32]),
+        ];
+
+        for (destination, source, direction) in passes {
+            context.bind_framebuffer(glow::FRAMEBUFFER, Some(destination.framebuffer));
+            pipeline.draw(context, source.texture, direction, sigma);
+        }
+
+        pipeline.unbind(context);
+        context.enable(glow::BLEND);
+        context.bind_framebuffer(glow::FRAMEBUFFER, target.framebuffer);
+        self.renderer
+            .textures
+            .borrow_mut()
+            .release(context, horizontal);
+        Ok(())
+    }
+
+    fn transform(&self) -> Transform {
+        self.transforms.last().copied().unwrap_or_default()
+    }
+
+    fn clip(&self) -> Clip {
+        self.clips.last().copied().unwrap_or_default()
+    }
+
+    fn record(&mut self, command: Command) {
+        let state = State {
+            transform: self.transform(),
+            clip: self.clip(),
+            blend_mode: self.blend_mode,
+        };
+
+        if !state.clip.is_empty() {
+            self.record_with(command, state);
+        }
+    }
+
+    fn record_with(&mut self, command: Command, state: State) {
+        if self.states.last() != Some(&state) {
+            self.states.push(state);
+        }
+
+        let draw = Draw {
+            command,
+            state: self.states.len() - 1,
+        };
+
+        match self.layers.last_mut() {
+            Some(layer) => layer.commands.push(draw),
+            None => self.commands.push(draw),
+        }
+    }
+
+    /// Draws the mesh of a tessellated path, or records the error if tessellating failed.
+    fn draw_tessellated(&mut self, mesh: Result<Mesh, Error>) {
+        match mesh {
+            Ok(mesh) if mesh.is_empty() => {}
+            Ok(mesh) => self.draw_mesh(mesh),
+            Err(error) => {
+                self.error.get_or_insert(error);
+            }
+        }
+    }
+}
+
+impl RenderQuad for GlowEncoder<'_> {
+    fn draw_quad(&mut self, quad: Quad) {
+        self.record(Command::Quad(quad));
+    }
+}
+
+impl RenderShadow for GlowEncoder<'_> {
+    fn draw_shadow(&mut self, shadow: BoxShadow) {
+        self.record(Command::Shadow(shadow));
+    }
+}
+
+impl RenderImage for GlowEncoder<'_> {
+    fn draw_image(&mut self, image: &GlowImage, draw: DrawImage) {
+        self.record(Command::Image {
+            texture: image.id(),
+            external: image.is_external(),
+            mask: image.is_mask(),
+            draw,
+        });
+    }
+}
+
+impl RenderMesh for GlowEncoder<'_> {
+    /// Invalid meshes are rejected, and [`GlowEncoder::submit`] returns [`Error::InvalidMesh`].
+    fn draw_mesh(&mut self, mesh: Mesh) {
+        if !mesh.is_valid() {
+            self.error.get_or_insert(Error::InvalidMesh);
+            return;
+        }
+
+        self.record(Command::Mesh {
+            mesh,
+            texture: None,
+            mask: false,
+        });
+    }
+}
+
+impl RenderTexturedMesh for GlowEncoder<'_> {
+    /// Invalid meshes and meshes without positions within the image are rejected, and [`GlowEncoder::submit`]
+    /// returns [`Error::InvalidMesh`]. External images are not supported, and [`GlowEncoder::submit`] returns
+    /// [`Error::Unsupported`].
+    fn draw_textured_mesh(&mut self, mesh: Mesh, image: &GlowImage) {
+        if !mesh.is_valid() || mesh.uvs.is_none() {
+            self.error.get_or_insert(Error::InvalidMesh);
+            return;
+        }
+
+        if image.is_external() {
+            self.error
+                .get_or_insert(Error::Unsupported("drawing meshes with external images"));
+            return;
+        }
+
+        self.record(Command::Mesh {
+            mesh,
+            texture: Some(image.id()),
+            mask: image.is_mask(),
+        });
+    }
+}
+
+impl RenderPath for GlowEncoder<'_> {
+    /// Paths which fail to tessellate are not drawn, and [`GlowEncoder::submit`] returns
+    /// [`Error::Tessellation`].
+    fn fill_path(&mut self, path: &Path, rule: FillRule, color: [f32; 4]) {
+        let mesh = self.renderer.tessellator.fill(path, rule, color);
+        self.draw_tessellated(mesh);
+    }
+
+    /// Paths which fail to tessellate are not drawn, and [`GlowEncoder::submit`] returns
+    /// [`Error::Tessellation`].
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke, color: [f32; 4]) {
+        let mesh = self.renderer.tessellator.stroke(path, stroke, color);
+        self.draw_tessellated(mesh);
+    }
+}
+
+impl RenderTransform for GlowEncoder<'_> {
+    fn push_transform(&mut self, transform: Transform) {
+        let transform = transform.then(&self.transform());
+        self.transforms.push(transform);
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop().expect("no transform was pushed");
+    }
+}
+
+impl RenderClip for GlowEncoder<'_> {
+    /// Rectangular clips which are not rotated are applied exactly with the scissor test. Other clips are applied
+    /// by the shaders, which apply the innermost four of them exactly, and any outer clips beyond those as the
+    /// rectangle containing them.
+    fn push_clip(&mut self, rect: [f32; 4], radii: CornerRadii) {
+        let clip = self.clip().push(&self.transform(), rect, radii);
+        self.clips.push(clip);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop().expect("no clip was pushed");
+    }
+}
+
+impl RenderBlend for GlowEncoder<'_> {
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
+
+impl RenderBlur for GlowEncoder<'_> {
+    /// Layers are blurred in two passes over a texture the size of the target, which each sample at most 65 pixels
+    /// for a pixel. Larger blurs skip pixels, which are interpolated.
+    fn push_blurred_layer(&mut self, blur_radius: f32, opacity: f32, blend_mode: BlendMode) {
+        self.layers.push(Layer {
+            commands: Vec::new(),
+            opacity,
+            blend_mode,
+            blur_radius: blur_radius.max(0.0),
+            clip: self.clip(),
+            backdrop: None,
+            bounds: [0.0; 4],
+        });
+    }
+
+    /// The backdrop is copied from the target, which must have an alpha channel and must not be multisampled, and
+    /// is blurred like a blurred layer.
+    fn push_backdrop_layer(&mut self, backdrop: Backdrop, opacity: f32, blend_mode: BlendMode) {
+        let clip = self.clip();
+        let backdrop_clip = clip.push(&self.transform(), backdrop.rect, backdrop.radii);
+
+        self.layers.push(Layer {
+            commands: Vec::new(),
+            opacity,
+            blend_mode,
+            blur_radius: 0.0,
+            clip,
+            // A backdrop which is clipped away is not drawn.
+            backdrop: (!backdrop_clip.is_empty()).then_some(BlurredBackdrop {
+                clip: backdrop_clip,
+                blur_radius: backdrop.blur_radius.max(0.0),
+            }),
+            bounds: [0.0; 4],
+        });
+    }
+}
+
+impl RenderLayer for GlowEncoder<'_> {
+    fn push_layer(&mut self, opacity: f32, blend_mode: BlendMode) {
+        self.layers.push(Layer {
+            commands: Vec::new(),
+            opacity,
+            blend_mode,
+            blur_radius: 0.0,
+            clip: self.clip(),
+            backdrop: None,
+            bounds: [0.0; 4],
+        });
+    }
+
+    fn pop_layer(&mut self) {
+        let mut layer = self.layers.pop().expect("no layer was pushed");
+        let bounds = layer
+            .commands
+            .iter()
+            .map(|draw| self.states[draw.state].bounds(draw.command.bounds()))
+            .reduce(union)
+            .map(|[left, top, right, bottom]| {
+                // The blur spreads the commands by 3 standard deviations.
+                let extent = 1.5 * layer.blur_radius;
+                [left - extent, top - extent, right + extent, bottom + extent]
+            });
+
+        // Commands drawn before the layer within the area the backdrop is copied from must be drawn first.
+        let bounds = match (bounds, layer.backdrop.as_ref().map(BlurredBackdrop::bounds)) {
+            (Some(bounds), Some(backdrop)) => Some(union(bounds, backdrop)),
+            (bounds, backdrop) => bounds.or(backdrop),
+        };
+
+        // An empty layer without a backdrop draws nothing.
+        if let Some(bounds) = bounds {
+            layer.bounds = bounds;
+
+            // The commands of the layer were transformed and clipped when recorded, but the blur spreads them past
+            // the clip, so the layer is drawn with the clip again.
+            let state = State {
+                clip: layer.clip,
+                ..State::default()
+            };
+            self.record_with(Command::Layer(Box::new(layer)), state);
+        }
+    }
+}
+
+/// A recorded command and the index of the state it is drawn with.
+struct Draw {
+    command: Command,
+    state: usize,
+}
+
+/// A recorded draw command.
+enum Command {
+    Quad(Quad),
+    Mesh {
+        mesh: Mesh,
+        /// The texture of the image the mesh is drawn with.
+        texture: Option<glow::Texture>,
+        /// Whether only the alpha of the image is sampled.
+        mask: bool,
+    },
+    Shadow(BoxShadow),
+    Image {
+        texture: glow::Texture,
+        external: bool,
+        /// Whether only the alpha of the image is sampled.
+        mask: bool,
+        draw: DrawImage,
+    },
+    Layer(Box<Layer>),
+}
+
+/// Commands drawn into a texture, which is then drawn as a whole.
+struct Layer {
+    commands: Vec<Draw>,
+    opacity: f32,
+    blend_mode: BlendMode,
+    /// How far the layer is blurred, which is twice the standard deviation of the blur.
+    ///
+    /// A radius of 0 does not blur the layer.
+    blur_radius: f32,
+    /// The clip when the layer was pushed, which the layer is drawn with.
+    clip: Clip,
+    backdrop: Option<BlurredBackdrop>,
+    /// The area containing every command of the layer and the area the backdrop is copied from, as the left, top,
+    /// right and bottom edges in physical pixels.
+    bounds: [f32; 4],
+}
+
+/// What was drawn before a layer within an area, which is blurred and drawn below the layer.
+struct BlurredBackdrop {
+    /// The clip of the area, within the clip the layer was pushed with.
+    clip: Clip,
+    /// How far the backdrop is blurred, which is twice the standard deviation of the blur.
+    blur_radius: f32,
+}
+
+impl BlurredBackdrop {
+    /// Returns the area the backdrop is copied from, which includes the pixels blurred into the area of the
+    /// backdrop, as the left, top, right and bottom edges in physical pixels.
+    fn bounds(&self) -> [f32; 4] {
+        let [left, top, right, bottom] = self.clip.scissor.unwrap_or_default();
+        let extent = 1.5 * self.blur_radius;
+        [left - extent, top - extent, right + extent, bottom + extent]
+    }
+}
+
+impl Command {
+    fn pipeline(&self) -> PipelineKind {
+        match self {
+            Command::Quad(_) => PipelineKind::Quad,
+            Command::Mesh { .. } => PipelineKind::Mesh,
+            Command::Shadow(_) => PipelineKind::Shadow,
+            &Command::Image { external, .. } => PipelineKind::Image { external },
+            Command::Layer(_) => PipelineKind::Layer,
+        }
+    }
+
+    /// Returns the area the command may draw to before it is transformed, as the left, top, right and bottom
+    /// edges.
+    fn bounds(&self) -> [f32; 4] {
+        match self {
+            Command::Quad(quad) => [quad.x, quad.y, quad.x + quad.width, quad.y + quad.height],
+            Command::Mesh { mesh, .. } => mesh.bounds().unwrap_or_default(),
+            Command::Shadow(shadow) => shadow::bounds(shadow),
+            Command::Image { draw, .. } => {
+                let [x, y, width, height] = draw.destination;
+                [x, y, x + width, y + height]
+            }
+            Command::Layer(layer) => layer.bounds,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PipelineKind {
+    Quad,
+    Mesh,
+    Shadow,
+    Image {
+        /// Whether the images are sampled with `samplerExternalOES`.
+        external: bool,
+    },
+    Layer,
+}
+
+/// Commands drawn with the same pipeline.
+struct Batch {
+    pipeline: PipelineKind,
+    /// The indices of the commands in the order they are drawn.
+    commands: Vec<usize>,
+    /// The area containing every command of the batch.
+    bounds: [f32; 4],
+}
+
+/// Groups commands into batches drawn with the same pipeline, in the order the batches must be drawn.
+///
+/// A command joins the last batch with the same pipeline unless it overlaps a command of a later batch, since it
+/// would then be drawn below a command recorded before it.
+fn batch(commands: &[Draw], states: &[State]) -> Vec<Batch> {
+    let mut batches: Vec<Batch> = Vec::new();
+
+    for (index, draw) in commands.iter().enumerate() {
+        let pipeline = draw.command.pipeline();
+        let bounds = states[draw.state].bounds(draw.command.bounds());
+        let mut target = None;
+
+        for (position, batch) in batches.iter().enumerate().rev() {
+            if batch.pipeline == pipeline {
+                target = Some(position);
+                break;
+            }
+
+            if overlaps(batch.bounds, bounds) {
+                break;
+            }
+        }
+
+        match target {
+            Some(position) => {
+                let batch = &mut batches[position];
+                batch.commands.push(index);
+                batch.bounds = union(batch.bounds, bounds);
+            }
+
+            None => batches.push(Batch {
+                pipeline,
+                commands: vec![index],
+                bounds,
+            }),
+        }
+    }
+
+    batches
+}
+
+/// Clears the bound framebuffer to transparent.
+unsafe fn clear(context: &glow::Context) {
+    context.clear_color(0.0, 0.0, 0.0, 0.0);
+    context.clear(glow::COLOR_BUFFER_BIT);
+}
+
+fn overlaps(a: [f32; 4], b: [f32; 4]) -> bool {
+    a[0] < b[2] && b[0] < a[2] && a[1] < b[3] && b[1] < a[3]
+}
+
+fn union(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [
+        a[0].min(b[0]),
+        a[1].min(b[1]),
+        a[2].max(b[2]),
+        a[3].max(b[3]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use ::renderer::{Mesh, Quad};
+
+    use crate::state::{Clip, State};
+
+    use super::{batch, Command, Draw, PipelineKind};
+
+    fn quad(x: f32, y: f32) -> Draw {
+        Draw {
+            command: Command::Quad(Quad::new(x, y, 10.0, 10.0, [1.0; 4])),
+            state: 0,
+        }
+    }
+
+    fn mesh(x: f32, y: f32) -> Draw {
+        let mut mesh = Mesh::default();
+        let a = mesh.push_vertex([x, y], [1.0; 4]);
+        let b = mesh.push_vertex([x + 10.0, y], [1.0; 4]);
+        let c = mesh.push_vertex([x, y + 10.0], [1.0; 4]);
+        mesh.push_triangle(a, b, c);
+
+        Draw {
+            command: Command::Mesh {
+                mesh,
+                texture: None,
+                mask: false,
+            },
+            state: 0,
+        }
+    }
+
+    fn batches(commands: &[Draw], states: &[State]) -> Vec<(PipelineKind, Vec<usize>)> {
+        batch(commands, states)
+            .into_iter()
+            .map(|batch| (batch.pipeline, batch.commands))
+            .collect()
+    }
+
+    #[test]
+    fn merge_past_batches() {
+        // The second quad does not overlap the mesh, so it joins the batch of the first quad.
+        let commands = [
+            quad(0.0, 0.0),
+            mesh(20.0, 0.0),
+            quad(40.0, 0.0),
+            mesh(60.0, 0.0),
+        ];
+
+        assert_eq!(
+            batches(&commands, &[State::default()]),
+            [
+                (PipelineKind::Quad, vec![0, 2]),
+                (PipelineKind::Mesh, vec![1, 3])
+            ]
+        );
+    }
+
+    #[test]
+    fn overlap_blocks_merge() {
+        // The second quad overlaps the mesh drawn before it, so it must be drawn after the mesh.
+        let commands = [quad(0.0, 0.0), mesh(20.0, 0.0), quad(25.0, 5.0)];
+
+        assert_eq!(
+            batches(&commands, &[State::default()]),
+            [
+                (PipelineKind::Quad, vec![0]),
+                (PipelineKind::Mesh, vec![1]),
+                (PipelineKind::Quad, vec![2])
+            ]
+        );
+
+        // Commands are compared by the area they draw to after clipping, so a quad clipped away from the mesh
+        // merges again.
+        let clipped = State {
+            clip: Clip {
+                scissor: Some([30.0, 0.0, 40.0, 20.0]),
+                ..Clip::default()
+            },
+            ..State::default()
+        };
+        let commands = [
+            quad(0.0, 0.0),
+            mesh(20.0, 0.0),
+            Draw {
+                state: 1,
+                ..quad(25.0, 5.0)
+            },
+        ];
+
+        assert_eq!(
+            batches(&commands, &[State::default(), clipped]),
+            [
+                (PipelineKind::Quad, vec![0, 2]),
+                (PipelineKind::Mesh, vec![1])
+            ]
+        );
+    }
+}
@@ -1,66 +1,203 @@
-use glam::Mat4;
+use ::renderer::{DrawImage, Filter};
 use glow::HasContext;
 
-use crate::{create_program, create_shader, ShaderType};
+use crate::{create_program, create_shader, state, ShaderType};
 
-const VERTEX_SHADER: &str = include_str!("shader/image.vert");
-const FRAGMENT_SHADER: &str = include_str!("shader/image.frag");
+const VERTEX_SHADER: &str = concat!(
+    include_str!("shader/image.vert"),
+    include_str!("shader/clip.vert")
+);
+const FRAGMENT_SHADER: &str = concat!(
+    include_str!("shader/image.frag"),
+    include_str!("shader/distance.frag"),
+    include_str!("shader/clip.frag")
+);
+const EXTERNAL_FRAGMENT_SHADER: &str = concat!(
+    include_str!("shader/image_external.frag"),
+    include_str!("shader/distance.frag"),
+    include_str!("shader/clip.frag")
+);
+
+/// The texture target of images imported with `GL_OES_EGL_image_external`.
+pub const TEXTURE_EXTERNAL_OES: u32 = 0x8D65;
 
 pub struct Pipeline {
-    pub program: glow::Program,
-    pub attrib_position: u32,
-    pub uniform_matrix: glow::UniformLocation,
+    /// Draws images sampled with `sampler2D`.
+    pub regular: Program,
+    /// Draws images sampled with `samplerExternalOES`, if `GL_OES_EGL_image_external` is supported.
+    pub external: Option<Program>,
 }
 
 impl Pipeline {
     pub unsafe fn new(context: &glow::Context) -> Result<Self, String> {
+        let regular = Program::new(context, FRAGMENT_SHADER, glow::TEXTURE_2D)?;
+        let external = if context
+            .supported_extensions()
+            .contains("GL_OES_EGL_image_external")
+        {
+            Some(Program::new(
+                context,
+                EXTERNAL_FRAGMENT_SHADER,
+                TEXTURE_EXTERNAL_OES,
+            )?)
+        } else {
+            None
+        };
+
+        Ok(Pipeline { regular, external })
+    }
+}
+
+/// A program drawing images bound to one texture target.
+pub struct Program {
+    pub program: glow::Program,
+    pub target: u32,
+    pub attrib_position: u32,
+    pub attrib_coords: u32,
+    pub uniform_image: glow::UniformLocation,
+    pub uniform_tint: glow::UniformLocation,
+    pub uniform_mask: glow::UniformLocation,
+    pub state: state::Uniforms,
+}
+
+impl Program {
+    unsafe fn new(context: &glow::Context, fragment: &str, target: u32) -> Result<Self, String> {
         let vertex = create_shader(context, ShaderType::Vertex, VERTEX_SHADER)?;
-        let fragment = create_shader(context, ShaderType::Fragment, FRAGMENT_SHADER);
+        let fragment = create_shader(context, ShaderType::Fragment, fragment);
 
         // If the fragment shader failed to compile, ensure the vertex shader is freed.
         if fragment.is_err() {
             context.delete_shader(vertex);
         }
 
-        let fragment = fragment.unwrap();
-        let program = create_program(context, &[vertex, fragment], &[(0, "position")])?;
+        let fragment = fragment?;
+        let program = create_program(
+            context,
+            &[vertex, fragment],
+            &[(0, "position"), (1, "coordinates")],
+        )?;
 
         let attrib_position = context
             .get_attrib_location(program, "position")
             .ok_or("Failed to get location of attribute \"position\"")?;
 
-        let uniform_matrix = context
-            .get_uniform_location(program, "matrix")
-            .ok_or("Failed to get location of uniform \"matrix\"")?;
+        let attrib_coords = context
+            .get_attrib_location(program, "coordinates")
+            .ok_or("Failed to get location of attribute \"coordinates\"")?;
+
+        let uniform = |name: &str| {
+            context
+                .get_uniform_location(program, name)
+                .ok_or_else(|| format!("Failed to get location of uniform \"{}\"", name))
+        };
 
-        Ok(Pipeline {
+        Ok(Program {
             program,
+            target,
             attrib_position,
-            uniform_matrix,
+            attrib_coords,
+            uniform_image: uniform("image")?,
+            uniform_tint: uniform("tint")?,
+            uniform_mask: uniform("mask")?,
+            state: state::Uniforms::new(context, program)?,
         })
     }
 
-    /// SAFETY: The pipeline must be bound.
-    pub unsafe fn draw(&self, _context: &glow::Context) {}
+    /// Draws an image in physical pixels. A mask image is drawn as white with the alpha of the image.
+    ///
+    /// SAFETY: The program must be bound.
+    pub unsafe fn draw(
+        &self,
+        context: &glow::Context,
+        texture: glow::Texture,
+        mask: bool,
+        draw: &DrawImage,
+    ) {
+        let [x, y, width, height] = draw.destination;
+        let [u, v, source_width, source_height] = draw.source;
+        let (u2, v2) = (u + source_width, v + source_height);
+
+        // The position followed by the position within the image of each vertex.
+        #[rustfmt::skip]
+        let verts: [f32; 24] = [
+            x, y, u, v,
+            x + width, y, u2, v,
+            x + width, y + height, u2, v2,
+            //
+            x, y, u, v,
+            x + width, y + height, u2, v2,
+            x, y + height, u, v2,
+        ];
+
+        context.bind_texture(self.target, Some(texture));
+
+        let filter = match draw.filter {
+            Filter::Linear => glow::LINEAR,
+            Filter::Nearest => glow::NEAREST,
+        } as i32;
+        context.tex_parameter_i32(self.target, glow::TEXTURE_MIN_FILTER, filter);
+        context.tex_parameter_i32(self.target, glow::TEXTURE_MAG_FILTER, filter);
+        // Sampling past the edges of a sub-rect must not wrap to the opposite edge.
+        context.tex_parameter_i32(
+            self.target,
+            glow::TEXTURE_WRAP_S,
+            glow::CLAMP_TO_EDGE as i32,
+        );
+        context.tex_parameter_i32(
+            self.target,
+            glow::TEXTURE_WRAP_T,
+            glow::CLAMP_TO_EDGE as i32,
+        );
+
+        let [r, g, b, a] = draw.tint;
+        context.uniform_4_f32(Some(&self.uniform_tint), r, g, b, a * draw.opacity);
+        context.uniform_1_f32(Some(&self.uniform_mask), mask as u32 as f32);
+
+        // Allocate a buffer to draw into
+        // TODO: Preallocation of buffers.
+        let buffer = context.create_buffer().unwrap();
+        context.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
+        context.buffer_data_u8_slice(
+            glow::ARRAY_BUFFER,
+            bytemuck::cast_slice(&verts),
+            glow::STREAM_DRAW,
+        );
+
+        let stride = (std::mem::size_of::<f32>() * 4) as i32;
+        context.vertex_attrib_pointer_f32(self.attrib_position, 2, glow::FLOAT, false, stride, 0);
+        context.vertex_attrib_pointer_f32(
+            self.attrib_coords,
+            2,
+            glow::FLOAT,
+            false,
+            stride,
+            (std::mem::size_of::<f32>() * 2) as _,
+        );
+        context.draw_arrays(glow::TRIANGLES, 0, (verts.len() / 4) as i32);
+
+        context.delete_buffer(buffer);
+        context.bind_texture(self.target, None);
+    }
 }
 
-impl crate::Pipeline for Pipeline {
-    unsafe fn bind(&self, context: &glow::Context, width: u32, height: u32) {
+impl crate::Pipeline for Program {
+    unsafe fn bind(&self, context: &glow::Context) {
         context.use_program(Some(self.program));
         context.enable_vertex_attrib_array(self.attrib_position);
+        context.enable_vertex_attrib_array(self.attrib_coords);
 
-        let matrix = Mat4::orthographic_rh(0.0, width as f32, 0.0, height as f32, -1.0, 1.0);
-
-        // Flip to compensate for the OpenGL coordinate system
-        let matrix = Mat4::from_rotation_x(std::f32::consts::PI) * matrix;
-        let array = matrix.to_cols_array();
-
-        // Set the size of the viewport
-        context.uniform_matrix_4_f32_slice(Some(&self.uniform_matrix), false, &array);
+        // Sample the image from the first texture unit.
+        context.active_texture(glow::TEXTURE0);
+        context.uniform_1_i32(Some(&self.uniform_image), 0);
     }
 
     unsafe fn unbind(&self, context: &glow::Context) {
         context.disable_vertex_attrib_array(self.attrib_position);
+        context.disable_vertex_attrib_array(self.attrib_coords);
         context.use_program(None);
     }
+
+    fn state(&self) -> &state::Uniforms {
+        &self.state
+    }
 }
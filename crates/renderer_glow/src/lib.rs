@@ -1,22 +1,63 @@
 use std::{
+    cell::RefCell,
     ffi::{c_void, CStr, CString},
     fmt,
     rc::Rc,
 };
 
+use ::renderer::{Format, Viewport};
 use glow::HasContext;
 
+mod blur;
+mod encoder;
 mod image;
+mod path;
+mod pool;
 mod quad;
 mod renderer;
+mod shadow;
+mod state;
 mod triangle;
 
+pub use encoder::GlowEncoder;
+
 #[derive(Debug)]
-pub enum Error {}
+pub enum Error {
+    /// The stride of image data is smaller than a row, or the data does not contain every row.
+    InvalidImageData,
+
+    /// An area outside of an image was updated.
+    OutOfBounds,
+
+    /// Image data has a different format than the image it updates.
+    FormatMismatch,
+
+    /// A mesh has vertices without a color, an incomplete triangle or an index past the vertices.
+    InvalidMesh,
+
+    /// A path could not be tessellated into triangles.
+    Tessellation(String),
+
+    /// A feature requires an OpenGL extension which is not supported.
+    Unsupported(&'static str),
+
+    /// OpenGL failed to create an object or reported errors while drawing.
+    Gl(String),
+}
 
 impl fmt::Display for Error {
-    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidImageData => f.write_str("image data does not contain every row"),
+            Error::OutOfBounds => f.write_str("updated area is outside of the image"),
+            Error::FormatMismatch => {
+                f.write_str("image data has a different format than the image")
+            }
+            Error::InvalidMesh => f.write_str("mesh is invalid"),
+            Error::Tessellation(message) => write!(f, "failed to tessellate path: {}", message),
+            Error::Unsupported(extension) => write!(f, "{} is not supported", extension),
+            Error::Gl(message) => write!(f, "OpenGL error: {}", message),
+        }
     }
 }
 
@@ -25,10 +66,18 @@ impl std::error::Error for Error {}
 pub struct GlowRenderer {
     /// Allocate the glow context on the heap to reduce the size of the renderer when moved in memory.
     context: Rc<glow::Context>,
+    /// Whether `GL_EXT_texture_format_BGRA8888` is supported, so BGRA images can be uploaded without swapping the
+    /// red and blue channels.
+    bgra: bool,
+    tessellator: path::Tessellator,
+    /// The textures layers are drawn into, which are kept between frames.
+    textures: RefCell<pool::TexturePool>,
     // Pipelines
     image: image::Pipeline,
     triangle: triangle::Pipeline,
     quad: quad::Pipeline,
+    shadow: shadow::Pipeline,
+    blur: blur::Pipeline,
 }
 
 impl GlowRenderer {
@@ -54,12 +103,22 @@ impl GlowRenderer {
         let triangle =
             triangle::Pipeline::new(&context).expect("Failed to create triangle pipeline");
         let quad = quad::Pipeline::new(&context).expect("Failed to create quad pipeline");
+        let shadow = shadow::Pipeline::new(&context).expect("Failed to create shadow pipeline");
+        let blur = blur::Pipeline::new(&context).expect("Failed to create blur pipeline");
+        let bgra = context
+            .supported_extensions()
+            .contains("GL_EXT_texture_format_BGRA8888");
 
         Self {
             context: Rc::new(context),
+            bgra,
+            tessellator: path::Tessellator::default(),
+            textures: RefCell::default(),
             image,
             triangle,
             quad,
+            shadow,
+            blur,
         }
     }
 
@@ -72,14 +131,20 @@ impl GlowRenderer {
     ///
     /// This may be used to use textures produced from another library using the same context, such as OpenXR.
     ///
-    /// The returned image will not destroy the texture id.
+    /// The returned image will not destroy the texture id. OpenGL ES can not query the size of a texture, so the
+    /// size must be provided.
     ///
     /// # Safety
     ///
     /// - The id must be a valid OpenGL texture.
     /// - The texture id must be valid for the lifetime of the returned image.
-    pub unsafe fn from_raw(&mut self, id: u32) -> Result<GlowImage, Error> {
-        self.from_raw_image(id, false)
+    pub unsafe fn from_raw(
+        &mut self,
+        id: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<GlowImage, Error> {
+        self.from_raw_image(id, width, height, false)
     }
 
     /// Creates an image from a raw OpenGL texture id that is bound to an external texture.
@@ -93,98 +158,97 @@ impl GlowRenderer {
     ///
     /// - The id must be a valid OpenGL texture.
     /// - The texture id must be valid for the lifetime of the returned image.
-    pub unsafe fn from_raw_image_external_egl(&mut self, id: u32) -> Result<GlowImage, Error> {
-        self.from_raw_image(id, true)
+    pub unsafe fn from_raw_image_external_egl(
+        &mut self,
+        id: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<GlowImage, Error> {
+        self.from_raw_image(id, width, height, true)
     }
 
-    unsafe fn from_raw_image(&mut self, id: u32, external: bool) -> Result<GlowImage, Error> {
-        if external {
-            // If the image is external, GL_OES_EGL_image_external must be supported.
-            if !self
-                .context
-                .supported_extensions()
-                .contains("GL_OES_EGL_image_external")
-            {
-                todo!("Not supported");
-            }
+    unsafe fn from_raw_image(
+        &mut self,
+        id: u32,
+        width: u32,
+        height: u32,
+        external: bool,
+    ) -> Result<GlowImage, Error> {
+        // If the image is external, GL_OES_EGL_image_external must be supported.
+        if external && self.image.external.is_none() {
+            return Err(Error::Unsupported("GL_OES_EGL_image_external"));
         }
 
         let id = glow::Context::create_texture_from_gl_name(id);
 
         Ok(GlowImage {
+            context: self.context.clone(),
             id,
+            width,
+            height,
+            format: None,
             is_external: external,
             owned: false,
         })
     }
 
-    pub fn temp_draw(&mut self, width: u32, height: u32) {
-        unsafe {
-            self.triangle.bind(&self.context, width, height);
-            self.triangle.draw(
-                &self.context,
-                &[100.0, 100.0, 0.0, 800.0, 800.0, 0.0, 100.0, 800.0, 0.0],
-                [0.0, 0.0, 0.0, 1.0],
-            );
-            self.triangle.unbind(&self.context);
-        }
-
-        // Quad
-        unsafe {
-            self.quad.bind(&self.context, width, height);
-            self.quad
-                .draw(&self.context, (100, 100), (400, 400), [0.3, 0.2, 0.9, 1.0]);
-
-            self.quad
-                .draw(&self.context, (500, 400), (400, 100), [0.2, 0.2, 0.2, 1.0]);
-
-            self.quad
-                .draw(&self.context, (500, 600), (100, 100), [0.7, 0.2, 0.9, 1.0]);
-            self.quad
-                .draw(&self.context, (600, 600), (100, 100), [0.7, 0.2, 0.9, 1.0]);
-
-            self.quad
-                .draw(&self.context, (500, 500), (200, 100), [0.7, 0.2, 0.1, 1.0]);
-            self.quad
-                .draw(&self.context, (700, 500), (100, 200), [0.7, 0.1, 0.6, 1.0]);
-            self.quad.unbind(&self.context);
-        }
-
-        unsafe {
-            loop {
-                let error = self.context.get_error();
-
-                if error == glow::NO_ERROR {
-                    break;
-                }
-
-                eprintln!("GL Error: {}", error);
-            }
-        }
+    /// Creates an encoder which records draw commands for a surface.
+    ///
+    /// The commands are drawn into the default framebuffer. The viewport of the context should be set to the size of
+    /// the viewport.
+    pub fn create_encoder(&mut self, viewport: Viewport) -> Result<GlowEncoder<'_>, Error> {
+        Ok(GlowEncoder::new(self, viewport, None))
     }
 
-    pub fn create_encoder(&mut self) -> Result<GlowEncoder<'_>, Error> {
-        let encoder = GlowEncoder { renderer: self };
+    /// Creates an empty image which can be drawn into with [`GlowRenderer::create_image_encoder`].
+    pub fn create_render_target(&mut self, width: u32, height: u32) -> Result<GlowImage, Error> {
+        let id = unsafe { create_render_texture(&self.context, width, height)? };
 
-        Ok(encoder)
+        Ok(GlowImage {
+            context: self.context.clone(),
+            id,
+            width,
+            height,
+            format: Some(Format::Rgba8),
+            is_external: false,
+            owned: true,
+        })
     }
-}
 
-#[must_use = "Dropping an encoder without submitting it does nothing"]
-pub struct GlowEncoder<'a> {
-    renderer: &'a GlowRenderer,
-}
+    /// Creates an encoder which records draw commands for an image, such as to draw content once and reuse it
+    /// across frames.
+    ///
+    /// The image is cleared to transparent when the encoder is submitted. The colors of the image are
+    /// premultiplied by their alpha.
+    pub fn create_image_encoder<'a>(
+        &'a mut self,
+        image: &'a GlowImage,
+        scale_factor: f32,
+    ) -> Result<GlowEncoder<'a>, Error> {
+        if image.is_external {
+            return Err(Error::Unsupported("drawing into external images"));
+        }
+
+        let viewport = Viewport {
+            width: image.width,
+            height: image.height,
+            scale_factor,
+        };
 
-impl<'a> GlowEncoder<'a> {
-    pub fn submit(self) -> Result<(), Error> {
-        todo!()
+        Ok(GlowEncoder::new(self, viewport, Some(image)))
     }
 }
 
 pub struct GlowImage {
+    context: Rc<glow::Context>,
     id: glow::Texture,
+    width: u32,
+    height: u32,
+    /// The format the image was created with, or [`None`] if the image was created from a texture id.
+    format: Option<Format>,
     /// Whether this image needs to use `samplerExternalOES` in order to be sampled.
     is_external: bool,
+    /// Whether the texture is destroyed with the image.
     owned: bool,
 }
 
@@ -198,6 +262,25 @@ impl GlowImage {
     pub fn is_external(&self) -> bool {
         self.is_external
     }
+
+    /// Returns whether only the alpha of the image is sampled, which is drawn as white.
+    pub(crate) fn is_mask(&self) -> bool {
+        self.format == Some(Format::A8)
+    }
+}
+
+impl Drop for GlowRenderer {
+    fn drop(&mut self) {
+        unsafe { self.textures.get_mut().clear(&self.context) };
+    }
+}
+
+impl Drop for GlowImage {
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe { self.context.delete_texture(self.id) };
+        }
+    }
 }
 
 #[repr(u32)]
@@ -269,8 +352,67 @@ unsafe fn create_program(
     Ok(program)
 }
 
+/// Creates an empty RGBA texture which can be drawn into.
+unsafe fn create_render_texture(
+    context: &glow::Context,
+    width: u32,
+    height: u32,
+) -> Result<glow::Texture, Error> {
+    let texture = context.create_texture().map_err(Error::Gl)?;
+    context.bind_texture(glow::TEXTURE_2D, Some(texture));
+    context.tex_image_2d(
+        glow::TEXTURE_2D,
+        0,
+        glow::RGBA as i32,
+        width as i32,
+        height as i32,
+        0,
+        glow::RGBA,
+        glow::UNSIGNED_BYTE,
+        None,
+    );
+    // The texture is incomplete with the default filter, which requires mipmaps.
+    context.tex_parameter_i32(
+        glow::TEXTURE_2D,
+        glow::TEXTURE_MIN_FILTER,
+        glow::LINEAR as i32,
+    );
+    context.bind_texture(glow::TEXTURE_2D, None);
+
+    Ok(texture)
+}
+
+/// Creates a framebuffer drawing into a texture, and binds it.
+unsafe fn create_framebuffer(
+    context: &glow::Context,
+    texture: glow::Texture,
+) -> Result<glow::Framebuffer, Error> {
+    let framebuffer = context.create_framebuffer().map_err(Error::Gl)?;
+    context.bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+    context.framebuffer_texture_2d(
+        glow::FRAMEBUFFER,
+        glow::COLOR_ATTACHMENT0,
+        glow::TEXTURE_2D,
+        Some(texture),
+        0,
+    );
+
+    let status = context.check_framebuffer_status(glow::FRAMEBUFFER);
+
+    if status != glow::FRAMEBUFFER_COMPLETE {
+        context.bind_framebuffer(glow::FRAMEBUFFER, None);
+        context.delete_framebuffer(framebuffer);
+        return Err(Error::Gl(format!("Incomplete framebuffer: {:#x}", status)));
+    }
+
+    Ok(framebuffer)
+}
+
 trait Pipeline: Sized {
-    unsafe fn bind(&self, context: &glow::Context, width: u32, height: u32);
+    unsafe fn bind(&self, context: &glow::Context);
 
     unsafe fn unbind(&self, context: &glow::Context);
+
+    /// Returns the uniforms setting the transform and clip of the commands drawn with the pipeline.
+    fn state(&self) -> &state::Uniforms;
 }
@@ -0,0 +1,353 @@
+use ::renderer::{FillRule, LineCap, LineJoin, Mesh, Path, Segment, Stroke};
+use lyon::{
+    geom::{point, Angle, Arc},
+    path::{iterator::PathIterator, PathEvent},
+    tessellation::{
+        self, FillGeometryBuilder, FillOptions, FillTessellator, FillVertex, GeometryBuilder,
+        GeometryBuilderError, StrokeGeometryBuilder, StrokeOptions, StrokeTessellator,
+        StrokeVertex, VertexId,
+    },
+};
+
+use crate::Error;
+
+/// The maximum distance in physical pixels between a curve and the lines approximating it.
+const TOLERANCE: f32 = 0.1;
+
+/// Turns paths into meshes.
+///
+/// The tessellators keep their allocations between paths, so the tessellator is kept by the renderer.
+#[derive(Default)]
+pub struct Tessellator {
+    fill: FillTessellator,
+    stroke: StrokeTessellator,
+}
+
+impl Tessellator {
+    /// Tessellates the inside of a path.
+    ///
+    /// Returns an error if the path could not be tessellated, in which case no part of the path should be drawn.
+    pub fn fill(&mut self, path: &Path, rule: FillRule, color: [f32; 4]) -> Result<Mesh, Error> {
+        let rule = match rule {
+            FillRule::NonZero => tessellation::FillRule::NonZero,
+            FillRule::EvenOdd => tessellation::FillRule::EvenOdd,
+        };
+        let options = FillOptions::tolerance(TOLERANCE).with_fill_rule(rule);
+
+        let mut mesh = Mesh::default();
+        let result = self.fill.tessellate_path(
+            &convert(path),
+            &options,
+            &mut MeshBuilder {
+                mesh: &mut mesh,
+                color,
+            },
+        );
+
+        result.map_err(|err| Error::Tessellation(err.to_string()))?;
+        Ok(mesh)
+    }
+
+    /// Tessellates the outline of a path.
+    ///
+    /// Returns an error if the path could not be tessellated, in which case no part of the path should be drawn.
+    pub fn stroke(&mut self, path: &Path, stroke: &Stroke, color: [f32; 4]) -> Result<Mesh, Error> {
+        let join = match stroke.join {
+            LineJoin::Miter => tessellation::LineJoin::Miter,
+            LineJoin::Round => tessellation::LineJoin::Round,
+            LineJoin::Bevel => tessellation::LineJoin::Bevel,
+        };
+        let cap = match stroke.cap {
+            LineCap::Butt => tessellation::LineCap::Butt,
+            LineCap::Round => tessellation::LineCap::Round,
+            LineCap::Square => tessellation::LineCap::Square,
+        };
+        let options = StrokeOptions::tolerance(TOLERANCE)
+            .with_line_width(stroke.width)
+            .with_line_join(join)
+            .with_line_cap(cap)
+            // lyon panics on a limit below 1, which would bevel every join anyway.
+            .with_miter_limit(stroke.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT));
+
+        let mut path = convert(path);
+
+        if !stroke.dashes.is_empty() {
+            path = dash(&path, &stroke.dashes, stroke.dash_offset);
+        }
+
+        let mut mesh = Mesh::default();
+        let result = self.stroke.tessellate_path(
+            &path,
+            &options,
+            &mut MeshBuilder {
+                mesh: &mut mesh,
+                color,
+            },
+        );
+
+        result.map_err(|err| Error::Tessellation(err.to_string()))?;
+        Ok(mesh)
+    }
+}
+
+fn convert(path: &Path) -> lyon::path::Path {
+    let mut builder = lyon::path::Path::builder();
+    let mut open = false;
+    // Where the next sub-path begins, if known.
+    let mut start = None;
+
+    for &segment in path.segments() {
+        match segment {
+            Segment::MoveTo([x, y]) => {
+                if open {
+                    builder.end(false);
+                    open = false;
+                }
+
+                start = Some(point(x, y));
+                continue;
+            }
+
+            Segment::Close => {
+                if open {
+                    builder.end(true);
+                    open = false;
+                }
+
+                continue;
+            }
+
+            _ => (),
+        }
+
+        let arc = match segment {
+            Segment::Arc {
+                center,
+                radii,
+                start_angle,
+                sweep_angle,
+            } => Some(Arc {
+                center: center.into(),
+                radii: radii.into(),
+                start_angle: Angle::radians(start_angle),
+                sweep_angle: Angle::radians(sweep_angle),
+                x_rotation: Angle::zero(),
+            }),
+
+            _ => None,
+        };
+
+        if !open {
+            let at = start
+                .or_else(|| arc.map(|arc| arc.from()))
+                .unwrap_or(point(0.0, 0.0));
+            builder.begin(at);
+            // A closed sub-path is continued from it's start.
+            start = Some(at);
+            open = true;
+        }
+
+        match segment {
+            Segment::LineTo(to) => {
+                builder.line_to(to.into());
+            }
+
+            Segment::QuadTo { control, to } => {
+                builder.quadratic_bezier_to(control.into(), to.into());
+            }
+
+            Segment::CubicTo {
+                control1,
+                control2,
+                to,
+            } => {
+                builder.cubic_bezier_to(control1.into(), control2.into(), to.into());
+            }
+
+            Segment::Arc { .. } => {
+                let arc = arc.unwrap();
+                builder.line_to(arc.from());
+                arc.for_each_cubic_bezier(&mut |curve| {
+                    builder.cubic_bezier_to(curve.ctrl1, curve.ctrl2, curve.to);
+                });
+            }
+
+            Segment::MoveTo(_) | Segment::Close => unreachable!(),
+        }
+    }
+
+    if open {
+        builder.end(false);
+    }
+
+    builder.build()
+}
+
+/// Splits the sub-paths of a path into dashes made of lines approximating the curves.
+fn dash(path: &lyon::path::Path, dashes: &[f32], offset: f32) -> lyon::path::Path {
+    // A pattern with an odd number of lengths would swap dashes and gaps every time it repeats.
+    let dashes = match dashes.len() % 2 {
+        0 => dashes.to_vec(),
+        _ => dashes.repeat(2),
+    };
+    let total = dashes.iter().sum::<f32>();
+
+    // A pattern without length would never advance.
+    if dashes.iter().any(|&length| length < 0.0) || total <= 0.0 || !total.is_finite() {
+        return path.clone();
+    }
+
+    let mut builder = lyon::path::Path::builder();
+    // The current dash or gap of the pattern and how much of it remains.
+    let mut index = 0;
+    let mut remaining = 0.0;
+    let mut drawing = false;
+
+    for event in path.iter().flattened(TOLERANCE) {
+        let (from, to) = match event {
+            PathEvent::Begin { at } => {
+                // Every sub-path starts at the offset into the pattern.
+                let mut offset = offset.rem_euclid(total);
+                index = 0;
+
+                while offset >= dashes[index] {
+                    offset -= dashes[index];
+                    index = (index + 1) % dashes.len();
+                }
+
+                remaining = dashes[index] - offset;
+
+                if index % 2 == 0 {
+                    builder.begin(at);
+                    drawing = true;
+                }
+
+                continue;
+            }
+
+            PathEvent::Line { from, to } => (from, to),
+            PathEvent::End {
+                last,
+                first,
+                close: true,
+            } => (last, first),
+            PathEvent::End { .. } => {
+                if drawing {
+                    builder.end(false);
+                    drawing = false;
+                }
+
+                continue;
+            }
+
+            // The path is flattened, so curves do not occur.
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => continue,
+        };
+
+        let length = (to - from).length();
+        let mut distance = 0.0;
+
+        while distance < length {
+            let step = f32::min(remaining, length - distance);
+            distance += step;
+            remaining -= step;
+
+            let position = from.lerp(to, distance / length);
+
+            if drawing {
+                builder.line_to(position);
+            }
+
+            if remaining <= 0.0 {
+                if drawing {
+                    builder.end(false);
+                    drawing = false;
+                }
+
+                index = (index + 1) % dashes.len();
+                remaining = dashes[index];
+
+                if index % 2 == 0 {
+                    builder.begin(position);
+                    drawing = true;
+                }
+            }
+        }
+
+        // The closing segment ends the sub-path.
+        if matches!(event, PathEvent::End { .. }) && drawing {
+            builder.end(false);
+            drawing = false;
+        }
+    }
+
+    builder.build()
+}
+
+/// Writes the vertices and triangles of a tessellated path into a mesh.
+struct MeshBuilder<'a> {
+    mesh: &'a mut Mesh,
+    color: [f32; 4],
+}
+
+impl GeometryBuilder for MeshBuilder<'_> {
+    fn add_triangle(&mut self, a: VertexId, b: VertexId, c: VertexId) {
+        self.mesh.push_triangle(a.0, b.0, c.0);
+    }
+
+    fn abort_geometry(&mut self) {
+        *self.mesh = Mesh::default();
+    }
+}
+
+impl FillGeometryBuilder for MeshBuilder<'_> {
+    fn add_fill_vertex(&mut self, vertex: FillVertex) -> Result<VertexId, GeometryBuilderError> {
+        let position = vertex.position();
+        let index = self.mesh.push_vertex([position.x, position.y], self.color);
+        Ok(VertexId(index))
+    }
+}
+
+impl StrokeGeometryBuilder for MeshBuilder<'_> {
+    fn add_stroke_vertex(
+        &mut self,
+        vertex: StrokeVertex,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        let position = vertex.position();
+        let index = self.mesh.push_vertex([position.x, position.y], self.color);
+        Ok(VertexId(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::renderer::{Path, Stroke};
+
+    use super::{convert, dash, Tessellator};
+
+    #[test]
+    fn dashes() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0);
+        path.line_to(100.0, 0.0);
+
+        // Dashes of 10 pixels every 30 pixels, starting halfway into the first dash.
+        let dashed = dash(&convert(&path), &[10.0, 20.0], 5.0);
+        let starts = dashed
+            .iter()
+            .filter_map(|event| match event {
+                lyon::path::PathEvent::Begin { at } => Some(at.x.round()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(starts, [0.0, 25.0, 55.0, 85.0]);
+
+        // The path ends in a gap after the dash from 70 to 80 pixels.
+        let mut stroke = Stroke::new(2.0);
+        stroke.dashes = vec![10.0, 25.0];
+        let mesh = Tessellator::default()
+            .stroke(&path, &stroke, [1.0; 4])
+            .unwrap();
+        assert_eq!(mesh.bounds(), Some([0.0, -1.0, 80.0, 1.0]));
+    }
+}
@@ -0,0 +1,92 @@
+use glow::HasContext;
+
+use crate::{create_framebuffer, create_render_texture, Error};
+
+/// The number of unused textures kept for later layers and frames.
+const MAX_FREE: usize = 8;
+
+/// A texture commands are drawn into, with a framebuffer drawing into it.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderTexture {
+    pub texture: glow::Texture,
+    pub framebuffer: glow::Framebuffer,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Keeps the textures of layers and blur passes after they were drawn, so later layers and frames reuse them
+/// rather than creating and deleting textures for every layer.
+#[derive(Debug, Default)]
+pub struct TexturePool {
+    /// The textures which are not in use, the least recently used first.
+    free: Vec<RenderTexture>,
+}
+
+impl TexturePool {
+    /// Returns a texture of a size and binds it's framebuffer.
+    ///
+    /// The contents of a reused texture are what was last drawn into it, so it should be cleared.
+    ///
+    /// SAFETY: The context must be current.
+    pub unsafe fn acquire(
+        &mut self,
+        context: &glow::Context,
+        width: u32,
+        height: u32,
+    ) -> Result<RenderTexture, Error> {
+        let reused = self
+            .free
+            .iter()
+            .rposition(|texture| texture.width == width && texture.height == height);
+
+        if let Some(index) = reused {
+            let texture = self.free.remove(index);
+            context.bind_framebuffer(glow::FRAMEBUFFER, Some(texture.framebuffer));
+            return Ok(texture);
+        }
+
+        let texture = create_render_texture(context, width, height)?;
+
+        match create_framebuffer(context, texture) {
+            Ok(framebuffer) => Ok(RenderTexture {
+                texture,
+                framebuffer,
+                width,
+                height,
+            }),
+
+            Err(err) => {
+                context.delete_texture(texture);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns a texture which is no longer drawn with to the pool.
+    ///
+    /// The least recently used textures are deleted once the pool is full, such as the textures of the previous
+    /// size after a surface is resized.
+    ///
+    /// SAFETY: The context must be current.
+    pub unsafe fn release(&mut self, context: &glow::Context, texture: RenderTexture) {
+        self.free.push(texture);
+
+        if self.free.len() > MAX_FREE {
+            delete(context, self.free.remove(0));
+        }
+    }
+
+    /// Deletes every texture in the pool.
+    ///
+    /// SAFETY: The context must be current.
+    pub unsafe fn clear(&mut self, context: &glow::Context) {
+        for texture in self.free.drain(..) {
+            delete(context, texture);
+        }
+    }
+}
+
+unsafe fn delete(context: &glow::Context, texture: RenderTexture) {
+    context.delete_framebuffer(texture.framebuffer);
+    context.delete_texture(texture.texture);
+}
@@ -1,22 +1,34 @@
-use glam::Mat4;
+use ::renderer::{Fill, Quad};
 use glow::HasContext;
 
-use crate::{create_program, create_shader, ShaderType};
+use crate::{create_program, create_shader, state, ShaderType};
 
 pub struct Pipeline {
     pub program: glow::Program,
     pub attrib_position: u32,
     pub attrib_coords: u32,
-    pub uniform_matrix: glow::UniformLocation,
+    pub uniform_fill: glow::UniformLocation,
     pub uniform_color: glow::UniformLocation,
+    pub uniform_color_end: glow::UniformLocation,
+    pub uniform_gradient_start: glow::UniformLocation,
+    pub uniform_gradient_end: glow::UniformLocation,
     pub uniform_size: glow::UniformLocation,
-    pub uniform_radius: glow::UniformLocation,
-    pub uniform_thickness: glow::UniformLocation,
+    pub uniform_radii: glow::UniformLocation,
+    pub uniform_border_width: glow::UniformLocation,
+    pub uniform_border_color: glow::UniformLocation,
     pub uniform_fade: glow::UniformLocation,
+    pub state: state::Uniforms,
 }
 
-const VERTEX_SHADER: &str = include_str!("shader/quad_es2.vert");
-const FRAGMENT_SHADER: &str = include_str!("shader/quad_es2.frag");
+const VERTEX_SHADER: &str = concat!(
+    include_str!("shader/quad_es2.vert"),
+    include_str!("shader/clip.vert")
+);
+const FRAGMENT_SHADER: &str = concat!(
+    include_str!("shader/quad_es2.frag"),
+    include_str!("shader/distance.frag"),
+    include_str!("shader/clip.frag")
+);
 
 // TODO(Instancing): Gles 3.0+ or GL_EXT_instanced_arrays + GL_EXT_draw_instanced.
 
@@ -46,106 +58,94 @@ impl Pipeline {
             .get_attrib_location(program, "coordinates")
             .ok_or("Failed to get location of attribute \"coordinates\"")?;
 
-        let uniform_matrix = context
-            .get_uniform_location(program, "matrix")
-            .ok_or("Failed to get location of uniform \"matrix\"")?;
-
-        // Uniform for color
-        let uniform_color = context
-            .get_uniform_location(program, "color")
-            .ok_or("Failed to get location of uniform \"color\"")?;
-
-        let uniform_size = context
-            .get_uniform_location(program, "size")
-            .ok_or("Failed to get location of uniform \"size\"")?;
-
-        let uniform_radius = context
-            .get_uniform_location(program, "radius")
-            .ok_or("Failed to get location of uniform \"radius\"")?;
-
-        let uniform_thickness = context
-            .get_uniform_location(program, "thickness")
-            .ok_or("Failed to get location of uniform \"thickness\"")?;
-
-        let uniform_fade = context
-            .get_uniform_location(program, "fade")
-            .ok_or("Failed to get location of uniform \"fade\"")?;
+        let uniform = |name: &str| {
+            context
+                .get_uniform_location(program, name)
+                .ok_or_else(|| format!("Failed to get location of uniform \"{}\"", name))
+        };
 
         Ok(Self {
             program,
             attrib_position,
             attrib_coords,
-            uniform_matrix,
-            uniform_color,
-            uniform_size,
-            uniform_radius,
-            uniform_thickness,
-            uniform_fade,
+            uniform_fill: uniform("fill")?,
+            uniform_color: uniform("color")?,
+            uniform_color_end: uniform("color_end")?,
+            uniform_gradient_start: uniform("gradient_start")?,
+            uniform_gradient_end: uniform("gradient_end")?,
+            uniform_size: uniform("size")?,
+            uniform_radii: uniform("radii")?,
+            uniform_border_width: uniform("border_width")?,
+            uniform_border_color: uniform("border_color")?,
+            uniform_fade: uniform("fade")?,
+            state: state::Uniforms::new(context, program)?,
         })
     }
 
+    /// Draws a quad in physical pixels.
+    ///
     /// SAFETY: The pipeline must be bound.
-    pub unsafe fn draw(
-        &self,
-        context: &glow::Context,
-        position: (u32, u32),
-        size: (u32, u32),
-        color: [f32; 4],
-    ) {
-        let x = position.0 as f32;
-        let y = position.1 as f32;
-
-        let width = size.0 as f32;
-        let height = size.1 as f32;
-
-        let verts: [f32; 30] = [
+    pub unsafe fn draw(&self, context: &glow::Context, quad: &Quad) {
+        let Quad {
             x,
             y,
-            0.0,
-            -1.0,
-            -1.0, // 1
-            width + x,
-            y,
-            0.0,
-            -1.0,
-            1.0, // 2
-            width + x,
-            height + y,
-            0.0,
-            1.0,
-            1.0, // 3
+            width,
+            height,
+            ..
+        } = *quad;
+
+        // The position followed by the local position of each vertex.
+        #[rustfmt::skip]
+        let verts: [f32; 30] = [
+            x, y, 0.0, -1.0, -1.0,
+            x + width, y, 0.0, 1.0, -1.0,
+            x + width, y + height, 0.0, 1.0, 1.0,
             //
-            x,
-            y,
-            0.0,
-            -1.0,
-            -1.0,
-            width + x,
-            height + y,
-            0.0,
-            1.0,
-            1.0,
-            x,
-            height + y,
-            0.0,
-            1.0,
-            -1.0,
+            x, y, 0.0, -1.0, -1.0,
+            x + width, y + height, 0.0, 1.0, 1.0,
+            x, y + height, 0.0, -1.0, 1.0,
         ];
 
-        context.uniform_4_f32_slice(Some(&self.uniform_color), &color);
-        let ratio = if width > height {
-            [1.0, width / height]
-        } else if height > width {
-            [height / width, 1.0]
-        } else {
-            [1.0, 1.0]
+        let (fill, color, color_end, start, end) = match quad.fill {
+            Fill::Solid(color) => (0.0, color, color, [0.0; 2], [0.0; 2]),
+            Fill::LinearGradient(gradient) => (
+                1.0,
+                gradient.start_color,
+                gradient.end_color,
+                gradient.start,
+                gradient.end,
+            ),
+            Fill::RadialGradient(gradient) => (
+                2.0,
+                gradient.inner_color,
+                gradient.outer_color,
+                gradient.center,
+                gradient.radius,
+            ),
         };
-        context.uniform_2_f32_slice(Some(&self.uniform_size), &ratio);
 
-        // TODO: Parameters to adjust these uniforms
-        context.uniform_1_f32(Some(&self.uniform_radius), 0.2);
-        context.uniform_1_f32(Some(&self.uniform_thickness), 1.0); // 0.0725
-        context.uniform_1_f32(Some(&self.uniform_fade), 0.006);
+        context.uniform_1_f32(Some(&self.uniform_fill), fill);
+        context.uniform_4_f32_slice(Some(&self.uniform_color), &color);
+        context.uniform_4_f32_slice(Some(&self.uniform_color_end), &color_end);
+        context.uniform_2_f32_slice(Some(&self.uniform_gradient_start), &start);
+        context.uniform_2_f32_slice(Some(&self.uniform_gradient_end), &end);
+        context.uniform_2_f32(Some(&self.uniform_size), width, height);
+
+        let radii = quad.radii;
+        context.uniform_4_f32(
+            Some(&self.uniform_radii),
+            radii.top_left,
+            radii.top_right,
+            radii.bottom_right,
+            radii.bottom_left,
+        );
+
+        let (border_width, border_color) = quad
+            .border
+            .map_or((0.0, [0.0; 4]), |border| (border.width, border.color));
+        context.uniform_1_f32(Some(&self.uniform_border_width), border_width);
+        context.uniform_4_f32_slice(Some(&self.uniform_border_color), &border_color);
+        context.uniform_1_f32(Some(&self.uniform_fade), quad.softness);
 
         // Allocate a buffer to draw into
         // TODO: Preallocation of buffers.
@@ -180,21 +180,10 @@ impl Pipeline {
 }
 
 impl crate::Pipeline for Pipeline {
-    unsafe fn bind(&self, context: &glow::Context, width: u32, height: u32) {
+    unsafe fn bind(&self, context: &glow::Context) {
         context.use_program(Some(self.program));
         context.enable_vertex_attrib_array(self.attrib_position);
         context.enable_vertex_attrib_array(self.attrib_coords);
-        // Set the color to a default value
-        context.uniform_4_f32_slice(Some(&self.uniform_color), &[1.0, 1.0, 1.0, 1.0]);
-
-        let matrix = Mat4::orthographic_rh(0.0, width as f32, 0.0, height as f32, -1.0, 1.0);
-
-        // Flip to compensate for the OpenGL coordinate system
-        let matrix = Mat4::from_rotation_x(std::f32::consts::PI) * matrix;
-        let array = matrix.to_cols_array();
-
-        // Set the size of the viewport
-        context.uniform_matrix_4_f32_slice(Some(&self.uniform_matrix), false, &array);
     }
 
     unsafe fn unbind(&self, context: &glow::Context) {
@@ -202,4 +191,8 @@ impl crate::Pipeline for Pipeline {
         context.disable_vertex_attrib_array(self.attrib_coords);
         context.use_program(None);
     }
+
+    fn state(&self) -> &state::Uniforms {
+        &self.state
+    }
 }
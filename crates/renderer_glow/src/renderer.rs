@@ -1,19 +1,172 @@
-use renderer::Renderer;
+use std::borrow::Cow;
 
-use crate::{Error, GlowImage, GlowRenderer};
+use glow::HasContext;
+use renderer::{Alpha, CreateImage, Format, ImageData, Renderer};
+
+use crate::{Error, GlowEncoder, GlowImage, GlowRenderer};
+
+/// `GL_BGRA_EXT` from `GL_EXT_texture_format_BGRA8888`.
+const BGRA_EXT: u32 = 0x80E1;
 
 impl Renderer for GlowRenderer {
-    // TODO: Proper error type
     type Error = Error;
     type Image = GlowImage;
 }
 
 impl renderer::Image<GlowRenderer> for GlowImage {
     fn width(&self) -> u32 {
-        todo!()
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl CreateImage for GlowRenderer {
+    fn create_image(&mut self, data: ImageData<'_>) -> Result<GlowImage, Error> {
+        let (format, pixels) = self.pixels(&data)?;
+        let context = &self.context;
+
+        unsafe {
+            let id = context.create_texture().map_err(Error::Gl)?;
+            context.bind_texture(glow::TEXTURE_2D, Some(id));
+            // Rows are tightly packed, regardless of the width.
+            context.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            context.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                format as i32,
+                data.width as i32,
+                data.height as i32,
+                0,
+                format,
+                glow::UNSIGNED_BYTE,
+                Some(&pixels),
+            );
+            context.bind_texture(glow::TEXTURE_2D, None);
+
+            Ok(GlowImage {
+                context: context.clone(),
+                id,
+                width: data.width,
+                height: data.height,
+                format: Some(data.format),
+                is_external: false,
+                owned: true,
+            })
+        }
+    }
+
+    fn update_image(
+        &mut self,
+        image: &mut GlowImage,
+        x: u32,
+        y: u32,
+        data: ImageData<'_>,
+    ) -> Result<(), Error> {
+        // Images created from a texture id are assumed to be RGBA.
+        if image.format.unwrap_or(Format::Rgba8) != data.format || image.is_external {
+            return Err(Error::FormatMismatch);
+        }
+
+        let right = x.checked_add(data.width);
+        let bottom = y.checked_add(data.height);
+
+        if right.is_none_or(|right| right > image.width)
+            || bottom.is_none_or(|bottom| bottom > image.height)
+        {
+            return Err(Error::OutOfBounds);
+        }
+
+        let (format, pixels) = self.pixels(&data)?;
+        let context = &self.context;
+
+        unsafe {
+            context.bind_texture(glow::TEXTURE_2D, Some(image.id));
+            context.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            context.tex_sub_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                x as i32,
+                y as i32,
+                data.width as i32,
+                data.height as i32,
+                format,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(&pixels),
+            );
+            context.bind_texture(glow::TEXTURE_2D, None);
+        }
+
+        Ok(())
+    }
+}
+
+impl GlowRenderer {
+    /// Returns the OpenGL format of image data and the pixels with rows tightly packed.
+    ///
+    /// OpenGL ES 2 can not skip the padding at the end of rows, so padded rows are copied. BGRA pixels are swapped
+    /// to RGBA if `GL_EXT_texture_format_BGRA8888` is not supported, and straight colors are premultiplied.
+    fn pixels<'a>(&self, data: &ImageData<'a>) -> Result<(u32, Cow<'a, [u8]>), Error> {
+        if !data.is_valid() {
+            return Err(Error::InvalidImageData);
+        }
+
+        let (format, swap) = match data.format {
+            Format::Rgba8 => (glow::RGBA, false),
+            Format::Bgra8 if self.bgra => (BGRA_EXT, false),
+            Format::Bgra8 => (glow::RGBA, true),
+            Format::A8 => (glow::ALPHA, false),
+        };
+
+        let premultiply = data.alpha == Alpha::Straight && data.format != Format::A8;
+        let row = data.width as usize * data.format.bytes_per_pixel();
+        let size = row * data.height as usize;
+
+        let pixels = if data.stride == row && !swap && !premultiply {
+            Cow::Borrowed(&data.data[..size])
+        } else {
+            let mut pixels = Vec::with_capacity(size);
+
+            for y in 0..data.height {
+                pixels.extend_from_slice(data.row(y).unwrap());
+            }
+
+            if swap {
+                for pixel in pixels.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+            }
+
+            if premultiply {
+                for pixel in pixels.chunks_exact_mut(4) {
+                    let alpha = pixel[3] as u32;
+
+                    for component in &mut pixel[..3] {
+                        *component = ((*component as u32 * alpha + 127) / 255) as u8;
+                    }
+                }
+            }
+
+            Cow::Owned(pixels)
+        };
+
+        Ok((format, pixels))
+    }
+}
+
+impl Renderer for GlowEncoder<'_> {
+    type Error = Error;
+    type Image = GlowImage;
+}
+
+impl renderer::Image<GlowEncoder<'_>> for GlowImage {
+    fn width(&self) -> u32 {
+        self.width
     }
 
     fn height(&self) -> u32 {
-        todo!()
+        self.height
     }
 }
@@ -0,0 +1,41 @@
+#version 100
+
+precision highp float;
+
+// The largest number of samples taken on each side of a fragment.
+#define MAX_SAMPLES 32
+
+uniform sampler2D image;
+
+// The distance between two texels along the direction of the blur, in texture coordinates.
+uniform vec2 direction;
+
+// The standard deviation of the blur in texels.
+uniform float sigma;
+
+varying vec2 uv;
+
+// Blurs the premultiplied colors of the image along one direction with a Gaussian.
+void main() {
+    // Past 3 standard deviations the weights are negligible.
+    float extent = ceil(3.0 * sigma);
+    // Large blurs skip texels, which are interpolated by the linear filter.
+    float spacing = max(1.0, extent / float(MAX_SAMPLES));
+
+    vec4 sum = texture2D(image, uv);
+    float total = 1.0;
+
+    for (int i = 1; i <= MAX_SAMPLES; i++) {
+        float x = float(i) * spacing;
+
+        if (x > extent) {
+            break;
+        }
+
+        float weight = exp(-(x * x) / (2.0 * sigma * sigma));
+        sum += (texture2D(image, uv + direction * x) + texture2D(image, uv - direction * x)) * weight;
+        total += 2.0 * weight;
+    }
+
+    gl_FragColor = sum / total;
+}
@@ -0,0 +1,11 @@
+#version 100
+
+// The position in normalized device coordinates, covering the framebuffer.
+attribute vec2 position;
+
+varying vec2 uv;
+
+void main() {
+    uv = position * 0.5 + 0.5;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
@@ -0,0 +1,41 @@
+// Clips to rectangles with rounded corners, which may be transformed.
+//
+// This is appended to the fragment shaders after `distance.frag`, and the fragment shaders declare `float _clip();`
+// before using it. The vertex shaders pass the positions in the coordinates of the clips with `clip.vert`.
+
+// The number of clips applied by the shaders, see `MAX_MASKS` in `state.rs`.
+#define MAX_CLIPS 4
+
+// The number of clips which are applied, from the outermost to the innermost.
+uniform float clip_count;
+
+// The clipped areas in the coordinates of the clips, as the x, y, width and height.
+uniform vec4 clip_rect[MAX_CLIPS];
+
+// The radii of the corners, in the order top left, top right, bottom right and bottom left.
+uniform vec4 clip_radii[MAX_CLIPS];
+
+// The position in the coordinates of each clip.
+varying vec2 clip_position[MAX_CLIPS];
+
+// Returns the distance from a rectangle with rounded corners, see `distance.frag`.
+float _distance(vec2 position, vec2 half_size, vec4 radii);
+
+// Returns how much of the fragment is inside of every clip, from 0.0 outside to 1.0 inside.
+float _clip() {
+    float coverage = 1.0;
+
+    for (int i = 0; i < MAX_CLIPS; i++) {
+        if (float(i) >= clip_count) {
+            break;
+        }
+
+        vec2 half_size = clip_rect[i].zw / 2.0;
+        float distance = _distance(clip_position[i] - clip_rect[i].xy - half_size, half_size, clip_radii[i]);
+
+        // Smooth the edge over one unit of the clip.
+        coverage *= clamp(0.5 - distance, 0.0, 1.0);
+    }
+
+    return coverage;
+}
@@ -0,0 +1,19 @@
+// Maps positions to the coordinates of the clips, see `clip.frag`.
+//
+// This is appended to the vertex shaders, which declare `void _clip(vec2 position);` before using it.
+
+// The number of clips applied by the shaders, see `MAX_MASKS` in `state.rs`.
+#define MAX_CLIPS 4
+
+// Maps positions to the coordinates of each clip.
+uniform mat3 clip_matrix[MAX_CLIPS];
+
+// The position in the coordinates of each clip.
+varying vec2 clip_position[MAX_CLIPS];
+
+// Passes the position in the coordinates of each clip to the fragment shader.
+void _clip(vec2 position) {
+    for (int i = 0; i < MAX_CLIPS; i++) {
+        clip_position[i] = (clip_matrix[i] * vec3(position, 1.0)).xy;
+    }
+}
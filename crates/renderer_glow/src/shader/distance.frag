@@ -0,0 +1,20 @@
+// SDF for a rectangle with rounded corners.
+//
+// This is appended to the fragment shaders, which declare `float _distance(vec2, vec2, vec4);` before using it.
+//
+// The `half_size` is half the width and height of the rectangle in pixels and `position` is relative to the center.
+// The radii are in the order top left, top right, bottom right and bottom left. The distance is negative inside of
+// the rectangle.
+//
+// Inspired by https://www.shadertoy.com/view/WtdSDs
+float _distance(vec2 position, vec2 half_size, vec4 radii) {
+    float radius = position.x < 0.0
+        ? (position.y < 0.0 ? radii.x : radii.w)
+        : (position.y < 0.0 ? radii.y : radii.z);
+
+    // A radius larger than the rectangle would produce a smaller shape.
+    radius = min(radius, min(half_size.x, half_size.y));
+
+    vec2 q = abs(position) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, 0.0)) - radius;
+}
@@ -1,8 +1,26 @@
 #version 100
 
 precision mediump float;
-uniform vec4 u_color;
+
+uniform sampler2D image;
+
+// The color each pixel is multiplied with, including the opacity. Unlike the image, the tint is not premultiplied.
+uniform vec4 tint;
+
+// 1.0 if only the alpha of the image is sampled, which draws the image as a white mask.
+uniform float mask;
+
+varying vec2 uv;
+
+// Returns how much of the fragment is inside of the clip, see `clip.frag`.
+float _clip();
 
 void main() {
-    gl_FragColor = u_color;
+    vec4 texel = texture2D(image, uv);
+
+    if (mask > 0.5) {
+        texel = vec4(texel.a);
+    }
+
+    gl_FragColor = texel * vec4(tint.rgb * tint.a, tint.a) * _clip();
 }
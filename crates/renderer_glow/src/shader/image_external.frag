@@ -0,0 +1,28 @@
+#version 100
+#extension GL_OES_EGL_image_external : require
+
+precision mediump float;
+
+// External images must be sampled with `samplerExternalOES`, otherwise this is the same as `image.frag`.
+uniform samplerExternalOES image;
+
+// The color each pixel is multiplied with, including the opacity. Unlike the image, the tint is not premultiplied.
+uniform vec4 tint;
+
+// 1.0 if only the alpha of the image is sampled, which draws the image as a white mask.
+uniform float mask;
+
+varying vec2 uv;
+
+// Returns how much of the fragment is inside of the clip, see `clip.frag`.
+float _clip();
+
+void main() {
+    vec4 texel = texture2D(image, uv);
+
+    if (mask > 0.5) {
+        texel = vec4(texel.a);
+    }
+
+    gl_FragColor = texel * vec4(tint.rgb * tint.a, tint.a) * _clip();
+}
@@ -2,69 +2,87 @@
 
 precision highp float;
 
-// TODO: Replace with vertex attribute to allow for gradients
+// The kind of fill.
+//
+// 0.0 fills the quad with `color`.
+// 1.0 fills the quad with a linear gradient from `color` at `gradient_start` to `color_end` at `gradient_end`.
+// 2.0 fills the quad with a radial gradient from `color` at the center `gradient_start` to `color_end` at the
+// radius `gradient_end`.
+uniform float fill;
+
 uniform vec4 color;
+uniform vec4 color_end;
 
-// The size of the shape as a ratio.
-//
-// One value must always be 1.0. The other value may be greater than 1.0 to create a rectangle.
+// The positions of the gradient as fractions of the size of the quad.
+uniform vec2 gradient_start;
+uniform vec2 gradient_end;
+
+// The size of the quad in pixels.
 uniform vec2 size;
 
-// The radius in a fraction.
-//
-// A value of 0.0 indicates that a quad should be drawn.
-// A value of 1.0 indicates that the radius of the curves should occupy the entire width or height (whichever is shorter).
-// Values between 0.0 and 1.0 will result in a quad with curved edges.
-uniform float radius;
+// The radii of the corners in pixels, in the order top left, top right, bottom right and bottom left.
+uniform vec4 radii;
 
-// The thickness of the shape.
+// The width of the border in pixels.
 //
-// A thickness of 1.0 means the shape will be solid.
-// A thickness of 0.0 means the shape will be invisible.
-//
-// Note: With smaller values for radius, the inner border might be a right angle.
-//
-// If the thickness is greater or equal to the radius, then the inside of the shape will be solid.
-uniform float thickness;
+// A width of 0.0 draws no border.
+uniform float border_width;
+uniform vec4 border_color;
 
-// The fade applied to edges of the shape.
+// The width in pixels over which the edges of the shape fade out.
 //
 // This value is used to smooth the edges of the shape and remove jagged edges.
 //
 // A fade of 0.0 will not smooth the shape.
-// High fade values will cause the shape to become invisible.
 uniform float fade;
 
-// The local position in normalized pixel coordinates.
-//
-// This is a value between 0 and 1.
+// The local position, from (-1, -1) in the top left corner to (1, 1) in the bottom right corner.
 varying vec2 local_position;
 
-// SDF for a rectangle.
-//
-// The `half_perimeter` is half the width and height of the rectangle in normalized pixel coordinates.
-// The `radius` is the radius of the corners in normalized pixel coordinates.
-//
-// Inspired by https://www.shadertoy.com/view/WtdSDs
-float _distance(vec2 position, vec2 size, float radius) {
-    vec2 q = abs(position) - size + radius;
-    return length(max(q, 0.0)) - radius;
+// Returns how much of the fragment is inside of the clip, see `clip.frag`.
+float _clip();
+
+// Returns the distance from a rectangle with rounded corners, see `distance.frag`.
+float _distance(vec2 position, vec2 half_size, vec4 radii);
+
+vec4 _premultiply(vec4 color) {
+    return vec4(color.rgb * color.a, color.a);
+}
+
+// Returns the premultiplied color of the fill.
+vec4 _fill(vec2 uv) {
+    if (fill < 0.5) {
+        return _premultiply(color);
+    }
+
+    float t;
+
+    if (fill < 1.5) {
+        vec2 direction = gradient_end - gradient_start;
+        t = dot(uv - gradient_start, direction) / max(dot(direction, direction), 0.000001);
+    } else {
+        t = length((uv - gradient_start) / max(gradient_end, vec2(0.000001)));
+    }
+
+    // Interpolate premultiplied colors, so a gradient to transparent does not darken.
+    return mix(_premultiply(color), _premultiply(color_end), clamp(t, 0.0, 1.0));
 }
 
 void main() {
-    // Subtract half the fade to ensure the rectangle is not clipped.
-    vec2 size = size - (fade / 2.0);
+    vec2 half_size = size / 2.0;
+    float distance = _distance(local_position * half_size, half_size, radii);
 
-    // Scale the position based on the size of the rectangle in normalized pixel coordinates.
-    // Then subtract half the size to center the position within normalized pixel coordinates.
+    // Fade out inside of the edge so the shape is not cut off by the edges of the quad.
+    float alpha = 1.0 - smoothstep(-fade, 0.0, distance);
 
-    float distance = _distance(local_position * size, size, radius);
-    float smoothed_alpha = 1.0 - smoothstep(0.0, fade, distance);
-    float border_alpha = 1.0 - smoothstep(thickness - fade, thickness, abs(distance));
+    vec4 inside = _fill(local_position * 0.5 + 0.5);
 
-    const vec4 CLEAR = vec4(0.0);
+    if (border_width > 0.0) {
+        float border = smoothstep(-border_width - fade, -border_width, distance);
+        inside = mix(inside, _premultiply(border_color), border);
+    }
 
-    gl_FragColor = mix(CLEAR, mix(CLEAR, color, border_alpha), smoothed_alpha);
+    gl_FragColor = inside * (alpha * _clip());
 
     if (gl_FragColor.a == 0.0) {
         discard;
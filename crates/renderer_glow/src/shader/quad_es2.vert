@@ -3,15 +3,18 @@
 precision mediump float;
 
 uniform mat4 matrix;
-uniform vec2 size;
 
 attribute vec4 position;
 attribute vec2 coordinates;
 
-// The local position within the normalized pixel coordinates.
+// The local position, from (-1, -1) in the top left corner to (1, 1) in the bottom right corner.
 varying vec2 local_position;
 
+// Passes the position to the clips, see `clip.vert`.
+void _clip(vec2 position);
+
 void main() {
+    _clip(position.xy);
     local_position = coordinates;
     gl_Position = matrix * vec4(position.xyz, 1.0);
 }
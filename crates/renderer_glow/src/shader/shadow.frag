@@ -0,0 +1,83 @@
+#version 100
+
+precision highp float;
+
+// The rectangle casting the shadow in pixels, as the x, y, width and height.
+uniform vec4 rect;
+
+// The radii of the corners in pixels, in the order top left, top right, bottom right and bottom left.
+uniform vec4 radii;
+
+// The shape of the shadow before it is blurred, which is the rectangle moved by the offset and grown by the spread.
+uniform vec4 shadow_rect;
+uniform vec4 shadow_radii;
+
+// The standard deviation of the blur in pixels.
+//
+// A deviation of 0.0 draws a shadow with sharp edges.
+uniform float sigma;
+
+uniform vec4 color;
+
+// 1.0 if the shadow is cast inside of the rectangle.
+uniform float inset;
+
+// The position in pixels.
+varying vec2 v_position;
+
+// Returns how much of the fragment is inside of the clip, see `clip.frag`.
+float _clip();
+
+// Returns the distance from a rectangle with rounded corners, see `distance.frag`.
+float _distance(vec2 position, vec2 half_size, vec4 radii);
+
+// Returns the distance from a rectangle with rounded corners, which is negative inside of the rectangle.
+float _rect_distance(vec4 rect, vec4 radii) {
+    vec2 half_size = rect.zw / 2.0;
+    return _distance(v_position - rect.xy - half_size, half_size, radii);
+}
+
+// Approximation of the error function, with an error below 0.0005.
+//
+// Abramowitz and Stegun, formula 7.1.27.
+float _erf(float x) {
+    float s = sign(x);
+    float a = abs(x);
+    float t = 1.0 + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+    t *= t;
+    return s - s / (t * t);
+}
+
+// Returns how much of the blurred shape covers a fragment at a distance from the edge of the shape.
+//
+// This is the integral of a Gaussian across a straight edge, so it is exact along the sides of the shape and
+// approximates the corners.
+float _coverage(float distance) {
+    if (sigma <= 0.0) {
+        return clamp(0.5 - distance, 0.0, 1.0);
+    }
+
+    return 0.5 - 0.5 * _erf(distance / (sigma * sqrt(2.0)));
+}
+
+void main() {
+    float distance = _rect_distance(rect, radii);
+    float shadow = _coverage(_rect_distance(shadow_rect, shadow_radii));
+    float alpha;
+
+    if (inset > 0.5) {
+        // The shape of the shadow is a hole inside of the rectangle.
+        alpha = (1.0 - shadow) * clamp(0.5 - distance, 0.0, 1.0);
+    } else {
+        // The shadow is not drawn below the rectangle.
+        alpha = shadow * clamp(0.5 + distance, 0.0, 1.0);
+    }
+
+    alpha *= color.a * _clip();
+
+    if (alpha <= 0.0) {
+        discard;
+    }
+
+    gl_FragColor = vec4(color.rgb * alpha, alpha);
+}
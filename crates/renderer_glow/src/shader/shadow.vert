@@ -0,0 +1,18 @@
+#version 100
+
+uniform mat4 matrix;
+
+
+attribute vec2 position;
+
+// The position in pixels before it is transformed.
+varying vec2 v_position;
+
+// Passes the position to the clips, see `clip.vert`.
+void _clip(vec2 position);
+
+void main() {
+    _clip(position);
+    v_position = position;
+    gl_Position = matrix * vec4(position, 0.0, 1.0);
+}
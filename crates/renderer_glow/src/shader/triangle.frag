@@ -2,9 +2,32 @@
 
 precision mediump float;
 
-// TODO: Replace with vertex attribute to allow for gradients
-uniform vec4 u_color;
+uniform sampler2D image;
+
+// 1.0 if the mesh is drawn with an image, which the color is multiplied with.
+uniform float textured;
+
+// 1.0 if only the alpha of the image is sampled, which draws the image as a white mask.
+uniform float mask;
+
+// The premultiplied color interpolated between the vertices of the triangle.
+varying vec4 v_color;
+
+varying vec2 uv;
+
+// Returns how much of the fragment is inside of the clip, see `clip.frag`.
+float _clip();
 
 void main() {
-    gl_FragColor = u_color;
+    vec4 texel = vec4(1.0);
+
+    if (textured > 0.5) {
+        texel = texture2D(image, uv);
+
+        if (mask > 0.5) {
+            texel = vec4(texel.a);
+        }
+    }
+
+    gl_FragColor = texel * v_color * _clip();
 }
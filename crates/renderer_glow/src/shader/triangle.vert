@@ -1,8 +1,23 @@
 #version 100
 
 uniform mat4 matrix;
-attribute vec4 position;
+
+attribute vec2 position;
+attribute vec4 color;
+attribute vec2 coordinates;
+
+varying vec4 v_color;
+
+// The position within the image, as fractions of the size of the image.
+varying vec2 uv;
+
+// Passes the position to the clips, see `clip.vert`.
+void _clip(vec2 position);
 
 void main() {
-    gl_Position = matrix * vec4(position.xyz, 1.0);
+    _clip(position);
+    // Premultiply before the color is interpolated between the vertices.
+    v_color = vec4(color.rgb * color.a, color.a);
+    uv = coordinates;
+    gl_Position = matrix * vec4(position, 0.0, 1.0);
 }
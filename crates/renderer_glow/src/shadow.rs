@@ -0,0 +1,230 @@
+use ::renderer::{BoxShadow, CornerRadii};
+use glow::HasContext;
+
+use crate::{create_program, create_shader, state, ShaderType};
+
+pub struct Pipeline {
+    pub program: glow::Program,
+    pub attrib_position: u32,
+    pub uniform_rect: glow::UniformLocation,
+    pub uniform_radii: glow::UniformLocation,
+    pub uniform_shadow_rect: glow::UniformLocation,
+    pub uniform_shadow_radii: glow::UniformLocation,
+    pub uniform_sigma: glow::UniformLocation,
+    pub uniform_color: glow::UniformLocation,
+    pub uniform_inset: glow::UniformLocation,
+    pub state: state::Uniforms,
+}
+
+/// How far a blurred shadow extends past it's shape, in standard deviations of the blur.
+///
+/// Less than 0.2% of the color remains past 3 deviations.
+const EXTENT: f32 = 3.0;
+
+const VERTEX_SHADER: &str = concat!(
+    include_str!("shader/shadow.vert"),
+    include_str!("shader/clip.vert")
+);
+const FRAGMENT_SHADER: &str = concat!(
+    include_str!("shader/shadow.frag"),
+    include_str!("shader/distance.frag"),
+    include_str!("shader/clip.frag")
+);
+
+impl Pipeline {
+    pub unsafe fn new(context: &glow::Context) -> Result<Self, String> {
+        let vertex = create_shader(context, ShaderType::Vertex, VERTEX_SHADER)?;
+        let fragment = create_shader(context, ShaderType::Fragment, FRAGMENT_SHADER);
+
+        // If the fragment shader failed to compile, ensure the vertex shader is freed.
+        if fragment.is_err() {
+            context.delete_shader(vertex);
+        }
+
+        let fragment = fragment.unwrap();
+        let program = create_program(context, &[vertex, fragment], &[(0, "position")])?;
+
+        // Get the attribute location of the position
+        let attrib_position = context
+            .get_attrib_location(program, "position")
+            .ok_or("Failed to get location of attribute \"position\"")?;
+
+        let uniform = |name: &str| {
+            context
+                .get_uniform_location(program, name)
+                .ok_or_else(|| format!("Failed to get location of uniform \"{}\"", name))
+        };
+
+        Ok(Self {
+            program,
+            attrib_position,
+            uniform_rect: uniform("rect")?,
+            uniform_radii: uniform("radii")?,
+            uniform_shadow_rect: uniform("shadow_rect")?,
+            uniform_shadow_radii: uniform("shadow_radii")?,
+            uniform_sigma: uniform("sigma")?,
+            uniform_color: uniform("color")?,
+            uniform_inset: uniform("inset")?,
+            state: state::Uniforms::new(context, program)?,
+        })
+    }
+
+    /// Draws a shadow in physical pixels.
+    ///
+    /// SAFETY: The pipeline must be bound.
+    pub unsafe fn draw(&self, context: &glow::Context, shadow: &BoxShadow) {
+        let [left, top, right, bottom] = bounds(shadow);
+
+        #[rustfmt::skip]
+        let verts: [f32; 12] = [
+            left, top,
+            right, top,
+            right, bottom,
+            //
+            left, top,
+            right, bottom,
+            left, bottom,
+        ];
+
+        let (shadow_rect, shadow_radii) = shape(shadow);
+        let uniform_radii = |location, radii: CornerRadii| {
+            context.uniform_4_f32(
+                Some(location),
+                radii.top_left,
+                radii.top_right,
+                radii.bottom_right,
+                radii.bottom_left,
+            );
+        };
+
+        context.uniform_4_f32_slice(Some(&self.uniform_rect), &shadow.rect);
+        uniform_radii(&self.uniform_radii, shadow.radii);
+        context.uniform_4_f32_slice(Some(&self.uniform_shadow_rect), &shadow_rect);
+        uniform_radii(&self.uniform_shadow_radii, shadow_radii);
+        context.uniform_1_f32(Some(&self.uniform_sigma), sigma(shadow));
+        context.uniform_4_f32_slice(Some(&self.uniform_color), &shadow.color);
+        context.uniform_1_f32(Some(&self.uniform_inset), shadow.inset as u32 as f32);
+
+        // Allocate a buffer to draw into
+        // TODO: Preallocation of buffers.
+        let buffer = context.create_buffer().unwrap();
+        context.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
+        context.buffer_data_u8_slice(
+            glow::ARRAY_BUFFER,
+            bytemuck::cast_slice(&verts),
+            glow::STATIC_DRAW,
+        );
+
+        context.vertex_attrib_pointer_f32(self.attrib_position, 2, glow::FLOAT, false, 0, 0);
+        context.draw_arrays(glow::TRIANGLES, 0, (verts.len() / 2) as i32);
+
+        context.delete_buffer(buffer);
+    }
+}
+
+impl crate::Pipeline for Pipeline {
+    unsafe fn bind(&self, context: &glow::Context) {
+        context.use_program(Some(self.program));
+        context.enable_vertex_attrib_array(self.attrib_position);
+    }
+
+    unsafe fn unbind(&self, context: &glow::Context) {
+        context.disable_vertex_attrib_array(self.attrib_position);
+        context.use_program(None);
+    }
+
+    fn state(&self) -> &state::Uniforms {
+        &self.state
+    }
+}
+
+/// Returns the area a shadow is drawn to, as the left, top, right and bottom edges.
+pub fn bounds(shadow: &BoxShadow) -> [f32; 4] {
+    // Inset shadows are drawn inside of the rectangle.
+    let [x, y, width, height] = match shadow.inset {
+        true => shadow.rect,
+        false => shape(shadow).0,
+    };
+    let extent = match shadow.inset {
+        true => 0.0,
+        // Include the pixels the edges of the shape partially cover.
+        false => EXTENT * sigma(shadow) + 1.0,
+    };
+
+    [
+        x - extent,
+        y - extent,
+        x + width + extent,
+        y + height + extent,
+    ]
+}
+
+/// Returns the standard deviation of the blur of a shadow.
+fn sigma(shadow: &BoxShadow) -> f32 {
+    shadow.blur_radius.max(0.0) / 2.0
+}
+
+/// Returns the shape of a shadow before it is blurred, as the x, y, width and height, and the radii of the corners.
+///
+/// The spread grows the rectangle and the radii of the corners which are rounded, or shrinks them for inset shadows.
+fn shape(shadow: &BoxShadow) -> ([f32; 4], CornerRadii) {
+    let [x, y, width, height] = shadow.rect;
+    let [offset_x, offset_y] = shadow.offset;
+    let spread = match shadow.inset {
+        true => -shadow.spread,
+        false => shadow.spread,
+    };
+
+    let rect = [
+        x + offset_x - spread,
+        y + offset_y - spread,
+        (width + 2.0 * spread).max(0.0),
+        (height + 2.0 * spread).max(0.0),
+    ];
+
+    let grow = |radius: f32| match radius > 0.0 {
+        true => (radius + spread).max(0.0),
+        false => 0.0,
+    };
+    let radii = CornerRadii {
+        top_left: grow(shadow.radii.top_left),
+        top_right: grow(shadow.radii.top_right),
+        bottom_right: grow(shadow.radii.bottom_right),
+        bottom_left: grow(shadow.radii.bottom_left),
+    };
+
+    (rect, radii)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::renderer::{BoxShadow, CornerRadii};
+
+    use super::{bounds, shape};
+
+    #[test]
+    fn spread() {
+        let mut shadow = BoxShadow::new(10.0, 10.0, 100.0, 50.0, [0.0, 0.0, 0.0, 1.0]);
+        shadow.radii = CornerRadii {
+            top_left: 8.0,
+            ..CornerRadii::ZERO
+        };
+        shadow.offset = [0.0, 4.0];
+        shadow.spread = 2.0;
+
+        let (rect, radii) = shape(&shadow);
+        assert_eq!(rect, [8.0, 12.0, 104.0, 54.0]);
+        assert_eq!((radii.top_left, radii.top_right), (10.0, 0.0));
+
+        // A blur radius of 8 pixels is a standard deviation of 4, which extends 12 pixels past the shape.
+        shadow.blur_radius = 8.0;
+        assert_eq!(bounds(&shadow), [-5.0, -1.0, 125.0, 79.0]);
+
+        // Inset shadows shrink and are drawn inside of the rectangle.
+        shadow.inset = true;
+        let (rect, radii) = shape(&shadow);
+        assert_eq!(rect, [12.0, 16.0, 96.0, 46.0]);
+        assert_eq!(radii.top_left, 6.0);
+        assert_eq!(bounds(&shadow), [10.0, 10.0, 110.0, 60.0]);
+    }
+}
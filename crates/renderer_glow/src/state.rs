@@ -0,0 +1,442 @@
+use ::renderer::{BlendMode, CornerRadii, Transform};
+use glam::{Mat3, Mat4};
+use glow::HasContext;
+
+/// The framebuffer commands are drawn into.
+#[derive(Debug, Clone, Copy)]
+pub struct Target {
+    /// The framebuffer, or [`None`] for the default framebuffer.
+    pub framebuffer: Option<glow::Framebuffer>,
+    pub width: u32,
+    pub height: u32,
+    /// Whether the first row of the framebuffer is the top, as for images, rather than the bottom as for surfaces.
+    pub top_down: bool,
+}
+
+/// The transform, clip and blend mode a command is drawn with.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct State {
+    pub transform: Transform,
+    pub clip: Clip,
+    pub blend_mode: BlendMode,
+}
+
+impl State {
+    /// Returns the area a command drawing into `bounds` may draw to, as the left, top, right and bottom edges in
+    /// physical pixels.
+    pub fn bounds(&self, bounds: [f32; 4]) -> [f32; 4] {
+        let bounds = transform_bounds(&self.transform, bounds);
+
+        match self.clip.scissor {
+            Some(scissor) => intersect(bounds, scissor),
+            None => bounds,
+        }
+    }
+}
+
+/// The number of clips which are not rectangles in physical pixels the shaders apply, see `clip.frag`.
+pub const MAX_MASKS: usize = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Clip {
+    /// The area drawing is limited to with the scissor test, as the left, top, right and bottom edges in physical
+    /// pixels.
+    pub scissor: Option<[f32; 4]>,
+    /// The clips which are not rectangles in physical pixels, from the outermost to the innermost, which are
+    /// applied by the fragment shaders.
+    ///
+    /// Only the innermost [`MAX_MASKS`] of such clips are applied by the shaders, any outer clips are applied by
+    /// the scissor.
+    pub masks: [Option<Mask>; MAX_MASKS],
+}
+
+impl Clip {
+    /// Returns a clip which is clipped to a rectangle transformed by `transform`.
+    pub fn push(&self, transform: &Transform, rect: [f32; 4], radii: CornerRadii) -> Self {
+        let [x, y, width, height] = rect;
+        let bounds = transform_bounds(transform, [x, y, x + width, y + height]);
+        let scissor = match self.scissor {
+            Some(scissor) => intersect(scissor, bounds),
+            None => bounds,
+        };
+
+        let mask = if transform.is_axis_aligned() && radii == CornerRadii::ZERO {
+            // The scissor clips to the rectangle exactly.
+            None
+        } else {
+            // A transform without an inverse collapses the rectangle, so the scissor clips everything.
+            transform.inverse().map(|inverse| Mask {
+                inverse,
+                rect,
+                radii,
+            })
+        };
+
+        let mut masks = self.masks;
+
+        if let Some(mask) = mask {
+            let mask = Some(mask);
+
+            match masks.iter().position(Option::is_none) {
+                Some(index) => masks[index] = mask,
+
+                None => {
+                    // Drop the outermost mask, which the scissor still clips to.
+                    masks.rotate_left(1);
+                    masks[MAX_MASKS - 1] = mask;
+                }
+            }
+        }
+
+        Self {
+            scissor: Some(scissor),
+            masks,
+        }
+    }
+
+    /// Returns the clips applied by the fragment shaders, from the outermost to the innermost.
+    pub fn masks(&self) -> impl Iterator<Item = &Mask> {
+        self.masks.iter().flatten()
+    }
+
+    /// Returns whether everything is clipped.
+    pub fn is_empty(&self) -> bool {
+        self.scissor
+            .is_some_and(|[left, top, right, bottom]| left >= right || top >= bottom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mask {
+    /// Maps physical pixels to the coordinates the clip was pushed in.
+    pub inverse: Transform,
+    /// The clipped area as the x, y, width and height.
+    pub rect: [f32; 4],
+    pub radii: CornerRadii,
+}
+
+/// The uniforms of a program which apply the [`State`].
+pub struct Uniforms {
+    matrix: glow::UniformLocation,
+    clip_count: glow::UniformLocation,
+    clip_matrix: glow::UniformLocation,
+    clip_rect: glow::UniformLocation,
+    clip_radii: glow::UniformLocation,
+}
+
+impl Uniforms {
+    pub unsafe fn new(context: &glow::Context, program: glow::Program) -> Result<Self, String> {
+        let uniform = |name: &str| {
+            context
+                .get_uniform_location(program, name)
+                .ok_or_else(|| format!("Failed to get location of uniform \"{}\"", name))
+        };
+
+        Ok(Self {
+            matrix: uniform("matrix")?,
+            clip_count: uniform("clip_count")?,
+            clip_matrix: uniform("clip_matrix")?,
+            clip_rect: uniform("clip_rect")?,
+            clip_radii: uniform("clip_radii")?,
+        })
+    }
+
+    /// Sets the uniforms for drawing into a target.
+    ///
+    /// SAFETY: The program must be bound.
+    pub unsafe fn set(&self, context: &glow::Context, target: &Target, state: &State) {
+        let (width, height) = (target.width as f32, target.height as f32);
+        let mut projection = Mat4::orthographic_rh(0.0, width, 0.0, height, -1.0, 1.0);
+
+        if !target.top_down {
+            // Flip to compensate for the OpenGL coordinate system
+            projection = Mat4::from_rotation_x(std::f32::consts::PI) * projection;
+        }
+
+        let matrix = projection * mat4(&state.transform);
+
+        context.uniform_matrix_4_f32_slice(Some(&self.matrix), false, &matrix.to_cols_array());
+
+        let mut clip_matrices = [0.0; MAX_MASKS * 9];
+        let mut clip_rects = [0.0; MAX_MASKS * 4];
+        let mut clip_radii = [0.0; MAX_MASKS * 4];
+        let mut count = 0;
+
+        for (index, mask) in state.clip.masks().enumerate() {
+            // Map the positions of the vertices to the coordinates of the clip.
+            let clip_matrix = mat3(&state.transform.then(&mask.inverse));
+            let radii = mask.radii;
+
+            clip_matrices[index * 9..][..9].copy_from_slice(&clip_matrix.to_cols_array());
+            clip_rects[index * 4..][..4].copy_from_slice(&mask.rect);
+            clip_radii[index * 4..][..4].copy_from_slice(&[
+                radii.top_left,
+                radii.top_right,
+                radii.bottom_right,
+                radii.bottom_left,
+            ]);
+            count += 1;
+        }
+
+        context.uniform_1_f32(Some(&self.clip_count), count as f32);
+        context.uniform_matrix_3_f32_slice(Some(&self.clip_matrix), false, &clip_matrices);
+        context.uniform_4_f32_slice(Some(&self.clip_rect), &clip_rects);
+        context.uniform_4_f32_slice(Some(&self.clip_radii), &clip_radii);
+    }
+}
+
+/// Limits drawing into a target to the scissor of a clip.
+pub unsafe fn set_scissor(context: &glow::Context, target: &Target, clip: &Clip) {
+    match clip.scissor {
+        Some([left, top, right, bottom]) => {
+            // Include every pixel which is partially inside of the clip.
+            let left = left.floor().max(0.0) as i32;
+            let top = top.floor().max(0.0) as i32;
+            let right = (right.ceil() as i32).max(left);
+            let bottom = (bottom.ceil() as i32).max(top);
+
+            // The scissor starts at the first row of the framebuffer.
+            let y = match target.top_down {
+                true => top,
+                false => target.height as i32 - bottom,
+            };
+
+            context.enable(glow::SCISSOR_TEST);
+            context.scissor(left, y, right - left, bottom - top);
+        }
+
+        None => context.disable(glow::SCISSOR_TEST),
+    }
+}
+
+/// Sets how the colors of following commands are combined with the colors drawn before.
+///
+/// Colors are premultiplied by their alpha. Multiplying ignores the alpha of the colors drawn before, so it
+/// is only exact over opaque colors.
+pub unsafe fn set_blend_mode(context: &glow::Context, mode: BlendMode) {
+    let (source, destination) = match mode {
+        BlendMode::Normal => (glow::ONE, glow::ONE_MINUS_SRC_ALPHA),
+        BlendMode::Multiply => (glow::DST_COLOR, glow::ONE_MINUS_SRC_ALPHA),
+        BlendMode::Screen => (glow::ONE, glow::ONE_MINUS_SRC_COLOR),
+        BlendMode::Additive => (glow::ONE, glow::ONE),
+        BlendMode::SourceCopy => (glow::ONE, glow::ZERO),
+    };
+
+    context.blend_func(source, destination);
+}
+
+fn mat4(transform: &Transform) -> Mat4 {
+    let [a, b] = transform.x_axis;
+    let [c, d] = transform.y_axis;
+    let [x, y] = transform.translation;
+
+    #[rustfmt::skip]
+    let matrix = Mat4::from_cols_array(&[
+        a, b, 0.0, 0.0,
+        c, d, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        x, y, 0.0, 1.0,
+    ]);
+
+    matrix
+}
+
+fn mat3(transform: &Transform) -> Mat3 {
+    let [a, b] = transform.x_axis;
+    let [c, d] = transform.y_axis;
+    let [x, y] = transform.translation;
+
+    #[rustfmt::skip]
+    let matrix = Mat3::from_cols_array(&[
+        a, b, 0.0,
+        c, d, 0.0,
+        x, y, 1.0,
+    ]);
+
+    matrix
+}
+
+/// Returns the area containing a transformed rectangle, where both are the left, top, right and bottom edges.
+fn transform_bounds(transform: &Transform, [left, top, right, bottom]: [f32; 4]) -> [f32; 4] {
+    let corners = [[left, top], [right, top], [right, bottom], [left, bottom]]
+        .map(|corner| transform.apply(corner));
+
+    corners.iter().fold(
+        [
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ],
+        |[left, top, right, bottom], &[x, y]| {
+            [left.min(x), top.min(y), right.max(x), bottom.max(y)]
+        },
+    )
+}
+
+/// Returns the area inside of both rectangles, which is empty if the rectangles do not overlap.
+fn intersect(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    let left = a[0].max(b[0]);
+    let top = a[1].max(b[1]);
+
+    [left, top, a[2].min(b[2]).max(left), a[3].min(b[3]).max(top)]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use ::renderer::{CornerRadii, Transform};
+
+    use super::{intersect, transform_bounds, Clip, MAX_MASKS};
+
+    /// Returns whether a point in physical pixels is inside of a clip, as the scissor and `clip.frag` clip it.
+    fn contains(clip: &Clip, [x, y]: [f32; 2]) -> bool {
+        let scissor = clip.scissor.is_none_or(|[left, top, right, bottom]| {
+            x >= left && x < right && y >= top && y < bottom
+        });
+
+        scissor
+            && clip.masks().all(|mask| {
+                let [x, y] = mask.inverse.apply([x, y]);
+                let [left, top, width, height] = mask.rect;
+                let (half_width, half_height) = (width / 2.0, height / 2.0);
+                let (x, y) = (x - left - half_width, y - top - half_height);
+
+                let radii = mask.radii;
+                let radius = match (x < 0.0, y < 0.0) {
+                    (true, true) => radii.top_left,
+                    (false, true) => radii.top_right,
+                    (false, false) => radii.bottom_right,
+                    (true, false) => radii.bottom_left,
+                };
+                let radius = radius.min(half_width.min(half_height));
+
+                let (qx, qy) = (
+                    x.abs() - half_width + radius,
+                    y.abs() - half_height + radius,
+                );
+                let distance = qx.max(qy).min(0.0) + qx.max(0.0).hypot(qy.max(0.0)) - radius;
+                distance < 0.0
+            })
+    }
+
+    fn assert_near(a: [f32; 4], b: [f32; 4]) {
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4),
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        // Rotating clockwise by a quarter turn around the origin moves the rectangle left of the origin.
+        let rotate = Transform::rotation(FRAC_PI_2);
+        assert_near(
+            transform_bounds(&rotate, [0.0, 0.0, 10.0, 20.0]),
+            [-20.0, 0.0, 0.0, 10.0],
+        );
+
+        assert_eq!(
+            intersect([0.0, 0.0, 10.0, 10.0], [5.0, -5.0, 20.0, 5.0]),
+            [5.0, 0.0, 10.0, 5.0]
+        );
+
+        // Rectangles which do not overlap intersect in an empty area.
+        let [left, top, right, bottom] =
+            intersect([0.0, 0.0, 10.0, 10.0], [20.0, 20.0, 30.0, 30.0]);
+        assert!(left >= right && top >= bottom);
+    }
+
+    #[test]
+    fn nested_clips() {
+        let clip = Clip::default().push(
+            &Transform::IDENTITY,
+            [0.0, 0.0, 100.0, 100.0],
+            CornerRadii::ZERO,
+        );
+        assert_eq!(clip.scissor, Some([0.0, 0.0, 100.0, 100.0]));
+        assert_eq!(clip.masks().count(), 0);
+
+        // Nested clips intersect, and rectangles in physical pixels are applied by the scissor alone.
+        let translate = Transform::translation(50.0, 25.0);
+        let inner = clip.push(&translate, [0.0, 0.0, 100.0, 50.0], CornerRadii::ZERO);
+        assert_eq!(inner.scissor, Some([50.0, 25.0, 100.0, 75.0]));
+        assert_eq!(inner.masks().count(), 0);
+        assert!(!inner.is_empty());
+
+        // Rounded clips are applied by a mask in the coordinates they were pushed in.
+        let rounded = inner.push(
+            &translate,
+            [10.0, 10.0, 20.0, 20.0],
+            CornerRadii::uniform(4.0),
+        );
+        assert_eq!(rounded.scissor, Some([60.0, 35.0, 80.0, 55.0]));
+        let mask = rounded.masks().next().unwrap();
+        assert_eq!(mask.rect, [10.0, 10.0, 20.0, 20.0]);
+        assert_eq!(mask.inverse.apply([60.0, 35.0]), [10.0, 10.0]);
+
+        // Masks are kept from the outermost to the innermost.
+        let rotated = rounded.push(
+            &Transform::rotation(0.1),
+            [0.0, 0.0, 100.0, 100.0],
+            CornerRadii::ZERO,
+        );
+        let rects = rotated.masks().map(|mask| mask.rect).collect::<Vec<_>>();
+        assert_eq!(rects, [[10.0, 10.0, 20.0, 20.0], [0.0, 0.0, 100.0, 100.0]]);
+        assert_eq!(rotated.scissor, rounded.scissor);
+
+        // Past the limit, the outermost mask is dropped and only applied by the scissor.
+        let deepest = (0..MAX_MASKS).fold(rotated, |clip, index| {
+            let inset = (index + 1) as f32;
+            clip.push(
+                &Transform::IDENTITY,
+                [inset, inset, 100.0, 100.0],
+                CornerRadii::uniform(1.0),
+            )
+        });
+        assert_eq!(deepest.masks().count(), MAX_MASKS);
+        assert_eq!(
+            deepest.masks().next().unwrap().rect,
+            [1.0, 1.0, 100.0, 100.0]
+        );
+
+        // Clips which do not overlap clip everything.
+        let outside = inner.push(
+            &Transform::IDENTITY,
+            [0.0, 0.0, 10.0, 10.0],
+            CornerRadii::ZERO,
+        );
+        assert!(outside.is_empty());
+    }
+
+    #[test]
+    fn nested_rounded_clips() {
+        let outer = Clip::default().push(
+            &Transform::IDENTITY,
+            [0.0, 0.0, 100.0, 100.0],
+            CornerRadii::uniform(40.0),
+        );
+        let inner = outer.push(
+            &Transform::IDENTITY,
+            [0.0, 0.0, 60.0, 60.0],
+            CornerRadii::uniform(10.0),
+        );
+
+        // The corner of the inner clip is cut off by the larger corner of the outer clip.
+        let alone = Clip::default().push(
+            &Transform::IDENTITY,
+            [0.0, 0.0, 60.0, 60.0],
+            CornerRadii::uniform(10.0),
+        );
+        assert!(contains(&alone, [8.0, 8.0]));
+        assert!(!contains(&inner, [8.0, 8.0]));
+        assert!(contains(&inner, [30.0, 30.0]));
+
+        // Both clips cut off the corner of the scissor.
+        assert!(!contains(&alone, [1.0, 1.0]));
+        assert!(!contains(&inner, [1.0, 1.0]));
+    }
+}
@@ -1,18 +1,35 @@
-use glam::Mat4;
+use ::renderer::{Indices, Mesh};
 use glow::HasContext;
 
-use crate::{create_program, create_shader, ShaderType};
+use crate::{create_program, create_shader, state, ShaderType};
 
-#[derive(Debug, Clone)]
 pub struct Pipeline {
     pub program: glow::Program,
     pub attrib_position: u32,
-    pub uniform_matrix: glow::UniformLocation,
-    pub uniform_color: glow::UniformLocation,
+    pub attrib_color: u32,
+    pub attrib_coords: u32,
+    pub uniform_image: glow::UniformLocation,
+    pub uniform_textured: glow::UniformLocation,
+    pub uniform_mask: glow::UniformLocation,
+    /// Whether 32 bit indices are supported.
+    ///
+    /// OpenGL ES 2.0 only supports 32 bit indices with `GL_OES_element_index_uint`.
+    pub u32_indices: bool,
+    pub state: state::Uniforms,
 }
 
-const VERTEX_SHADER: &str = include_str!("shader/triangle.vert");
-const FRAGMENT_SHADER: &str = include_str!("shader/triangle.frag");
+/// The number of floats of each vertex, the position followed by the color and the position within the image.
+const VERTEX_SIZE: usize = 8;
+
+const VERTEX_SHADER: &str = concat!(
+    include_str!("shader/triangle.vert"),
+    include_str!("shader/clip.vert")
+);
+const FRAGMENT_SHADER: &str = concat!(
+    include_str!("shader/triangle.frag"),
+    include_str!("shader/distance.frag"),
+    include_str!("shader/clip.frag")
+);
 
 impl Pipeline {
     pub unsafe fn new(context: &glow::Context) -> Result<Self, String> {
@@ -25,77 +42,225 @@ impl Pipeline {
         }
 
         let fragment = fragment.unwrap();
-        let program = create_program(context, &[vertex, fragment], &[(0, "position")])?;
+        let program = create_program(
+            context,
+            &[vertex, fragment],
+            &[(0, "position"), (1, "color"), (2, "coordinates")],
+        )?;
 
         // Get the attribute location of the position
         let attrib_position = context
             .get_attrib_location(program, "position")
             .ok_or("Failed to get location of attribute \"position\"")?;
 
-        let uniform_matrix = context
-            .get_uniform_location(program, "matrix")
-            .ok_or("Failed to get location of uniform \"matrix\"")?;
+        let attrib_color = context
+            .get_attrib_location(program, "color")
+            .ok_or("Failed to get location of attribute \"color\"")?;
+
+        let attrib_coords = context
+            .get_attrib_location(program, "coordinates")
+            .ok_or("Failed to get location of attribute \"coordinates\"")?;
 
-        // Uniform for color
-        let uniform_color = context
-            .get_uniform_location(program, "u_color")
-            .ok_or("Failed to get location of uniform \"u_color\"")?;
+        let uniform = |name: &str| {
+            context
+                .get_uniform_location(program, name)
+                .ok_or_else(|| format!("Failed to get location of uniform \"{}\"", name))
+        };
+
+        let version = context.version();
+        let u32_indices = !version.is_embedded
+            || version.major >= 3
+            || context
+                .supported_extensions()
+                .contains("GL_OES_element_index_uint");
 
         Ok(Self {
             program,
             attrib_position,
-            uniform_matrix,
-            uniform_color,
+            attrib_color,
+            attrib_coords,
+            uniform_image: uniform("image")?,
+            uniform_textured: uniform("textured")?,
+            uniform_mask: uniform("mask")?,
+            u32_indices,
+            state: state::Uniforms::new(context, program)?,
         })
     }
 
+    /// Draws the triangles of a mesh in physical pixels, with the texture of an image if the mesh has positions
+    /// within the image. A mask image is drawn as white with the alpha of the image.
+    ///
     /// SAFETY: The pipeline must be bound.
-    pub unsafe fn draw(&self, context: &glow::Context, verts: &[f32], color: [f32; 4]) {
-        context.uniform_4_f32_slice(Some(&self.uniform_color), &color);
+    pub unsafe fn draw(
+        &self,
+        context: &glow::Context,
+        mesh: &Mesh,
+        texture: Option<glow::Texture>,
+        mask: bool,
+    ) {
+        if mesh.indices.is_empty() {
+            return;
+        }
+
+        let uvs = texture.and(mesh.uvs.as_deref());
+        let mut verts = Vec::with_capacity(mesh.len() * VERTEX_SIZE);
+
+        for (index, (position, color)) in mesh.positions.iter().zip(&mesh.colors).enumerate() {
+            verts.extend_from_slice(position);
+            verts.extend_from_slice(color);
+            verts.extend_from_slice(&uvs.map_or([0.0; 2], |uvs| uvs[index]));
+        }
+
+        match texture {
+            Some(texture) if uvs.is_some() => {
+                context.bind_texture(glow::TEXTURE_2D, Some(texture));
+                context.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MIN_FILTER,
+                    glow::LINEAR as i32,
+                );
+                context.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MAG_FILTER,
+                    glow::LINEAR as i32,
+                );
+                // Positions outside of the image sample the closest edge rather than wrapping around.
+                context.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_WRAP_S,
+                    glow::CLAMP_TO_EDGE as i32,
+                );
+                context.tex_parameter_i32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_WRAP_T,
+                    glow::CLAMP_TO_EDGE as i32,
+                );
+                context.uniform_1_f32(Some(&self.uniform_textured), 1.0);
+                context.uniform_1_f32(Some(&self.uniform_mask), mask as u32 as f32);
+            }
+
+            _ => context.uniform_1_f32(Some(&self.uniform_textured), 0.0),
+        }
 
         // Allocate a buffer to draw into
         // TODO: Preallocation of buffers.
         let buffer = context.create_buffer().unwrap();
         context.bind_buffer(glow::ARRAY_BUFFER, Some(buffer));
-        context.buffer_data_u8_slice(
-            glow::ARRAY_BUFFER,
-            bytemuck::cast_slice(verts),
-            glow::STATIC_DRAW,
-        );
-
-        context.vertex_attrib_pointer_f32(
-            0,
-            3,
-            glow::FLOAT,
-            false,
-            (std::mem::size_of::<f32>() * 3) as _,
-            0,
-        );
-        context.draw_arrays(glow::TRIANGLES, 0, (verts.len() / 3) as i32);
+
+        let stride = (std::mem::size_of::<f32>() * VERTEX_SIZE) as i32;
+        let attributes = |context: &glow::Context| {
+            context.vertex_attrib_pointer_f32(
+                self.attrib_position,
+                2,
+                glow::FLOAT,
+                false,
+                stride,
+                0,
+            );
+            context.vertex_attrib_pointer_f32(
+                self.attrib_color,
+                4,
+                glow::FLOAT,
+                false,
+                stride,
+                (std::mem::size_of::<f32>() * 2) as _,
+            );
+            context.vertex_attrib_pointer_f32(
+                self.attrib_coords,
+                2,
+                glow::FLOAT,
+                false,
+                stride,
+                (std::mem::size_of::<f32>() * 6) as _,
+            );
+        };
+
+        match &mesh.indices {
+            Indices::U32(indices) if !self.u32_indices => {
+                // Without support for 32 bit indices, copy the vertices of each triangle instead.
+                let expanded = indices
+                    .iter()
+                    .flat_map(|&index| {
+                        let start = index as usize * VERTEX_SIZE;
+                        &verts[start..start + VERTEX_SIZE]
+                    })
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                context.buffer_data_u8_slice(
+                    glow::ARRAY_BUFFER,
+                    bytemuck::cast_slice(&expanded),
+                    glow::STREAM_DRAW,
+                );
+                attributes(context);
+                context.draw_arrays(glow::TRIANGLES, 0, indices.len() as i32);
+            }
+
+            indices => {
+                context.buffer_data_u8_slice(
+                    glow::ARRAY_BUFFER,
+                    bytemuck::cast_slice(&verts),
+                    glow::STREAM_DRAW,
+                );
+                attributes(context);
+
+                let index_buffer = context.create_buffer().unwrap();
+                context.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+
+                let ty = match indices {
+                    Indices::U16(indices) => {
+                        context.buffer_data_u8_slice(
+                            glow::ELEMENT_ARRAY_BUFFER,
+                            bytemuck::cast_slice(indices),
+                            glow::STREAM_DRAW,
+                        );
+                        glow::UNSIGNED_SHORT
+                    }
+
+                    Indices::U32(indices) => {
+                        context.buffer_data_u8_slice(
+                            glow::ELEMENT_ARRAY_BUFFER,
+                            bytemuck::cast_slice(indices),
+                            glow::STREAM_DRAW,
+                        );
+                        glow::UNSIGNED_INT
+                    }
+                };
+
+                context.draw_elements(glow::TRIANGLES, indices.len() as i32, ty, 0);
+                context.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+                context.delete_buffer(index_buffer);
+            }
+        }
 
         context.delete_buffer(buffer);
+
+        if uvs.is_some() {
+            context.bind_texture(glow::TEXTURE_2D, None);
+        }
     }
 }
 
 impl crate::Pipeline for Pipeline {
-    unsafe fn bind(&self, context: &glow::Context, width: u32, height: u32) {
+    unsafe fn bind(&self, context: &glow::Context) {
         context.use_program(Some(self.program));
         context.enable_vertex_attrib_array(self.attrib_position);
-        // Set the color to a default value
-        context.uniform_4_f32_slice(Some(&self.uniform_color), &[1.0, 1.0, 1.0, 1.0]);
-
-        let matrix = Mat4::orthographic_rh(0.0, width as f32, 0.0, height as f32, -1.0, 1.0);
+        context.enable_vertex_attrib_array(self.attrib_color);
+        context.enable_vertex_attrib_array(self.attrib_coords);
 
-        // Flip to compensate for the OpenGL coordinate system
-        let matrix = Mat4::from_rotation_x(std::f32::consts::PI) * matrix;
-        let array = matrix.to_cols_array();
-
-        // Set the size of the viewport
-        context.uniform_matrix_4_f32_slice(Some(&self.uniform_matrix), false, &array);
+        // Sample the image from the first texture unit.
+        context.active_texture(glow::TEXTURE0);
+        context.uniform_1_i32(Some(&self.uniform_image), 0);
     }
 
     unsafe fn unbind(&self, context: &glow::Context) {
         context.disable_vertex_attrib_array(self.attrib_position);
+        context.disable_vertex_attrib_array(self.attrib_color);
+        context.disable_vertex_attrib_array(self.attrib_coords);
         context.use_program(None);
     }
+
+    fn state(&self) -> &state::Uniforms {
+        &self.state
+    }
 }
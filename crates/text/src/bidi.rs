@@ -0,0 +1,312 @@
+//! Bidirectional text.
+//!
+//! Text mixing left-to-right scripts such as Latin with right-to-left scripts such as Arabic and Hebrew is stored
+//! in logical order, the order in which it is read. The [Unicode Bidirectional Algorithm] resolves the direction of
+//! each character and reorders the runs of a line into visual order, the order in which it is displayed.
+//!
+//! Lines must be broken in logical order before reordering, since the visual order of a line depends on which
+//! characters are on the line.
+//!
+//! [Unicode Bidirectional Algorithm]: https://www.unicode.org/reports/tr9/
+
+use std::ops::Range;
+
+use swash::{
+    shape::{self, ShapeContext},
+    text::{Codepoint, Script},
+    FontRef, GlyphId,
+};
+use unicode_bidi::{BidiInfo, Level};
+
+/// The direction of text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Direction {
+    fn from_level(level: Level) -> Self {
+        if level.is_rtl() {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
+        }
+    }
+}
+
+impl From<Direction> for shape::Direction {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::LeftToRight => shape::Direction::LeftToRight,
+            Direction::RightToLeft => shape::Direction::RightToLeft,
+        }
+    }
+}
+
+/// A paragraph of text, separated from the next paragraph by a paragraph separator such as a newline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraph {
+    /// The range of the paragraph in the text, including the separator.
+    pub range: Range<usize>,
+    /// The base direction of the paragraph.
+    pub direction: Direction,
+}
+
+/// A sequence of characters with the same embedding level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    /// The range of the run in the text.
+    pub range: Range<usize>,
+    /// The embedding level of the run. Odd levels are right-to-left.
+    pub level: u8,
+}
+
+impl Run {
+    pub fn direction(&self) -> Direction {
+        if self.level % 2 == 1 {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
+        }
+    }
+}
+
+/// Text with the directions of it's characters resolved.
+pub struct BidiText<'a> {
+    info: BidiInfo<'a>,
+}
+
+impl<'a> BidiText<'a> {
+    /// Resolves the directions of the characters in the text.
+    ///
+    /// The base direction of each paragraph is `direction`, or determined by the first strong character of the
+    /// paragraph if [`None`].
+    pub fn new(text: &'a str, direction: Option<Direction>) -> Self {
+        let level = direction.map(|direction| match direction {
+            Direction::LeftToRight => Level::ltr(),
+            Direction::RightToLeft => Level::rtl(),
+        });
+
+        Self {
+            info: BidiInfo::new(text, level),
+        }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.info.text
+    }
+
+    pub fn paragraphs(&self) -> impl Iterator<Item = Paragraph> + '_ {
+        self.info.paragraphs.iter().map(|paragraph| Paragraph {
+            range: paragraph.range.clone(),
+            direction: Direction::from_level(paragraph.level),
+        })
+    }
+
+    /// Returns the direction of the character starting at a byte offset.
+    ///
+    /// # Panics
+    ///
+    /// If the offset is out of bounds.
+    pub fn direction_at(&self, offset: usize) -> Direction {
+        Direction::from_level(self.info.levels[offset])
+    }
+
+    /// Returns the runs of a line in visual order, from left to right.
+    ///
+    /// The line should not contain a line break, except at the end.
+    ///
+    /// # Panics
+    ///
+    /// If the line is out of bounds or does not start and end on character boundaries.
+    pub fn visual_runs(&self, line: Range<usize>) -> Vec<Run> {
+        assert!(line.end <= self.text().len(), "line out of bounds");
+        let mut visual = Vec::new();
+
+        for paragraph in &self.info.paragraphs {
+            let start = line.start.max(paragraph.range.start);
+            let end = line.end.min(paragraph.range.end);
+
+            if start >= end {
+                continue;
+            }
+
+            let (levels, runs) = self.info.visual_runs(paragraph, start..end);
+            visual.extend(runs.into_iter().map(|range| Run {
+                level: levels[range.start].number(),
+                range,
+            }));
+        }
+
+        visual
+    }
+}
+
+/// A glyph positioned on a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedGlyph {
+    pub id: GlyphId,
+    /// The horizontal position of the glyph relative to the start of the line.
+    pub x: f32,
+    /// The vertical offset of the glyph from the baseline.
+    pub y: f32,
+    /// The range of the text the cluster containing the glyph was shaped from.
+    pub source: Range<usize>,
+}
+
+/// Shapes a line of text, returning it's glyphs in visual order from left to right.
+///
+/// Each run of the line is shaped in it's direction, split further where the script changes so joining scripts
+/// such as Arabic are shaped correctly.
+pub fn shape_line(
+    context: &mut ShapeContext,
+    font: FontRef<'_>,
+    size: f32,
+    text: &BidiText<'_>,
+    line: Range<usize>,
+) -> Vec<PositionedGlyph> {
+    let mut glyphs = Vec::new();
+    let mut pen = 0.0;
+
+    for run in text.visual_runs(line) {
+        let direction = run.direction();
+        let mut segments = script_segments(text.text(), run.range);
+
+        if direction == Direction::RightToLeft {
+            segments.reverse();
+        }
+
+        for (range, script) in segments {
+            let mut shaper = context
+                .builder(font)
+                .size(size)
+                .script(script)
+                .direction(direction.into())
+                .build();
+            shaper.add_str(&text.text()[range.clone()]);
+
+            let mut clusters = Vec::new();
+            shaper.shape_with(|cluster| {
+                let source = cluster.source.to_range();
+                let source = range.start + source.start..range.start + source.end;
+                clusters.push((source, cluster.glyphs.to_vec()));
+            });
+
+            // The shaper returns clusters in logical order. Glyphs within a cluster remain in logical order so marks
+            // are placed correctly.
+            if direction == Direction::RightToLeft {
+                clusters.reverse();
+            }
+
+            for (source, cluster) in clusters {
+                for glyph in cluster {
+                    glyphs.push(PositionedGlyph {
+                        id: glyph.id,
+                        x: pen + glyph.x,
+                        y: glyph.y,
+                        source: source.clone(),
+                    });
+                    pen += glyph.advance;
+                }
+            }
+        }
+    }
+
+    glyphs
+}
+
+/// Splits a range of text where the script changes, in logical order.
+///
+/// Characters used by multiple scripts, such as spaces and punctuation, belong to the surrounding script.
+fn script_segments(text: &str, range: Range<usize>) -> Vec<(Range<usize>, Script)> {
+    let mut segments: Vec<(Range<usize>, Script)> = Vec::new();
+
+    for (offset, char) in text[range.clone()].char_indices() {
+        let offset = range.start + offset;
+        let script = char.script();
+
+        match segments.last_mut() {
+            Some((segment, current))
+                if script == *current || matches!(script, Script::Common | Script::Inherited) =>
+            {
+                segment.end = offset + char.len_utf8();
+            }
+
+            // Characters before the first character with a script take the script of that character.
+            Some((segment, current)) if matches!(current, Script::Common | Script::Inherited) => {
+                segment.end = offset + char.len_utf8();
+                *current = script;
+            }
+
+            _ => segments.push((offset..offset + char.len_utf8(), script)),
+        }
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use swash::text::Script;
+
+    use super::{script_segments, BidiText, Direction, Run};
+
+    #[test]
+    fn visual_runs() {
+        // "abc " followed by two Hebrew words and " def".
+        let text = "abc \u{5d0}\u{5d1} \u{5d2}\u{5d3} def";
+        let bidi = BidiText::new(text, None);
+
+        let paragraphs = bidi.paragraphs().collect::<Vec<_>>();
+        assert_eq!(paragraphs.len(), 1);
+        assert_eq!(paragraphs[0].direction, Direction::LeftToRight);
+        assert_eq!(bidi.direction_at(4), Direction::RightToLeft);
+
+        let runs = bidi.visual_runs(0..text.len());
+        assert_eq!(
+            runs,
+            [
+                Run {
+                    range: 0..4,
+                    level: 0
+                },
+                Run {
+                    range: 4..13,
+                    level: 1
+                },
+                Run {
+                    range: 13..17,
+                    level: 0
+                },
+            ]
+        );
+        assert_eq!(runs[1].direction(), Direction::RightToLeft);
+
+        // In a right-to-left paragraph, the runs are displayed from right to left.
+        let bidi = BidiText::new(text, Some(Direction::RightToLeft));
+        let runs = bidi.visual_runs(0..text.len());
+        assert_eq!(
+            runs.iter().map(|run| run.range.clone()).collect::<Vec<_>>(),
+            [14..17, 3..14, 0..3]
+        );
+
+        // Lines are reordered independently.
+        assert_eq!(
+            bidi.visual_runs(4..13),
+            [Run {
+                range: 4..13,
+                level: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn scripts() {
+        let text = "(\u{627}\u{644}) abc";
+        let segments = script_segments(text, 0..text.len());
+
+        assert_eq!(segments, [(0..7, Script::Arabic), (7..10, Script::Latin)]);
+    }
+}
@@ -1 +1,3 @@
+pub mod bidi;
 mod font;
+pub mod raster;
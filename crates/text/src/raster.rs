@@ -0,0 +1,148 @@
+//! Rasterization of glyphs.
+//!
+//! Text is laid out in logical units, but glyphs are rasterized in physical pixels so they remain sharp at any scale
+//! factor. A [`GlyphCache`] rasterizes glyphs for one scale factor and discards them when the scale factor changes,
+//! such as when a window moves to an output with a different scale factor.
+
+use std::collections::HashMap;
+
+use swash::{
+    scale::{image::Image, Render, ScaleContext, Source, StrikeWith},
+    zeno::{Format, Vector},
+    FontRef, GlyphId,
+};
+
+/// The number of horizontal subpixel positions glyphs are rasterized at.
+const SUBPIXEL_POSITIONS: f32 = 4.0;
+
+/// Identifies a rasterized glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    font: u64,
+    glyph: GlyphId,
+    /// The font size in physical pixels, as the bits of the float.
+    size: u32,
+    /// The horizontal subpixel position.
+    subpixel: u8,
+}
+
+/// Rasterizes glyphs at the scale factor of a surface and caches the results.
+pub struct GlyphCache {
+    context: ScaleContext,
+    glyphs: HashMap<GlyphKey, Option<Image>>,
+    scale_factor: f32,
+}
+
+impl GlyphCache {
+    pub fn new(scale_factor: f32) -> Self {
+        Self {
+            context: ScaleContext::new(),
+            glyphs: HashMap::new(),
+            scale_factor,
+        }
+    }
+
+    /// Returns the number of physical pixels per logical unit glyphs are rasterized for.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Sets the number of physical pixels per logical unit glyphs are rasterized for.
+    ///
+    /// Glyphs rasterized at the previous scale factor are discarded, so they are rasterized again at the new
+    /// resolution the next time they are drawn.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if self.scale_factor != scale_factor {
+            self.scale_factor = scale_factor;
+            self.glyphs.clear();
+        }
+    }
+
+    /// Returns a glyph of a font size in logical units, rasterized in physical pixels.
+    ///
+    /// `x` is the horizontal position of the glyph in logical units, which selects the subpixel offset the glyph is
+    /// rasterized at. The placement of the returned image is in physical pixels relative to the whole pixel
+    /// containing the position.
+    ///
+    /// Returns [`None`] if the glyph has no image, such as a space.
+    pub fn rasterize(
+        &mut self,
+        font: FontRef<'_>,
+        glyph: GlyphId,
+        size: f32,
+        x: f32,
+    ) -> Option<&Image> {
+        let size = size * self.scale_factor;
+        let x = x * self.scale_factor;
+        let subpixel = ((x - x.floor()) * SUBPIXEL_POSITIONS).floor() as u8;
+
+        let key = GlyphKey {
+            font: font.key.value(),
+            glyph,
+            size: size.to_bits(),
+            subpixel,
+        };
+
+        let context = &mut self.context;
+        self.glyphs
+            .entry(key)
+            .or_insert_with(|| {
+                let mut scaler = context.builder(font).size(size).hint(true).build();
+
+                Render::new(&[
+                    Source::ColorBitmap(StrikeWith::BestFit),
+                    Source::ColorOutline(0),
+                    Source::Outline,
+                ])
+                .format(Format::Alpha)
+                .offset(Vector::new(subpixel as f32 / SUBPIXEL_POSITIONS, 0.0))
+                .render(&mut scaler, glyph)
+            })
+            .as_ref()
+    }
+
+    /// Returns the number of cached glyphs.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swash::FontDataRef;
+
+    use super::GlyphCache;
+
+    #[test]
+    fn scale_factor_change() {
+        // A font with a single square glyph for '#'.
+        let data = include_bytes!("test.ttf");
+        let fonts = FontDataRef::new(data).unwrap();
+        let font = fonts.get(0).unwrap();
+        let glyph = font.charmap().map('#');
+
+        let mut cache = GlyphCache::new(1.0);
+        let width = cache.rasterize(font, glyph, 16.0, 0.0).unwrap().placement.width;
+
+        // Glyphs at another subpixel position are cached separately, the same position is reused.
+        cache.rasterize(font, glyph, 16.0, 0.5);
+        cache.rasterize(font, glyph, 16.0, 10.0);
+        assert_eq!(cache.len(), 2);
+
+        // Setting the same scale factor keeps the glyphs.
+        cache.set_scale_factor(1.0);
+        assert_eq!(cache.len(), 2);
+
+        // Changing the scale factor evicts every glyph, and glyphs are rasterized again at the new resolution.
+        cache.set_scale_factor(2.0);
+        assert!(cache.is_empty());
+
+        let scaled = cache.rasterize(font, glyph, 16.0, 0.0).unwrap().placement.width;
+        assert!(scaled > width);
+        assert_eq!(cache.len(), 1);
+    }
+}